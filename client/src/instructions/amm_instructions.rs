@@ -1,17 +1,29 @@
 use anchor_client::{Client, Cluster};
 use anyhow::Ok;
 use anyhow::Result;
-use solana_sdk::{instruction::Instruction, pubkey::Pubkey, system_program};
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    system_program,
+};
+use spl_associated_token_account::get_associated_token_address_with_program_id;
 
 use cpmm_lp_lock::accounts as raydium_cp_accounts;
 use cpmm_lp_lock::instruction as raydium_cp_instructions;
+use cpmm_lp_lock::instructions::LockMode;
 use cpmm_lp_lock::raydium_cpmm;
+use cpmm_lp_lock::states::{
+    ActiveLockPage, FeeSplitRecipient, LockMetadata, RegistryStatus, ACTIVE_LOCK_PAGE_SEED,
+    GLOBAL_LOCK_COUNTER_SEED, LOCK_LISTING_SEED, LP_LOCK_COUNTER_SEED, POOL_LOCK_STATS_SEED,
+    REGISTRY_CONFIG_SEED, REGISTRY_ENTRY_SEED, REWARD_RECEIPT_SEED,
+};
 use cpmm_lp_lock::vault_and_lp_mint_auth;
-use cpmm_lp_lock::AUTH_SEED;
+use cpmm_lp_lock::{AUTH_SEED, LOCK_FEE_VAULT_SEED};
 use std::rc::Rc;
 
 use super::super::{read_keypair_file, ClientConfig};
 
+#[allow(clippy::too_many_arguments)]
 pub fn lock_lp_instr(
     config: &ClientConfig,
     pool_id: Pubkey,
@@ -19,11 +31,226 @@ pub fn lock_lp_instr(
     token_lp_mint: Pubkey,
     lp_lock_counter: Pubkey,
     user_lp_lock: Pubkey,
+    active_lock_page: Pubkey,
+    lock_id_index: Pubkey,
     lp_lock_vault: Pubkey,
     token_0_vault: Pubkey,
     token_1_vault: Pubkey,
+    vault_0_mint: Pubkey,
+    vault_1_mint: Pubkey,
+    reward_pool: Option<Pubkey>,
+    lp_token_amount: u64,
+    lock_duration: u64,
+    lock_mode: LockMode,
+    metadata: LockMetadata,
+    beneficiaries: Vec<FeeSplitRecipient>,
+) -> Result<Vec<Instruction>> {
+    let payer = read_keypair_file(&config.payer_path)?;
+    let url = Cluster::Custom(config.http_url.clone(), config.ws_url.clone());
+    // Client.
+    let client = Client::new(url, Rc::new(payer));
+    let program = client.program(config.cpmm_lp_lock_program)?;
+
+    let (lock_vault_authority, __bump) =
+        Pubkey::find_program_address(&[AUTH_SEED.as_bytes()], &program.id());
+    let (pool_lock_stats, __bump) = Pubkey::find_program_address(
+        &[POOL_LOCK_STATS_SEED.as_bytes(), token_lp_mint.as_ref()],
+        &program.id(),
+    );
+    let (global_lock_counter, __bump) =
+        Pubkey::find_program_address(&[GLOBAL_LOCK_COUNTER_SEED.as_bytes()], &program.id());
+    let (registry_config, __bump) =
+        Pubkey::find_program_address(&[REGISTRY_CONFIG_SEED.as_bytes()], &program.id());
+    let registry_entry = |key: &Pubkey| {
+        Pubkey::find_program_address(
+            &[REGISTRY_ENTRY_SEED.as_bytes(), key.as_ref()],
+            &program.id(),
+        )
+        .0
+    };
+
+    let request = program.request().accounts(raydium_cp_accounts::LockLp {
+        owner: program.payer(),
+        payer: program.payer(),
+        authority: vault_and_lp_mint_auth::id(),
+        lock_vault_authority,
+        pool_state: pool_id,
+        owner_lp_token: user_token_lp_account,
+        token_program: spl_token::id(),
+        lp_mint: token_lp_mint,
+        lp_lock_counter,
+        pool_lock_stats,
+        global_lock_counter,
+        lock_id_index,
+        user_lp_lock,
+        active_lock_page,
+        lp_lock_vault,
+        token_0_vault,
+        token_1_vault,
+        vault_0_mint,
+        vault_1_mint,
+        registry_config,
+        pool_registry_entry: registry_entry(&pool_id),
+        vault_0_mint_registry_entry: registry_entry(&vault_0_mint),
+        vault_1_mint_registry_entry: registry_entry(&vault_1_mint),
+        reward_pool,
+        system_program: system_program::id(),
+    });
+    let amount = lp_token_amount;
+    let has_options = metadata != LockMetadata::default() || !beneficiaries.is_empty();
+    let instructions = match lock_mode {
+        LockMode::Timed | LockMode::Permanent if has_options => request
+            .args(raydium_cp_instructions::LockLpWithOptions {
+                amount,
+                lock_duration,
+                lock_permanent: lock_mode == LockMode::Permanent,
+                metadata,
+                beneficiaries,
+            })
+            .instructions()?,
+        LockMode::Timed => request
+            .args(raydium_cp_instructions::LockLp {
+                amount,
+                lock_duration,
+            })
+            .instructions()?,
+        LockMode::FromOpenTime => request
+            .args(raydium_cp_instructions::LockLpFromOpenTime {
+                amount,
+                lock_duration,
+                metadata,
+                beneficiaries,
+            })
+            .instructions()?,
+        LockMode::Rolling => request
+            .args(raydium_cp_instructions::LockLpRolling {
+                amount,
+                lock_duration,
+                metadata,
+                beneficiaries,
+            })
+            .instructions()?,
+        LockMode::Permanent => request
+            .args(raydium_cp_instructions::LockLpPermanent { amount })
+            .instructions()?,
+    };
+    Ok(instructions)
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn lock_lp_perm_instr(
+    config: &ClientConfig,
+    pool_id: Pubkey,
+    user_token_lp_account: Pubkey,
+    token_lp_mint: Pubkey,
+    lp_lock_counter: Pubkey,
+    user_lp_lock: Pubkey,
+    active_lock_page: Pubkey,
+    lock_id_index: Pubkey,
+    lp_lock_vault: Pubkey,
+    token_0_vault: Pubkey,
+    token_1_vault: Pubkey,
+    vault_0_mint: Pubkey,
+    vault_1_mint: Pubkey,
+    reward_pool: Option<Pubkey>,
+    lp_token_amount: u64,
+    metadata: LockMetadata,
+) -> Result<Vec<Instruction>> {
+    let payer = read_keypair_file(&config.payer_path)?;
+    let url = Cluster::Custom(config.http_url.clone(), config.ws_url.clone());
+    // Client.
+    let client = Client::new(url, Rc::new(payer));
+    let program = client.program(config.cpmm_lp_lock_program)?;
+
+    let (lock_vault_authority, __bump) =
+        Pubkey::find_program_address(&[AUTH_SEED.as_bytes()], &program.id());
+    let (pool_lock_stats, __bump) = Pubkey::find_program_address(
+        &[POOL_LOCK_STATS_SEED.as_bytes(), token_lp_mint.as_ref()],
+        &program.id(),
+    );
+    let (global_lock_counter, __bump) =
+        Pubkey::find_program_address(&[GLOBAL_LOCK_COUNTER_SEED.as_bytes()], &program.id());
+    let (registry_config, __bump) =
+        Pubkey::find_program_address(&[REGISTRY_CONFIG_SEED.as_bytes()], &program.id());
+    let registry_entry = |key: &Pubkey| {
+        Pubkey::find_program_address(
+            &[REGISTRY_ENTRY_SEED.as_bytes(), key.as_ref()],
+            &program.id(),
+        )
+        .0
+    };
+
+    let request = program.request().accounts(raydium_cp_accounts::LockLp {
+        owner: program.payer(),
+        payer: program.payer(),
+        authority: vault_and_lp_mint_auth::id(),
+        lock_vault_authority,
+        pool_state: pool_id,
+        owner_lp_token: user_token_lp_account,
+        token_program: spl_token::id(),
+        lp_mint: token_lp_mint,
+        lp_lock_counter,
+        pool_lock_stats,
+        global_lock_counter,
+        lock_id_index,
+        user_lp_lock,
+        active_lock_page,
+        lp_lock_vault,
+        token_0_vault,
+        token_1_vault,
+        vault_0_mint,
+        vault_1_mint,
+        registry_config,
+        pool_registry_entry: registry_entry(&pool_id),
+        vault_0_mint_registry_entry: registry_entry(&vault_0_mint),
+        vault_1_mint_registry_entry: registry_entry(&vault_1_mint),
+        reward_pool,
+        system_program: system_program::id(),
+    });
+    let instructions = if metadata == LockMetadata::default() {
+        request
+            .args(raydium_cp_instructions::LockLpPermanent {
+                amount: lp_token_amount,
+            })
+            .instructions()?
+    } else {
+        request
+            .args(raydium_cp_instructions::LockLpWithOptions {
+                amount: lp_token_amount,
+                lock_duration: 0,
+                lock_permanent: true,
+                metadata,
+                beneficiaries: Vec::new(),
+            })
+            .instructions()?
+    };
+    Ok(instructions)
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn deposit_and_lock_instr(
+    config: &ClientConfig,
+    pool_id: Pubkey,
+    user_token_lp_account: Pubkey,
+    token_lp_mint: Pubkey,
+    lp_lock_counter: Pubkey,
+    user_lp_lock: Pubkey,
+    active_lock_page: Pubkey,
+    lock_id_index: Pubkey,
+    lp_lock_vault: Pubkey,
+    token_0_account: Pubkey,
+    token_1_account: Pubkey,
+    token_0_vault: Pubkey,
+    token_1_vault: Pubkey,
+    vault_0_mint: Pubkey,
+    vault_1_mint: Pubkey,
+    reward_pool: Option<Pubkey>,
     lp_token_amount: u64,
+    maximum_token_0_amount: u64,
+    maximum_token_1_amount: u64,
     lock_duration: u64,
+    lock_permanent: bool,
+    metadata: LockMetadata,
 ) -> Result<Vec<Instruction>> {
     let payer = read_keypair_file(&config.payer_path)?;
     let url = Cluster::Custom(config.http_url.clone(), config.ws_url.clone());
@@ -33,43 +260,153 @@ pub fn lock_lp_instr(
 
     let (lock_vault_authority, __bump) =
         Pubkey::find_program_address(&[AUTH_SEED.as_bytes()], &program.id());
+    let (pool_lock_stats, __bump) = Pubkey::find_program_address(
+        &[POOL_LOCK_STATS_SEED.as_bytes(), token_lp_mint.as_ref()],
+        &program.id(),
+    );
+    let (global_lock_counter, __bump) =
+        Pubkey::find_program_address(&[GLOBAL_LOCK_COUNTER_SEED.as_bytes()], &program.id());
+    let (registry_config, __bump) =
+        Pubkey::find_program_address(&[REGISTRY_CONFIG_SEED.as_bytes()], &program.id());
+    let registry_entry = |key: &Pubkey| {
+        Pubkey::find_program_address(
+            &[REGISTRY_ENTRY_SEED.as_bytes(), key.as_ref()],
+            &program.id(),
+        )
+        .0
+    };
 
     let instructions = program
         .request()
-        .accounts(raydium_cp_accounts::LockLp {
+        .accounts(raydium_cp_accounts::DepositAndLock {
             owner: program.payer(),
+            payer: program.payer(),
             authority: vault_and_lp_mint_auth::id(),
             lock_vault_authority,
             pool_state: pool_id,
             owner_lp_token: user_token_lp_account,
+            token_0_account,
+            token_1_account,
+            token_0_vault,
+            token_1_vault,
             token_program: spl_token::id(),
+            token_program_2022: spl_token_2022::id(),
+            vault_0_mint,
+            vault_1_mint,
+            registry_config,
+            pool_registry_entry: registry_entry(&pool_id),
+            vault_0_mint_registry_entry: registry_entry(&vault_0_mint),
+            vault_1_mint_registry_entry: registry_entry(&vault_1_mint),
             lp_mint: token_lp_mint,
             lp_lock_counter,
             user_lp_lock,
+            active_lock_page,
+            pool_lock_stats,
+            global_lock_counter,
+            lock_id_index,
             lp_lock_vault,
-            token_0_vault,
-            token_1_vault,
+            reward_pool,
+            raydium_cpmm_program: raydium_cpmm::id(),
             system_program: system_program::id(),
         })
-        .args(raydium_cp_instructions::LockLp {
-            amount: lp_token_amount,
+        .args(raydium_cp_instructions::DepositAndLock {
+            lp_token_amount,
+            maximum_token_0_amount,
+            maximum_token_1_amount,
             lock_duration,
+            lock_permanent,
+            metadata,
         })
         .instructions()?;
     Ok(instructions)
 }
 
-pub fn lock_lp_perm_instr(
+#[allow(clippy::too_many_arguments)]
+pub fn unlock_lp_instr(
     config: &ClientConfig,
     pool_id: Pubkey,
     user_token_lp_account: Pubkey,
     token_lp_mint: Pubkey,
     lp_lock_counter: Pubkey,
     user_lp_lock: Pubkey,
+    active_lock_page: Pubkey,
+    lp_lock_vault: Pubkey,
+    lock_id_index: Option<Pubkey>,
+    rent_payer: Pubkey,
+    reward_pool: Option<Pubkey>,
+    beneficiary_lp_accounts: Vec<Pubkey>,
+) -> Result<Vec<Instruction>> {
+    let payer = read_keypair_file(&config.payer_path)?;
+    let url = Cluster::Custom(config.http_url.clone(), config.ws_url.clone());
+    // Client.
+    let client = Client::new(url, Rc::new(payer));
+    let program = client.program(config.cpmm_lp_lock_program)?;
+
+    let (lock_vault_authority, __bump) =
+        Pubkey::find_program_address(&[AUTH_SEED.as_bytes()], &program.id());
+    let (pool_lock_stats, __bump) = Pubkey::find_program_address(
+        &[POOL_LOCK_STATS_SEED.as_bytes(), token_lp_mint.as_ref()],
+        &program.id(),
+    );
+
+    // Unclaimed rewards of an enrolled lock move to a receipt on unlock
+    let reward_receipt = reward_pool.map(|_| {
+        Pubkey::find_program_address(
+            &[REWARD_RECEIPT_SEED.as_bytes(), user_lp_lock.as_ref()],
+            &program.id(),
+        )
+        .0
+    });
+
+    let mut request = program
+        .request()
+        .accounts(raydium_cp_accounts::UnlockLp {
+            owner: program.payer(),
+            authority: vault_and_lp_mint_auth::id(),
+            lock_vault_authority,
+            pool_state: pool_id,
+            owner_lp_token: user_token_lp_account,
+            token_program: spl_token::id(),
+            lp_mint: token_lp_mint,
+            lp_lock_counter,
+            pool_lock_stats,
+            user_lp_lock,
+            lock_id_index,
+            active_lock_page,
+            lp_lock_vault,
+            rent_payer,
+            reward_pool,
+            reward_receipt,
+            system_program: system_program::id(),
+        })
+        .args(raydium_cp_instructions::UnlockLp {});
+    for beneficiary_lp_account in beneficiary_lp_accounts {
+        request = request.accounts(AccountMeta::new(beneficiary_lp_account, false));
+    }
+    let instructions = request.instructions()?;
+    Ok(instructions)
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn unlock_and_withdraw_instr(
+    config: &ClientConfig,
+    pool_id: Pubkey,
+    token_lp_mint: Pubkey,
+    lp_lock_counter: Pubkey,
+    user_lp_lock: Pubkey,
+    active_lock_page: Pubkey,
     lp_lock_vault: Pubkey,
+    lock_id_index: Option<Pubkey>,
+    rent_payer: Pubkey,
+    token_0_account: Pubkey,
+    token_1_account: Pubkey,
     token_0_vault: Pubkey,
     token_1_vault: Pubkey,
-    lp_token_amount: u64,
+    vault_0_mint: Pubkey,
+    vault_1_mint: Pubkey,
+    reward_pool: Option<Pubkey>,
+    minimum_token_0_amount: u64,
+    minimum_token_1_amount: u64,
 ) -> Result<Vec<Instruction>> {
     let payer = read_keypair_file(&config.payer_path)?;
     let url = Cluster::Custom(config.http_url.clone(), config.ws_url.clone());
@@ -79,32 +416,59 @@ pub fn lock_lp_perm_instr(
 
     let (lock_vault_authority, __bump) =
         Pubkey::find_program_address(&[AUTH_SEED.as_bytes()], &program.id());
+    let (pool_lock_stats, __bump) = Pubkey::find_program_address(
+        &[POOL_LOCK_STATS_SEED.as_bytes(), token_lp_mint.as_ref()],
+        &program.id(),
+    );
+
+    // Unclaimed rewards of an enrolled lock move to a receipt on unlock
+    let reward_receipt = reward_pool.map(|_| {
+        Pubkey::find_program_address(
+            &[REWARD_RECEIPT_SEED.as_bytes(), user_lp_lock.as_ref()],
+            &program.id(),
+        )
+        .0
+    });
 
     let instructions = program
         .request()
-        .accounts(raydium_cp_accounts::LockLp {
+        .accounts(raydium_cp_accounts::UnlockAndWithdraw {
             owner: program.payer(),
             authority: vault_and_lp_mint_auth::id(),
             lock_vault_authority,
             pool_state: pool_id,
-            owner_lp_token: user_token_lp_account,
-            token_program: spl_token::id(),
             lp_mint: token_lp_mint,
             lp_lock_counter,
+            pool_lock_stats,
             user_lp_lock,
+            lock_id_index,
+            active_lock_page,
             lp_lock_vault,
+            rent_payer,
+            token_0_account,
+            token_1_account,
             token_0_vault,
             token_1_vault,
+            token_program: spl_token::id(),
+            token_program_2022: spl_token_2022::id(),
+            vault_0_mint,
+            vault_1_mint,
+            memo_program: spl_memo::id(),
+            raydium_cpmm_program: raydium_cpmm::id(),
+            reward_pool,
+            reward_receipt,
             system_program: system_program::id(),
         })
-        .args(raydium_cp_instructions::LockLpPermanent {
-            amount: lp_token_amount,
+        .args(raydium_cp_instructions::UnlockAndWithdraw {
+            minimum_token_0_amount,
+            minimum_token_1_amount,
         })
         .instructions()?;
     Ok(instructions)
 }
 
-pub fn unlock_lp_instr(
+#[allow(clippy::too_many_arguments)]
+pub fn sync_lock_vault_instr(
     config: &ClientConfig,
     pool_id: Pubkey,
     user_token_lp_account: Pubkey,
@@ -112,6 +476,10 @@ pub fn unlock_lp_instr(
     lp_lock_counter: Pubkey,
     user_lp_lock: Pubkey,
     lp_lock_vault: Pubkey,
+    token_0_vault: Pubkey,
+    token_1_vault: Pubkey,
+    reward_pool: Option<Pubkey>,
+    credit: bool,
 ) -> Result<Vec<Instruction>> {
     let payer = read_keypair_file(&config.payer_path)?;
     let url = Cluster::Custom(config.http_url.clone(), config.ws_url.clone());
@@ -121,10 +489,14 @@ pub fn unlock_lp_instr(
 
     let (lock_vault_authority, __bump) =
         Pubkey::find_program_address(&[AUTH_SEED.as_bytes()], &program.id());
+    let (pool_lock_stats, __bump) = Pubkey::find_program_address(
+        &[POOL_LOCK_STATS_SEED.as_bytes(), token_lp_mint.as_ref()],
+        &program.id(),
+    );
 
     let instructions = program
         .request()
-        .accounts(raydium_cp_accounts::UnlockLp {
+        .accounts(raydium_cp_accounts::SyncLockVault {
             owner: program.payer(),
             authority: vault_and_lp_mint_auth::id(),
             lock_vault_authority,
@@ -133,15 +505,19 @@ pub fn unlock_lp_instr(
             token_program: spl_token::id(),
             lp_mint: token_lp_mint,
             lp_lock_counter,
+            pool_lock_stats,
             user_lp_lock,
             lp_lock_vault,
-            system_program: system_program::id(),
+            token_0_vault,
+            token_1_vault,
+            reward_pool,
         })
-        .args(raydium_cp_instructions::UnlockLp {})
+        .args(raydium_cp_instructions::SyncLockVault { credit })
         .instructions()?;
     Ok(instructions)
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn collect_fees_instr(
     config: &ClientConfig,
     pool_id: Pubkey,
@@ -150,12 +526,15 @@ pub fn collect_fees_instr(
     lp_lock_counter: Pubkey,
     user_lp_lock: Pubkey,
     lp_lock_vault: Pubkey,
-    token_0_account: Pubkey,
-    token_1_account: Pubkey,
+    token_0_account: Option<Pubkey>,
+    token_1_account: Option<Pubkey>,
     token_0_vault: Pubkey,
     token_1_vault: Pubkey,
     vault_0_mint: Pubkey,
     vault_1_mint: Pubkey,
+    reward_pool: Option<Pubkey>,
+    fee_split: Option<Pubkey>,
+    fee_split_recipient_accounts: Vec<Pubkey>,
 ) -> Result<Vec<Instruction>> {
     let payer = read_keypair_file(&config.payer_path)?;
     let url = Cluster::Custom(config.http_url.clone(), config.ws_url.clone());
@@ -165,8 +544,12 @@ pub fn collect_fees_instr(
 
     let (lock_vault_authority, __bump) =
         Pubkey::find_program_address(&[AUTH_SEED.as_bytes()], &program.id());
+    let (pool_lock_stats, __bump) = Pubkey::find_program_address(
+        &[POOL_LOCK_STATS_SEED.as_bytes(), token_lp_mint.as_ref()],
+        &program.id(),
+    );
 
-    let instructions = program
+    let mut request = program
         .request()
         .accounts(raydium_cp_accounts::CollectFees {
             owner: program.payer(),
@@ -176,6 +559,7 @@ pub fn collect_fees_instr(
             owner_lp_token: user_token_lp_account,
             lp_mint: token_lp_mint,
             lp_lock_counter,
+            pool_lock_stats,
             user_lp_lock,
             lp_lock_vault,
             token_0_account,
@@ -188,9 +572,1206 @@ pub fn collect_fees_instr(
             vault_1_mint,
             memo_program: spl_memo::id(),
             raydium_cpmm_program: raydium_cpmm::id(),
+            reward_pool,
+            fee_split,
+            system_program: system_program::id(),
+        })
+        .args(raydium_cp_instructions::CollectFees {});
+    for recipient_account in fee_split_recipient_accounts {
+        request = request.accounts(AccountMeta::new(recipient_account, false));
+    }
+    let instructions = request.instructions()?;
+    Ok(instructions)
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn collect_fees_as_lp_instr(
+    config: &ClientConfig,
+    pool_id: Pubkey,
+    user_token_lp_account: Pubkey,
+    token_lp_mint: Pubkey,
+    lp_lock_counter: Pubkey,
+    user_lp_lock: Pubkey,
+    lp_lock_vault: Pubkey,
+    token_0_vault: Pubkey,
+    token_1_vault: Pubkey,
+    reward_pool: Option<Pubkey>,
+) -> Result<Vec<Instruction>> {
+    let payer = read_keypair_file(&config.payer_path)?;
+    let url = Cluster::Custom(config.http_url.clone(), config.ws_url.clone());
+    // Client.
+    let client = Client::new(url, Rc::new(payer));
+    let program = client.program(config.cpmm_lp_lock_program)?;
+
+    let (lock_vault_authority, __bump) =
+        Pubkey::find_program_address(&[AUTH_SEED.as_bytes()], &program.id());
+    let (pool_lock_stats, __bump) = Pubkey::find_program_address(
+        &[POOL_LOCK_STATS_SEED.as_bytes(), token_lp_mint.as_ref()],
+        &program.id(),
+    );
+
+    let instructions = program
+        .request()
+        .accounts(raydium_cp_accounts::CollectFeesAsLp {
+            owner: program.payer(),
+            lock_vault_authority,
+            pool_state: pool_id,
+            owner_lp_token: user_token_lp_account,
+            lp_mint: token_lp_mint,
+            lp_lock_counter,
+            pool_lock_stats,
+            user_lp_lock,
+            lp_lock_vault,
+            token_0_vault,
+            token_1_vault,
+            token_program: spl_token::id(),
+            reward_pool,
+        })
+        .args(raydium_cp_instructions::CollectFeesAsLp {})
+        .instructions()?;
+    Ok(instructions)
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn collect_fees_as_delegate_instr(
+    config: &ClientConfig,
+    pool_id: Pubkey,
+    owner: Pubkey,
+    keeper_lp_token: Pubkey,
+    token_lp_mint: Pubkey,
+    lp_lock_counter: Pubkey,
+    user_lp_lock: Pubkey,
+    lp_lock_vault: Pubkey,
+    token_0_account: Option<Pubkey>,
+    token_1_account: Option<Pubkey>,
+    token_0_vault: Pubkey,
+    token_1_vault: Pubkey,
+    vault_0_mint: Pubkey,
+    vault_1_mint: Pubkey,
+    reward_pool: Option<Pubkey>,
+    fee_split: Option<Pubkey>,
+    fee_split_recipient_accounts: Vec<Pubkey>,
+) -> Result<Vec<Instruction>> {
+    let payer = read_keypair_file(&config.payer_path)?;
+    let url = Cluster::Custom(config.http_url.clone(), config.ws_url.clone());
+    // Client.
+    let client = Client::new(url, Rc::new(payer));
+    let program = client.program(config.cpmm_lp_lock_program)?;
+
+    let (lock_vault_authority, __bump) =
+        Pubkey::find_program_address(&[AUTH_SEED.as_bytes()], &program.id());
+    let (pool_lock_stats, __bump) = Pubkey::find_program_address(
+        &[POOL_LOCK_STATS_SEED.as_bytes(), token_lp_mint.as_ref()],
+        &program.id(),
+    );
+
+    let mut request = program
+        .request()
+        .accounts(raydium_cp_accounts::CollectFeesAsDelegate {
+            collect_delegate: program.payer(),
+            owner,
+            authority: vault_and_lp_mint_auth::id(),
+            lock_vault_authority,
+            pool_state: pool_id,
+            lp_mint: token_lp_mint,
+            lp_lock_counter,
+            pool_lock_stats,
+            user_lp_lock,
+            lp_lock_vault,
+            token_0_account,
+            token_1_account,
+            keeper_lp_token: Some(keeper_lp_token),
+            token_0_vault,
+            token_1_vault,
+            token_program: spl_token::id(),
+            token_program_2022: spl_token_2022::id(),
+            vault_0_mint,
+            vault_1_mint,
+            memo_program: spl_memo::id(),
+            raydium_cpmm_program: raydium_cpmm::id(),
+            reward_pool,
+            fee_split,
+        })
+        .args(raydium_cp_instructions::CollectFeesAsDelegate {});
+    for recipient_account in fee_split_recipient_accounts {
+        request = request.accounts(AccountMeta::new(recipient_account, false));
+    }
+    let instructions = request.instructions()?;
+    Ok(instructions)
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn collect_fees_single_sided_instr(
+    config: &ClientConfig,
+    pool_id: Pubkey,
+    user_token_lp_account: Pubkey,
+    token_lp_mint: Pubkey,
+    lp_lock_counter: Pubkey,
+    user_lp_lock: Pubkey,
+    lp_lock_vault: Pubkey,
+    token_0_account: Pubkey,
+    token_1_account: Pubkey,
+    token_0_vault: Pubkey,
+    token_1_vault: Pubkey,
+    vault_0_mint: Pubkey,
+    vault_1_mint: Pubkey,
+    reward_pool: Option<Pubkey>,
+    fee_split: Option<Pubkey>,
+    fee_split_recipient_accounts: Vec<Pubkey>,
+    amm_config: Pubkey,
+    observation_state: Pubkey,
+    output_mint: Pubkey,
+    minimum_amount_out: u64,
+) -> Result<Vec<Instruction>> {
+    let payer = read_keypair_file(&config.payer_path)?;
+    let url = Cluster::Custom(config.http_url.clone(), config.ws_url.clone());
+    // Client.
+    let client = Client::new(url, Rc::new(payer));
+    let program = client.program(config.cpmm_lp_lock_program)?;
+
+    let (lock_vault_authority, __bump) =
+        Pubkey::find_program_address(&[AUTH_SEED.as_bytes()], &program.id());
+    let (pool_lock_stats, __bump) = Pubkey::find_program_address(
+        &[POOL_LOCK_STATS_SEED.as_bytes(), token_lp_mint.as_ref()],
+        &program.id(),
+    );
+
+    let mut request = program
+        .request()
+        .accounts(raydium_cp_accounts::CollectFeesSingleSided {
+            collect_fees: raydium_cp_accounts::CollectFees {
+                owner: program.payer(),
+                authority: vault_and_lp_mint_auth::id(),
+                lock_vault_authority,
+                pool_state: pool_id,
+                owner_lp_token: user_token_lp_account,
+                lp_mint: token_lp_mint,
+                lp_lock_counter,
+                pool_lock_stats,
+                user_lp_lock,
+                lp_lock_vault,
+                token_0_account: Some(token_0_account),
+                token_1_account: Some(token_1_account),
+                token_0_vault,
+                token_1_vault,
+                token_program: spl_token::id(),
+                token_program_2022: spl_token_2022::id(),
+                vault_0_mint,
+                vault_1_mint,
+                memo_program: spl_memo::id(),
+                raydium_cpmm_program: raydium_cpmm::id(),
+                reward_pool,
+                fee_split,
+                system_program: system_program::id(),
+            },
+            amm_config,
+            observation_state,
+        })
+        .args(raydium_cp_instructions::CollectFeesSingleSided {
+            output_mint,
+            minimum_amount_out,
+        });
+    for recipient_account in fee_split_recipient_accounts {
+        request = request.accounts(AccountMeta::new(recipient_account, false));
+    }
+    let instructions = request.instructions()?;
+    Ok(instructions)
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn initialize_reward_pool_instr(
+    config: &ClientConfig,
+    pool_id: Pubkey,
+    token_lp_mint: Pubkey,
+    reward_pool: Pubkey,
+    reward_mint: Pubkey,
+    reward_vault: Pubkey,
+    authority_reward_token: Pubkey,
+    reward_token_program: Pubkey,
+    campaign: u64,
+    emission_rate: u64,
+    end_time: u64,
+) -> Result<Vec<Instruction>> {
+    let payer = read_keypair_file(&config.payer_path)?;
+    let url = Cluster::Custom(config.http_url.clone(), config.ws_url.clone());
+    // Client.
+    let client = Client::new(url, Rc::new(payer));
+    let program = client.program(config.cpmm_lp_lock_program)?;
+
+    let (lock_vault_authority, __bump) =
+        Pubkey::find_program_address(&[AUTH_SEED.as_bytes()], &program.id());
+
+    let instructions = program
+        .request()
+        .accounts(raydium_cp_accounts::InitializeRewardPool {
+            authority: program.payer(),
+            lock_vault_authority,
+            pool_state: pool_id,
+            lp_mint: token_lp_mint,
+            reward_pool,
+            reward_mint,
+            reward_vault,
+            authority_reward_token,
+            reward_token_program,
+            system_program: system_program::id(),
+        })
+        .args(raydium_cp_instructions::InitializeRewardPool {
+            campaign,
+            emission_rate,
+            end_time,
+        })
+        .instructions()?;
+    Ok(instructions)
+}
+
+pub fn sweep_reward_pool_instr(
+    config: &ClientConfig,
+    reward_pool: Pubkey,
+    reward_vault: Pubkey,
+    reward_mint: Pubkey,
+    authority_reward_token: Pubkey,
+    reward_token_program: Pubkey,
+) -> Result<Vec<Instruction>> {
+    let payer = read_keypair_file(&config.payer_path)?;
+    let url = Cluster::Custom(config.http_url.clone(), config.ws_url.clone());
+    // Client.
+    let client = Client::new(url, Rc::new(payer));
+    let program = client.program(config.cpmm_lp_lock_program)?;
+
+    let (lock_vault_authority, __bump) =
+        Pubkey::find_program_address(&[AUTH_SEED.as_bytes()], &program.id());
+
+    let instructions = program
+        .request()
+        .accounts(raydium_cp_accounts::SweepRewardPool {
+            authority: program.payer(),
+            lock_vault_authority,
+            reward_pool,
+            reward_vault,
+            reward_mint,
+            authority_reward_token,
+            reward_token_program,
+        })
+        .args(raydium_cp_instructions::SweepRewardPool {})
+        .instructions()?;
+    Ok(instructions)
+}
+
+pub fn claim_rewards_instr(
+    config: &ClientConfig,
+    user_lp_lock: Pubkey,
+    reward_pool: Pubkey,
+    reward_vault: Pubkey,
+    reward_mint: Pubkey,
+    owner_reward_token: Pubkey,
+    reward_token_program: Pubkey,
+) -> Result<Vec<Instruction>> {
+    let payer = read_keypair_file(&config.payer_path)?;
+    let url = Cluster::Custom(config.http_url.clone(), config.ws_url.clone());
+    // Client.
+    let client = Client::new(url, Rc::new(payer));
+    let program = client.program(config.cpmm_lp_lock_program)?;
+
+    let (lock_vault_authority, __bump) =
+        Pubkey::find_program_address(&[AUTH_SEED.as_bytes()], &program.id());
+
+    let instructions = program
+        .request()
+        .accounts(raydium_cp_accounts::ClaimRewards {
+            owner: program.payer(),
+            lock_vault_authority,
+            user_lp_lock,
+            reward_pool,
+            reward_vault,
+            reward_mint,
+            owner_reward_token,
+            reward_token_program,
+        })
+        .args(raydium_cp_instructions::ClaimRewards {})
+        .instructions()?;
+    Ok(instructions)
+}
+
+pub fn claim_reward_receipt_instr(
+    config: &ClientConfig,
+    reward_receipt: Pubkey,
+    reward_pool: Pubkey,
+    reward_vault: Pubkey,
+    reward_mint: Pubkey,
+    owner_reward_token: Pubkey,
+    reward_token_program: Pubkey,
+) -> Result<Vec<Instruction>> {
+    let payer = read_keypair_file(&config.payer_path)?;
+    let url = Cluster::Custom(config.http_url.clone(), config.ws_url.clone());
+    // Client.
+    let client = Client::new(url, Rc::new(payer));
+    let program = client.program(config.cpmm_lp_lock_program)?;
+
+    let (lock_vault_authority, __bump) =
+        Pubkey::find_program_address(&[AUTH_SEED.as_bytes()], &program.id());
+
+    let instructions = program
+        .request()
+        .accounts(raydium_cp_accounts::ClaimRewardReceipt {
+            owner: program.payer(),
+            lock_vault_authority,
+            reward_receipt,
+            reward_pool,
+            reward_vault,
+            reward_mint,
+            owner_reward_token,
+            reward_token_program,
+        })
+        .args(raydium_cp_instructions::ClaimRewardReceipt {})
+        .instructions()?;
+    Ok(instructions)
+}
+
+pub fn checkpoint_rewards_instr(
+    config: &ClientConfig,
+    user_lp_lock: Pubkey,
+    reward_pool: Pubkey,
+) -> Result<Vec<Instruction>> {
+    let payer = read_keypair_file(&config.payer_path)?;
+    let url = Cluster::Custom(config.http_url.clone(), config.ws_url.clone());
+    // Client.
+    let client = Client::new(url, Rc::new(payer));
+    let program = client.program(config.cpmm_lp_lock_program)?;
+
+    let instructions = program
+        .request()
+        .accounts(raydium_cp_accounts::CheckpointRewards {
+            signer: program.payer(),
+            user_lp_lock,
+            reward_pool,
+        })
+        .args(raydium_cp_instructions::CheckpointRewards {})
+        .instructions()?;
+    Ok(instructions)
+}
+
+pub fn get_voting_power_instr(
+    config: &ClientConfig,
+    user: Pubkey,
+    token_lp_mint: Pubkey,
+    lp_lock_counter: Pubkey,
+    timed_locks: Vec<Pubkey>,
+    timestamp: u64,
+) -> Result<Vec<Instruction>> {
+    let payer = read_keypair_file(&config.payer_path)?;
+    let url = Cluster::Custom(config.http_url.clone(), config.ws_url.clone());
+    // Client.
+    let client = Client::new(url, Rc::new(payer));
+    let program = client.program(config.cpmm_lp_lock_program)?;
+
+    let mut request = program
+        .request()
+        .accounts(raydium_cp_accounts::GetVotingPower { lp_lock_counter })
+        .args(raydium_cp_instructions::GetVotingPower {
+            user,
+            lp_mint: token_lp_mint,
+            timestamp,
+        });
+    for timed_lock in timed_locks {
+        request = request.accounts(AccountMeta::new_readonly(timed_lock, false));
+    }
+    let instructions = request.instructions()?;
+    Ok(instructions)
+}
+
+pub fn get_creator_lock_share_instr(
+    config: &ClientConfig,
+    pool_id: Pubkey,
+    token_lp_mint: Pubkey,
+) -> Result<Vec<Instruction>> {
+    let payer = read_keypair_file(&config.payer_path)?;
+    let url = Cluster::Custom(config.http_url.clone(), config.ws_url.clone());
+    // Client.
+    let client = Client::new(url, Rc::new(payer));
+    let program = client.program(config.cpmm_lp_lock_program)?;
+
+    let (pool_lock_stats, _) = Pubkey::find_program_address(
+        &[POOL_LOCK_STATS_SEED.as_bytes(), token_lp_mint.as_ref()],
+        &program.id(),
+    );
+    let instructions = program
+        .request()
+        .accounts(raydium_cp_accounts::GetCreatorLockShare {
+            pool_state: pool_id,
+            pool_lock_stats,
+        })
+        .args(raydium_cp_instructions::GetCreatorLockShare {})
+        .instructions()?;
+    Ok(instructions)
+}
+
+pub fn set_fee_split_instr(
+    config: &ClientConfig,
+    user_lp_lock: Pubkey,
+    fee_split: Pubkey,
+    recipients: Vec<FeeSplitRecipient>,
+) -> Result<Vec<Instruction>> {
+    let payer = read_keypair_file(&config.payer_path)?;
+    let url = Cluster::Custom(config.http_url.clone(), config.ws_url.clone());
+    // Client.
+    let client = Client::new(url, Rc::new(payer));
+    let program = client.program(config.cpmm_lp_lock_program)?;
+
+    let instructions = program
+        .request()
+        .accounts(raydium_cp_accounts::SetFeeSplit {
+            owner: program.payer(),
+            user_lp_lock,
+            fee_split,
+            system_program: system_program::id(),
+        })
+        .args(raydium_cp_instructions::SetFeeSplit { recipients })
+        .instructions()?;
+    Ok(instructions)
+}
+
+pub fn remove_fee_split_instr(
+    config: &ClientConfig,
+    user_lp_lock: Pubkey,
+    fee_split: Pubkey,
+) -> Result<Vec<Instruction>> {
+    let payer = read_keypair_file(&config.payer_path)?;
+    let url = Cluster::Custom(config.http_url.clone(), config.ws_url.clone());
+    // Client.
+    let client = Client::new(url, Rc::new(payer));
+    let program = client.program(config.cpmm_lp_lock_program)?;
+
+    let instructions = program
+        .request()
+        .accounts(raydium_cp_accounts::RemoveFeeSplit {
+            owner: program.payer(),
+            user_lp_lock,
+            fee_split,
+        })
+        .args(raydium_cp_instructions::RemoveFeeSplit {})
+        .instructions()?;
+    Ok(instructions)
+}
+
+pub fn initialize_registry_instr(config: &ClientConfig, admin: Pubkey) -> Result<Vec<Instruction>> {
+    let payer = read_keypair_file(&config.payer_path)?;
+    let url = Cluster::Custom(config.http_url.clone(), config.ws_url.clone());
+    // Client.
+    let client = Client::new(url, Rc::new(payer));
+    let program = client.program(config.cpmm_lp_lock_program)?;
+
+    let (registry_config, __bump) =
+        Pubkey::find_program_address(&[REGISTRY_CONFIG_SEED.as_bytes()], &program.id());
+    let (program_data, __bump) = Pubkey::find_program_address(
+        &[program.id().as_ref()],
+        &solana_sdk::bpf_loader_upgradeable::id(),
+    );
+
+    let instructions = program
+        .request()
+        .accounts(raydium_cp_accounts::InitializeRegistry {
+            authority: program.payer(),
+            program: program.id(),
+            program_data,
+            registry_config,
+            system_program: system_program::id(),
+        })
+        .args(raydium_cp_instructions::InitializeRegistry { admin })
+        .instructions()?;
+    Ok(instructions)
+}
+
+pub fn set_registry_admin_instr(
+    config: &ClientConfig,
+    new_admin: Pubkey,
+) -> Result<Vec<Instruction>> {
+    let payer = read_keypair_file(&config.payer_path)?;
+    let url = Cluster::Custom(config.http_url.clone(), config.ws_url.clone());
+    // Client.
+    let client = Client::new(url, Rc::new(payer));
+    let program = client.program(config.cpmm_lp_lock_program)?;
+
+    let (registry_config, __bump) =
+        Pubkey::find_program_address(&[REGISTRY_CONFIG_SEED.as_bytes()], &program.id());
+
+    let instructions = program
+        .request()
+        .accounts(raydium_cp_accounts::SetRegistryAdmin {
+            admin: program.payer(),
+            registry_config,
+        })
+        .args(raydium_cp_instructions::SetRegistryAdmin { new_admin })
+        .instructions()?;
+    Ok(instructions)
+}
+
+pub fn set_registry_config_instr(
+    config: &ClientConfig,
+    allow_list_enabled: bool,
+) -> Result<Vec<Instruction>> {
+    let payer = read_keypair_file(&config.payer_path)?;
+    let url = Cluster::Custom(config.http_url.clone(), config.ws_url.clone());
+    // Client.
+    let client = Client::new(url, Rc::new(payer));
+    let program = client.program(config.cpmm_lp_lock_program)?;
+
+    let (registry_config, __bump) =
+        Pubkey::find_program_address(&[REGISTRY_CONFIG_SEED.as_bytes()], &program.id());
+
+    let instructions = program
+        .request()
+        .accounts(raydium_cp_accounts::SetRegistryConfig {
+            admin: program.payer(),
+            registry_config,
+        })
+        .args(raydium_cp_instructions::SetRegistryConfig { allow_list_enabled })
+        .instructions()?;
+    Ok(instructions)
+}
+
+pub fn set_registry_entry_instr(
+    config: &ClientConfig,
+    key: Pubkey,
+    status: RegistryStatus,
+) -> Result<Vec<Instruction>> {
+    let payer = read_keypair_file(&config.payer_path)?;
+    let url = Cluster::Custom(config.http_url.clone(), config.ws_url.clone());
+    // Client.
+    let client = Client::new(url, Rc::new(payer));
+    let program = client.program(config.cpmm_lp_lock_program)?;
+
+    let (registry_config, __bump) =
+        Pubkey::find_program_address(&[REGISTRY_CONFIG_SEED.as_bytes()], &program.id());
+    let (registry_entry, __bump) = Pubkey::find_program_address(
+        &[REGISTRY_ENTRY_SEED.as_bytes(), key.as_ref()],
+        &program.id(),
+    );
+
+    let instructions = program
+        .request()
+        .accounts(raydium_cp_accounts::SetRegistryEntry {
+            admin: program.payer(),
+            registry_config,
+            registry_entry,
+            system_program: system_program::id(),
+        })
+        .args(raydium_cp_instructions::SetRegistryEntry { key, status })
+        .instructions()?;
+    Ok(instructions)
+}
+
+pub fn update_lock_metadata_instr(
+    config: &ClientConfig,
+    user_lp_lock: Pubkey,
+    metadata: LockMetadata,
+) -> Result<Vec<Instruction>> {
+    let payer = read_keypair_file(&config.payer_path)?;
+    let url = Cluster::Custom(config.http_url.clone(), config.ws_url.clone());
+    // Client.
+    let client = Client::new(url, Rc::new(payer));
+    let program = client.program(config.cpmm_lp_lock_program)?;
+
+    let instructions = program
+        .request()
+        .accounts(raydium_cp_accounts::UpdateLockMetadata {
+            owner: program.payer(),
+            user_lp_lock,
+        })
+        .args(raydium_cp_instructions::UpdateLockMetadata { metadata })
+        .instructions()?;
+    Ok(instructions)
+}
+
+pub fn grant_lien_instr(
+    config: &ClientConfig,
+    user_lp_lock: Pubkey,
+    lienholder: Pubkey,
+) -> Result<Vec<Instruction>> {
+    let payer = read_keypair_file(&config.payer_path)?;
+    let url = Cluster::Custom(config.http_url.clone(), config.ws_url.clone());
+    // Client.
+    let client = Client::new(url, Rc::new(payer));
+    let program = client.program(config.cpmm_lp_lock_program)?;
+
+    let instructions = program
+        .request()
+        .accounts(raydium_cp_accounts::GrantLien {
+            owner: program.payer(),
+            user_lp_lock,
+        })
+        .args(raydium_cp_instructions::GrantLien { lienholder })
+        .instructions()?;
+    Ok(instructions)
+}
+
+pub fn release_lien_instr(config: &ClientConfig, user_lp_lock: Pubkey) -> Result<Vec<Instruction>> {
+    let payer = read_keypair_file(&config.payer_path)?;
+    let url = Cluster::Custom(config.http_url.clone(), config.ws_url.clone());
+    // Client.
+    let client = Client::new(url, Rc::new(payer));
+    let program = client.program(config.cpmm_lp_lock_program)?;
+
+    let instructions = program
+        .request()
+        .accounts(raydium_cp_accounts::ReleaseLien {
+            lienholder: program.payer(),
+            user_lp_lock,
+        })
+        .args(raydium_cp_instructions::ReleaseLien {})
+        .instructions()?;
+    Ok(instructions)
+}
+
+pub fn foreclose_instr(
+    config: &ClientConfig,
+    user_lp_lock: Pubkey,
+    owner: Pubkey,
+    lp_mint: Pubkey,
+    active_lock_page: Pubkey,
+    new_owner: Pubkey,
+    new_active_lock_page: Pubkey,
+) -> Result<Vec<Instruction>> {
+    let payer = read_keypair_file(&config.payer_path)?;
+    let url = Cluster::Custom(config.http_url.clone(), config.ws_url.clone());
+    // Client.
+    let client = Client::new(url, Rc::new(payer));
+    let program = client.program(config.cpmm_lp_lock_program)?;
+
+    let (lp_lock_counter, __bump) = Pubkey::find_program_address(
+        &[
+            LP_LOCK_COUNTER_SEED.as_bytes(),
+            owner.as_ref(),
+            lp_mint.as_ref(),
+        ],
+        &program.id(),
+    );
+    let (new_lp_lock_counter, __bump) = Pubkey::find_program_address(
+        &[
+            LP_LOCK_COUNTER_SEED.as_bytes(),
+            new_owner.as_ref(),
+            lp_mint.as_ref(),
+        ],
+        &program.id(),
+    );
+    let (pool_lock_stats, __bump) = Pubkey::find_program_address(
+        &[POOL_LOCK_STATS_SEED.as_bytes(), lp_mint.as_ref()],
+        &program.id(),
+    );
+
+    let instructions = program
+        .request()
+        .accounts(raydium_cp_accounts::Foreclose {
+            lienholder: program.payer(),
+            payer: program.payer(),
+            user_lp_lock,
+            lp_lock_counter,
+            pool_lock_stats,
+            new_owner,
+            new_lp_lock_counter,
+            active_lock_page,
+            new_active_lock_page,
+            system_program: system_program::id(),
+        })
+        .args(raydium_cp_instructions::Foreclose {})
+        .instructions()?;
+    Ok(instructions)
+}
+
+pub fn list_lock_instr(
+    config: &ClientConfig,
+    user_lp_lock: Pubkey,
+    price_mint: Pubkey,
+    price: u64,
+) -> Result<Vec<Instruction>> {
+    let payer = read_keypair_file(&config.payer_path)?;
+    let url = Cluster::Custom(config.http_url.clone(), config.ws_url.clone());
+    // Client.
+    let client = Client::new(url, Rc::new(payer));
+    let program = client.program(config.cpmm_lp_lock_program)?;
+
+    let (lock_listing, __bump) = Pubkey::find_program_address(
+        &[LOCK_LISTING_SEED.as_bytes(), user_lp_lock.as_ref()],
+        &program.id(),
+    );
+
+    let instructions = program
+        .request()
+        .accounts(raydium_cp_accounts::ListLock {
+            owner: program.payer(),
+            user_lp_lock,
+            lock_listing,
+            system_program: system_program::id(),
+        })
+        .args(raydium_cp_instructions::ListLock { price_mint, price })
+        .instructions()?;
+    Ok(instructions)
+}
+
+pub fn update_listing_instr(
+    config: &ClientConfig,
+    user_lp_lock: Pubkey,
+    price_mint: Pubkey,
+    price: u64,
+) -> Result<Vec<Instruction>> {
+    let payer = read_keypair_file(&config.payer_path)?;
+    let url = Cluster::Custom(config.http_url.clone(), config.ws_url.clone());
+    // Client.
+    let client = Client::new(url, Rc::new(payer));
+    let program = client.program(config.cpmm_lp_lock_program)?;
+
+    let (lock_listing, __bump) = Pubkey::find_program_address(
+        &[LOCK_LISTING_SEED.as_bytes(), user_lp_lock.as_ref()],
+        &program.id(),
+    );
+
+    let instructions = program
+        .request()
+        .accounts(raydium_cp_accounts::UpdateListing {
+            owner: program.payer(),
+            lock_listing,
+        })
+        .args(raydium_cp_instructions::UpdateListing { price_mint, price })
+        .instructions()?;
+    Ok(instructions)
+}
+
+pub fn cancel_listing_instr(
+    config: &ClientConfig,
+    user_lp_lock: Pubkey,
+) -> Result<Vec<Instruction>> {
+    let payer = read_keypair_file(&config.payer_path)?;
+    let url = Cluster::Custom(config.http_url.clone(), config.ws_url.clone());
+    // Client.
+    let client = Client::new(url, Rc::new(payer));
+    let program = client.program(config.cpmm_lp_lock_program)?;
+
+    let (lock_listing, __bump) = Pubkey::find_program_address(
+        &[LOCK_LISTING_SEED.as_bytes(), user_lp_lock.as_ref()],
+        &program.id(),
+    );
+
+    let instructions = program
+        .request()
+        .accounts(raydium_cp_accounts::CancelListing {
+            owner: program.payer(),
+            user_lp_lock,
+            lock_listing,
+        })
+        .args(raydium_cp_instructions::CancelListing {})
+        .instructions()?;
+    Ok(instructions)
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn buy_lock_instr(
+    config: &ClientConfig,
+    user_lp_lock: Pubkey,
+    seller: Pubkey,
+    lp_mint: Pubkey,
+    seller_active_lock_page: Pubkey,
+    buyer_active_lock_page: Pubkey,
+    price_mint: Pubkey,
+    price_token_program: Option<Pubkey>,
+    max_price: u64,
+) -> Result<Vec<Instruction>> {
+    let payer = read_keypair_file(&config.payer_path)?;
+    let url = Cluster::Custom(config.http_url.clone(), config.ws_url.clone());
+    // Client.
+    let client = Client::new(url, Rc::new(payer));
+    let program = client.program(config.cpmm_lp_lock_program)?;
+
+    let (lock_listing, __bump) = Pubkey::find_program_address(
+        &[LOCK_LISTING_SEED.as_bytes(), user_lp_lock.as_ref()],
+        &program.id(),
+    );
+    let (seller_lp_lock_counter, __bump) = Pubkey::find_program_address(
+        &[
+            LP_LOCK_COUNTER_SEED.as_bytes(),
+            seller.as_ref(),
+            lp_mint.as_ref(),
+        ],
+        &program.id(),
+    );
+    let (buyer_lp_lock_counter, __bump) = Pubkey::find_program_address(
+        &[
+            LP_LOCK_COUNTER_SEED.as_bytes(),
+            program.payer().as_ref(),
+            lp_mint.as_ref(),
+        ],
+        &program.id(),
+    );
+    let (pool_lock_stats, __bump) = Pubkey::find_program_address(
+        &[POOL_LOCK_STATS_SEED.as_bytes(), lp_mint.as_ref()],
+        &program.id(),
+    );
+
+    let instructions = program
+        .request()
+        .accounts(raydium_cp_accounts::BuyLock {
+            buyer: program.payer(),
+            seller,
+            user_lp_lock,
+            lock_listing,
+            seller_lp_lock_counter,
+            buyer_lp_lock_counter,
+            seller_active_lock_page,
+            buyer_active_lock_page,
+            pool_lock_stats,
+            price_mint: price_token_program.map(|_| price_mint),
+            buyer_payment_token: price_token_program.map(|token_program| {
+                get_associated_token_address_with_program_id(
+                    &program.payer(),
+                    &price_mint,
+                    &token_program,
+                )
+            }),
+            seller_payment_token: price_token_program.map(|token_program| {
+                get_associated_token_address_with_program_id(&seller, &price_mint, &token_program)
+            }),
+            token_program: price_token_program,
+            system_program: system_program::id(),
+        })
+        .args(raydium_cp_instructions::BuyLock {
+            price_mint,
+            max_price,
+        })
+        .instructions()?;
+    Ok(instructions)
+}
+
+pub fn stop_rolling_instr(
+    config: &ClientConfig,
+    user_lp_lock: Pubkey,
+    lp_lock_counter: Pubkey,
+    lp_mint: Pubkey,
+    reward_pool: Option<Pubkey>,
+) -> Result<Vec<Instruction>> {
+    let payer = read_keypair_file(&config.payer_path)?;
+    let url = Cluster::Custom(config.http_url.clone(), config.ws_url.clone());
+    // Client.
+    let client = Client::new(url, Rc::new(payer));
+    let program = client.program(config.cpmm_lp_lock_program)?;
+
+    let (pool_lock_stats, __bump) = Pubkey::find_program_address(
+        &[POOL_LOCK_STATS_SEED.as_bytes(), lp_mint.as_ref()],
+        &program.id(),
+    );
+
+    let instructions = program
+        .request()
+        .accounts(raydium_cp_accounts::StopRolling {
+            owner: program.payer(),
+            user_lp_lock,
+            lp_lock_counter,
+            pool_lock_stats,
+            reward_pool,
+        })
+        .args(raydium_cp_instructions::StopRolling {})
+        .instructions()?;
+    Ok(instructions)
+}
+
+pub fn set_notice_period_instr(
+    config: &ClientConfig,
+    user_lp_lock: Pubkey,
+    notice_period: u64,
+) -> Result<Vec<Instruction>> {
+    let payer = read_keypair_file(&config.payer_path)?;
+    let url = Cluster::Custom(config.http_url.clone(), config.ws_url.clone());
+    // Client.
+    let client = Client::new(url, Rc::new(payer));
+    let program = client.program(config.cpmm_lp_lock_program)?;
+
+    let instructions = program
+        .request()
+        .accounts(raydium_cp_accounts::ManageUnlockNotice {
+            owner: program.payer(),
+            user_lp_lock,
+        })
+        .args(raydium_cp_instructions::SetNoticePeriod { notice_period })
+        .instructions()?;
+    Ok(instructions)
+}
+
+pub fn request_unlock_instr(
+    config: &ClientConfig,
+    user_lp_lock: Pubkey,
+) -> Result<Vec<Instruction>> {
+    let payer = read_keypair_file(&config.payer_path)?;
+    let url = Cluster::Custom(config.http_url.clone(), config.ws_url.clone());
+    // Client.
+    let client = Client::new(url, Rc::new(payer));
+    let program = client.program(config.cpmm_lp_lock_program)?;
+
+    let instructions = program
+        .request()
+        .accounts(raydium_cp_accounts::ManageUnlockNotice {
+            owner: program.payer(),
+            user_lp_lock,
+        })
+        .args(raydium_cp_instructions::RequestUnlock {})
+        .instructions()?;
+    Ok(instructions)
+}
+
+pub fn cancel_unlock_request_instr(
+    config: &ClientConfig,
+    user_lp_lock: Pubkey,
+) -> Result<Vec<Instruction>> {
+    let payer = read_keypair_file(&config.payer_path)?;
+    let url = Cluster::Custom(config.http_url.clone(), config.ws_url.clone());
+    // Client.
+    let client = Client::new(url, Rc::new(payer));
+    let program = client.program(config.cpmm_lp_lock_program)?;
+
+    let instructions = program
+        .request()
+        .accounts(raydium_cp_accounts::ManageUnlockNotice {
+            owner: program.payer(),
+            user_lp_lock,
+        })
+        .args(raydium_cp_instructions::CancelUnlockRequest {})
+        .instructions()?;
+    Ok(instructions)
+}
+
+pub fn set_collect_delegate_instr(
+    config: &ClientConfig,
+    user_lp_lock: Pubkey,
+    collect_delegate: Pubkey,
+    keeper_tip_bps: u16,
+) -> Result<Vec<Instruction>> {
+    let payer = read_keypair_file(&config.payer_path)?;
+    let url = Cluster::Custom(config.http_url.clone(), config.ws_url.clone());
+    // Client.
+    let client = Client::new(url, Rc::new(payer));
+    let program = client.program(config.cpmm_lp_lock_program)?;
+
+    let instructions = program
+        .request()
+        .accounts(raydium_cp_accounts::SetCollectDelegate {
+            owner: program.payer(),
+            user_lp_lock,
+        })
+        .args(raydium_cp_instructions::SetCollectDelegate {
+            collect_delegate,
+            keeper_tip_bps,
+        })
+        .instructions()?;
+    Ok(instructions)
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn harvest_fees_instr(
+    config: &ClientConfig,
+    pool_id: Pubkey,
+    owner: Pubkey,
+    token_lp_mint: Pubkey,
+    lp_lock_counter: Pubkey,
+    user_lp_lock: Pubkey,
+    lp_lock_vault: Pubkey,
+    token_0_vault: Pubkey,
+    token_1_vault: Pubkey,
+    vault_0_mint: Pubkey,
+    vault_1_mint: Pubkey,
+    reward_pool: Option<Pubkey>,
+) -> Result<Vec<Instruction>> {
+    let payer = read_keypair_file(&config.payer_path)?;
+    let url = Cluster::Custom(config.http_url.clone(), config.ws_url.clone());
+    // Client.
+    let client = Client::new(url, Rc::new(payer));
+    let program = client.program(config.cpmm_lp_lock_program)?;
+
+    let (lock_vault_authority, __bump) =
+        Pubkey::find_program_address(&[AUTH_SEED.as_bytes()], &program.id());
+    let (pool_lock_stats, __bump) = Pubkey::find_program_address(
+        &[POOL_LOCK_STATS_SEED.as_bytes(), token_lp_mint.as_ref()],
+        &program.id(),
+    );
+    let lock_fee_vault = |mint: &Pubkey| {
+        Pubkey::find_program_address(
+            &[
+                LOCK_FEE_VAULT_SEED.as_bytes(),
+                user_lp_lock.as_ref(),
+                mint.as_ref(),
+            ],
+            &program.id(),
+        )
+        .0
+    };
+
+    let instructions = program
+        .request()
+        .accounts(raydium_cp_accounts::HarvestFees {
+            harvester: program.payer(),
+            owner,
+            authority: vault_and_lp_mint_auth::id(),
+            lock_vault_authority,
+            pool_state: pool_id,
+            lp_mint: token_lp_mint,
+            lp_lock_counter,
+            pool_lock_stats,
+            user_lp_lock,
+            lp_lock_vault,
+            lock_fee_vault_0: lock_fee_vault(&vault_0_mint),
+            lock_fee_vault_1: lock_fee_vault(&vault_1_mint),
+            token_0_vault,
+            token_1_vault,
+            token_program: spl_token::id(),
+            token_program_2022: spl_token_2022::id(),
+            vault_0_mint,
+            vault_1_mint,
+            memo_program: spl_memo::id(),
+            raydium_cpmm_program: raydium_cpmm::id(),
+            reward_pool,
+            system_program: system_program::id(),
+        })
+        .args(raydium_cp_instructions::HarvestFees {})
+        .instructions()?;
+    Ok(instructions)
+}
+
+pub fn claim_harvested_instr(
+    config: &ClientConfig,
+    user_lp_lock: Pubkey,
+    token_0_account: Pubkey,
+    token_1_account: Pubkey,
+    vault_0_mint: Pubkey,
+    vault_1_mint: Pubkey,
+    harvester: Pubkey,
+) -> Result<Vec<Instruction>> {
+    let payer = read_keypair_file(&config.payer_path)?;
+    let url = Cluster::Custom(config.http_url.clone(), config.ws_url.clone());
+    // Client.
+    let client = Client::new(url, Rc::new(payer));
+    let program = client.program(config.cpmm_lp_lock_program)?;
+
+    let (lock_vault_authority, __bump) =
+        Pubkey::find_program_address(&[AUTH_SEED.as_bytes()], &program.id());
+    let lock_fee_vault = |mint: &Pubkey| {
+        Pubkey::find_program_address(
+            &[
+                LOCK_FEE_VAULT_SEED.as_bytes(),
+                user_lp_lock.as_ref(),
+                mint.as_ref(),
+            ],
+            &program.id(),
+        )
+        .0
+    };
+
+    let instructions = program
+        .request()
+        .accounts(raydium_cp_accounts::ClaimHarvested {
+            owner: program.payer(),
+            user_lp_lock,
+            lock_vault_authority,
+            lock_fee_vault_0: lock_fee_vault(&vault_0_mint),
+            lock_fee_vault_1: lock_fee_vault(&vault_1_mint),
+            token_0_account,
+            token_1_account,
+            token_program: spl_token::id(),
+            token_program_2022: spl_token_2022::id(),
+            vault_0_mint,
+            vault_1_mint,
+            harvester,
+        })
+        .args(raydium_cp_instructions::ClaimHarvested {})
+        .instructions()?;
+    Ok(instructions)
+}
+
+pub fn migrate_user_lock_instr(
+    config: &ClientConfig,
+    user_lp_lock: Pubkey,
+    owner: Pubkey,
+    lp_mint: Pubkey,
+    lp_lock_counter: Pubkey,
+    lock_count: u64,
+) -> Result<Vec<Instruction>> {
+    let payer = read_keypair_file(&config.payer_path)?;
+    let url = Cluster::Custom(config.http_url.clone(), config.ws_url.clone());
+    // Client.
+    let client = Client::new(url, Rc::new(payer));
+    let program = client.program(config.cpmm_lp_lock_program)?;
+
+    let (pool_lock_stats, __bump) = Pubkey::find_program_address(
+        &[POOL_LOCK_STATS_SEED.as_bytes(), lp_mint.as_ref()],
+        &program.id(),
+    );
+    let (active_lock_page, __bump) = Pubkey::find_program_address(
+        &[
+            ACTIVE_LOCK_PAGE_SEED.as_bytes(),
+            owner.as_ref(),
+            lp_mint.as_ref(),
+            ActiveLockPage::page_of(lock_count).to_le_bytes().as_ref(),
+        ],
+        &program.id(),
+    );
+
+    let instructions = program
+        .request()
+        .accounts(raydium_cp_accounts::MigrateUserLock {
+            payer: program.payer(),
+            user_lp_lock,
+            owner,
+            lp_mint,
+            lp_lock_counter,
+            pool_lock_stats,
+            active_lock_page,
+            system_program: system_program::id(),
+        })
+        .args(raydium_cp_instructions::MigrateUserLock { lock_count })
+        .instructions()?;
+    Ok(instructions)
+}
+
+pub fn backfill_lock_id_instr(
+    config: &ClientConfig,
+    user_lp_lock: Pubkey,
+    lock_id_index: Pubkey,
+) -> Result<Vec<Instruction>> {
+    let payer = read_keypair_file(&config.payer_path)?;
+    let url = Cluster::Custom(config.http_url.clone(), config.ws_url.clone());
+    // Client.
+    let client = Client::new(url, Rc::new(payer));
+    let program = client.program(config.cpmm_lp_lock_program)?;
+
+    let (global_lock_counter, __bump) =
+        Pubkey::find_program_address(&[GLOBAL_LOCK_COUNTER_SEED.as_bytes()], &program.id());
+
+    let instructions = program
+        .request()
+        .accounts(raydium_cp_accounts::BackfillLockId {
+            payer: program.payer(),
+            user_lp_lock,
+            global_lock_counter,
+            lock_id_index,
+            system_program: system_program::id(),
+        })
+        .args(raydium_cp_instructions::BackfillLockId {})
+        .instructions()?;
+    Ok(instructions)
+}
+
+pub fn migrate_lp_lock_counter_instr(
+    config: &ClientConfig,
+    lp_lock_counter: Pubkey,
+) -> Result<Vec<Instruction>> {
+    let payer = read_keypair_file(&config.payer_path)?;
+    let url = Cluster::Custom(config.http_url.clone(), config.ws_url.clone());
+    // Client.
+    let client = Client::new(url, Rc::new(payer));
+    let program = client.program(config.cpmm_lp_lock_program)?;
+
+    let instructions = program
+        .request()
+        .accounts(raydium_cp_accounts::MigrateLpLockCounter {
+            payer: program.payer(),
+            lp_lock_counter,
             system_program: system_program::id(),
         })
-        .args(raydium_cp_instructions::CollectFees {})
+        .args(raydium_cp_instructions::MigrateLpLockCounter {})
         .instructions()?;
     Ok(instructions)
 }
@@ -1,20 +1,32 @@
 #![allow(dead_code)]
-use anchor_client::{Client, Cluster};
+use anchor_client::{Client, Cluster, Program};
+use anchor_lang::AnchorDeserialize;
 use anyhow::{format_err, Result};
+use base64::Engine;
 use clap::Parser;
 use configparser::ini::Ini;
 use cpmm_lp_lock::{
-    states::{LP_LOCK_COUNTER_SEED, USER_LOCK_SEED},
+    instructions::LockMode,
+    states::{
+        ActiveLockPage, CreatorLockShare, FeeSplitRecipient, LockListing, LockMetadata,
+        LpLockCounterV0, RegistryStatus, UserLockV0, ACTIVE_LOCK_PAGE_SEED, FEE_SPLIT_SEED,
+        GLOBAL_LOCK_COUNTER_SEED, LOCKS_PER_ACTIVE_LOCK_PAGE, LOCK_ID_SEED, LOCK_LISTING_SEED,
+        LP_LOCK_COUNTER_SEED, REWARD_POOL_SEED, REWARD_RECEIPT_SEED, REWARD_VAULT_SEED,
+        USER_LOCK_SEED,
+    },
     LP_LOCK_VAULT_SEED,
 };
 use solana_client::rpc_client::RpcClient;
+use solana_client::rpc_filter::{Memcmp, RpcFilterType};
 use solana_sdk::{
+    commitment_config::CommitmentConfig,
     pubkey::Pubkey,
     signature::{Keypair, Signer},
     transaction::Transaction,
 };
 use spl_associated_token_account::get_associated_token_address_with_program_id;
 use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
 use std::{ops::Add, rc::Rc};
 
 mod instructions;
@@ -70,6 +82,156 @@ fn read_keypair_file(s: &str) -> Result<Keypair> {
         .map_err(|_| format_err!("failed to read keypair from {}", s))
 }
 
+fn reward_pool_address(program_id: &Pubkey, lp_mint: &Pubkey, campaign: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            REWARD_POOL_SEED.as_bytes(),
+            lp_mint.as_ref(),
+            campaign.to_le_bytes().as_ref(),
+        ],
+        program_id,
+    )
+    .0
+}
+
+/// Number of reward campaigns created so far for an LP mint
+fn reward_campaign_count(rpc_client: &RpcClient, program_id: &Pubkey, lp_mint: &Pubkey) -> u64 {
+    (0..)
+        .find(|campaign| {
+            rpc_client
+                .get_account(&reward_pool_address(program_id, lp_mint, *campaign))
+                .is_err()
+        })
+        .unwrap_or_default()
+}
+
+/// Latest reward campaign of an LP mint, if any
+fn find_reward_pool(
+    rpc_client: &RpcClient,
+    program_id: &Pubkey,
+    lp_mint: &Pubkey,
+) -> Option<Pubkey> {
+    reward_campaign_count(rpc_client, program_id, lp_mint)
+        .checked_sub(1)
+        .map(|campaign| reward_pool_address(program_id, lp_mint, campaign))
+}
+
+/// Reward pool a lock is enrolled in, if any
+fn find_enrolled_reward_pool(
+    program: &Program<Rc<Keypair>>,
+    user_lp_lock: Pubkey,
+) -> Result<Option<Pubkey>> {
+    let user_lock: cpmm_lp_lock::states::UserLock = program.account(user_lp_lock)?;
+    Ok((user_lock.reward_pool != Pubkey::default()).then_some(user_lock.reward_pool))
+}
+
+/// Address the next lock's program-wide ID will be indexed at
+fn find_next_lock_id_index(program: &Program<Rc<Keypair>>) -> Pubkey {
+    let (global_lock_counter, _) =
+        Pubkey::find_program_address(&[GLOBAL_LOCK_COUNTER_SEED.as_bytes()], &program.id());
+    let global_lock_counter_info: cpmm_lp_lock::states::GlobalLockCounter =
+        program.account(global_lock_counter).unwrap_or_default();
+    let (lock_id_index, _) = Pubkey::find_program_address(
+        &[
+            LOCK_ID_SEED.as_bytes(),
+            global_lock_counter_info
+                .total_lock_count
+                .add(1)
+                .to_le_bytes()
+                .as_ref(),
+        ],
+        &program.id(),
+    );
+    lock_id_index
+}
+
+/// Address the lock with a program-wide ID is indexed at, if it has an ID
+fn find_lock_id_index(program_id: &Pubkey, lock_id: u64) -> Option<Pubkey> {
+    if lock_id == 0 {
+        return None;
+    }
+    let (lock_id_index, _) = Pubkey::find_program_address(
+        &[LOCK_ID_SEED.as_bytes(), lock_id.to_le_bytes().as_ref()],
+        program_id,
+    );
+    Some(lock_id_index)
+}
+
+/// Address and state of the lock with a program-wide ID
+fn find_lock_by_id(
+    program: &Program<Rc<Keypair>>,
+    lock_id: u64,
+) -> Result<(Pubkey, cpmm_lp_lock::states::UserLock)> {
+    let (lock_id_index, _) = Pubkey::find_program_address(
+        &[LOCK_ID_SEED.as_bytes(), lock_id.to_le_bytes().as_ref()],
+        &program.id(),
+    );
+    let lock_id_index_info: cpmm_lp_lock::states::LockIdIndex = program.account(lock_id_index)?;
+    let user_lock = program.account(lock_id_index_info.user_lock)?;
+    Ok((lock_id_index_info.user_lock, user_lock))
+}
+
+/// Address of the active-lock index page tracking `lock_count` of a user's locks for an LP mint
+fn find_active_lock_page(
+    program_id: &Pubkey,
+    user: &Pubkey,
+    lp_mint: &Pubkey,
+    lock_count: u64,
+) -> Pubkey {
+    let (active_lock_page, _) = Pubkey::find_program_address(
+        &[
+            ACTIVE_LOCK_PAGE_SEED.as_bytes(),
+            user.as_ref(),
+            lp_mint.as_ref(),
+            ActiveLockPage::page_of(lock_count).to_le_bytes().as_ref(),
+        ],
+        program_id,
+    );
+    active_lock_page
+}
+
+/// Address of the active-lock index page tracking a lock while `holder` owns it, which for
+/// locks received from another wallet is a page of the holder kept apart per creator
+fn find_held_active_lock_page(
+    program_id: &Pubkey,
+    holder: &Pubkey,
+    user_lock: &cpmm_lp_lock::states::UserLock,
+) -> Pubkey {
+    let (active_lock_page, _) = Pubkey::find_program_address(
+        &[
+            ACTIVE_LOCK_PAGE_SEED.as_bytes(),
+            holder.as_ref(),
+            user_lock.lp_mint.as_ref(),
+            ActiveLockPage::page_of(user_lock.lock_count).to_le_bytes().as_ref(),
+            user_lock.active_lock_page_seed(holder),
+        ],
+        program_id,
+    );
+    active_lock_page
+}
+
+fn parse_fee_split_recipient(s: &str) -> Result<FeeSplitRecipient> {
+    let (recipient, bps) = s
+        .split_once(':')
+        .ok_or(format_err!("expected <pubkey>:<bps>, got {}", s))?;
+    Ok(FeeSplitRecipient {
+        recipient: Pubkey::from_str(recipient)?,
+        bps: bps.parse()?,
+    })
+}
+
+fn parse_registry_status(s: &str) -> Result<RegistryStatus> {
+    match s {
+        "allowed" => Ok(RegistryStatus::Allowed),
+        "denied" => Ok(RegistryStatus::Denied),
+        "unlisted" => Ok(RegistryStatus::Unlisted),
+        _ => Err(format_err!(
+            "expected allowed, denied or unlisted, got {}",
+            s
+        )),
+    }
+}
+
 #[derive(Debug, Parser)]
 pub struct Opts {
     #[clap(subcommand)]
@@ -85,24 +247,324 @@ pub enum RaydiumCpCommands {
         amount: u64,
         #[arg(long)]
         duration: u64,
+        /// Start the duration at the pool open time if the pool has not opened yet
+        #[arg(long, conflicts_with = "rolling")]
+        from_open_time: bool,
+        /// Keep renewing the lock until `stop-rolling` is called
+        #[arg(long)]
+        rolling: bool,
+        /// Beneficiaries receiving the LP on unlock as `<pubkey>:<bps>`, bps summing to 10000
+        #[arg(long, num_args = 1.., value_parser = parse_fee_split_recipient)]
+        beneficiaries: Vec<FeeSplitRecipient>,
+        /// Human-readable name of the lock
+        #[arg(long, default_value = "")]
+        label: String,
+        /// Link to off-chain metadata of the lock
+        #[arg(long, default_value = "")]
+        uri: String,
     },
     LockLpPermanently {
         #[arg(long)]
         pool_id: Pubkey,
         #[arg(long)]
         amount: u64,
+        /// Human-readable name of the lock
+        #[arg(long, default_value = "")]
+        label: String,
+        /// Link to off-chain metadata of the lock
+        #[arg(long, default_value = "")]
+        uri: String,
+    },
+    /// Deposit token_0/token_1 into the pool and lock the minted LP in one transaction
+    DepositAndLock {
+        #[arg(long)]
+        pool_id: Pubkey,
+        #[arg(long)]
+        lp_amount: u64,
+        #[arg(long)]
+        maximum_token_0_amount: u64,
+        #[arg(long)]
+        maximum_token_1_amount: u64,
+        #[arg(long, default_value_t = 0)]
+        duration: u64,
+        #[arg(long)]
+        permanent: bool,
+        /// Human-readable name of the lock
+        #[arg(long, default_value = "")]
+        label: String,
+        /// Link to off-chain metadata of the lock
+        #[arg(long, default_value = "")]
+        uri: String,
+    },
+    /// Replace the label and URI of a lock
+    UpdateLockMetadata {
+        #[arg(long)]
+        pool_id: Pubkey,
+        #[arg(long)]
+        lock_id: u64,
+        /// Human-readable name of the lock
+        #[arg(long, default_value = "")]
+        label: String,
+        /// Link to off-chain metadata of the lock
+        #[arg(long, default_value = "")]
+        uri: String,
+    },
+    /// Burn a lock's fee LP into its fee vaults; anyone can crank this
+    HarvestFees {
+        #[arg(long)]
+        pool_id: Pubkey,
+        /// Owner of the lock (defaults to the payer)
+        #[arg(long)]
+        owner: Option<Pubkey>,
+        #[arg(long)]
+        lock_id: u64,
+    },
+    /// Drain the fees harvested into a lock's fee vaults
+    ClaimHarvested {
+        #[arg(long)]
+        pool_id: Pubkey,
+        #[arg(long)]
+        lock_id: u64,
+    },
+    /// Stop a rolling lock from renewing, starting its countdown
+    StopRolling {
+        #[arg(long)]
+        pool_id: Pubkey,
+        #[arg(long)]
+        lock_id: u64,
+    },
+    /// Require notice, in seconds, between requesting an unlock and unlocking
+    SetNoticePeriod {
+        #[arg(long)]
+        pool_id: Pubkey,
+        #[arg(long)]
+        lock_id: u64,
+        #[arg(long)]
+        notice_period: u64,
+    },
+    /// Announce an unlock and start the lock's notice period
+    RequestUnlock {
+        #[arg(long)]
+        pool_id: Pubkey,
+        #[arg(long)]
+        lock_id: u64,
+    },
+    CancelUnlockRequest {
+        #[arg(long)]
+        pool_id: Pubkey,
+        #[arg(long)]
+        lock_id: u64,
+    },
+    /// Encumber a lock, by program-wide ID, with a lien held by `lienholder`
+    GrantLien {
+        #[arg(long)]
+        lock_id: u64,
+        #[arg(long)]
+        lienholder: Pubkey,
+    },
+    /// Release a lien held by the payer, by program-wide lock ID
+    ReleaseLien {
+        #[arg(long)]
+        lock_id: u64,
+    },
+    /// List a lock, by program-wide ID, for sale at `price` in SOL or `price_mint`
+    ListLock {
+        #[arg(long)]
+        lock_id: u64,
+        /// Ask price in lamports, or base units of `price_mint`
+        #[arg(long)]
+        price: u64,
+        #[arg(long)]
+        price_mint: Option<Pubkey>,
+    },
+    UpdateListing {
+        #[arg(long)]
+        lock_id: u64,
+        #[arg(long)]
+        price: u64,
+        #[arg(long)]
+        price_mint: Option<Pubkey>,
+    },
+    CancelListing {
+        #[arg(long)]
+        lock_id: u64,
+    },
+    /// Buy a listed lock, by program-wide ID, paying at most `max_price`
+    BuyLock {
+        #[arg(long)]
+        lock_id: u64,
+        #[arg(long)]
+        max_price: u64,
+    },
+    /// Seize a lock the payer holds a lien on, by program-wide lock ID
+    Foreclose {
+        #[arg(long)]
+        lock_id: u64,
+        /// Owner the lock is handed to, the payer by default
+        #[arg(long)]
+        new_owner: Option<Pubkey>,
     },
     UnlockLp {
         #[arg(long)]
         pool_id: Pubkey,
         #[arg(long)]
         lock_id: u64,
+        /// Withdraw the unlocked LP from the pool and receive the underlying tokens
+        #[arg(long)]
+        withdraw: bool,
+        #[arg(long, default_value_t = 0)]
+        minimum_token_0_amount: u64,
+        #[arg(long, default_value_t = 0)]
+        minimum_token_1_amount: u64,
+    },
+    /// Credit LP sent directly to a lock vault as principal, or sweep it back
+    SyncLockVault {
+        #[arg(long)]
+        pool_id: Pubkey,
+        #[arg(long)]
+        lock_id: u64,
+        #[arg(long)]
+        credit: bool,
     },
     CollectFees {
         #[arg(long)]
         pool_id: Pubkey,
         #[arg(long)]
         lock_id: u64,
+        /// Collect for this lock owner as its collect delegate, taking the keeper tip in LP
+        #[arg(long, conflicts_with = "output_mint")]
+        owner: Option<Pubkey>,
+        /// Swap the other side into this mint and receive fees in it only
+        #[arg(long)]
+        output_mint: Option<Pubkey>,
+        /// Minimum total amount of `output_mint` to receive
+        #[arg(long, default_value_t = 0)]
+        minimum_amount_out: u64,
+        /// Receive the fees as LP tokens, e.g. while the pool has withdrawals disabled
+        #[arg(long, conflicts_with_all = ["owner", "output_mint"])]
+        as_lp: bool,
+    },
+    /// Let a keeper collect a lock's fees, or revoke it by omitting `--delegate`
+    SetCollectDelegate {
+        #[arg(long)]
+        pool_id: Pubkey,
+        #[arg(long)]
+        lock_id: u64,
+        #[arg(long)]
+        delegate: Option<Pubkey>,
+        /// Share of collected fees paid to the delegate, in basis points
+        #[arg(long, default_value_t = 0)]
+        tip_bps: u16,
+    },
+    InitRewardPool {
+        #[arg(long)]
+        pool_id: Pubkey,
+        /// Campaign index, defaults to the one after the LP mint's latest campaign
+        #[arg(long)]
+        campaign: Option<u64>,
+        #[arg(long)]
+        reward_mint: Pubkey,
+        #[arg(long)]
+        emission_rate: u64,
+        #[arg(long)]
+        end_time: u64,
+    },
+    /// Return the rewards no lock earned from an ended campaign of the payer
+    SweepRewardPool {
+        #[arg(long)]
+        pool_id: Pubkey,
+        #[arg(long)]
+        campaign: u64,
+    },
+    ClaimRewards {
+        #[arg(long)]
+        pool_id: Pubkey,
+        #[arg(long)]
+        lock_id: u64,
+    },
+    /// Claim the rewards a lock of the payer left unclaimed when it was unlocked
+    ClaimRewardReceipt {
+        #[arg(long)]
+        pool_id: Pubkey,
+        #[arg(long)]
+        lock_id: u64,
+    },
+    /// Re-weight any enrolled lock in its reward pool, e.g. once it has expired,
+    /// or enroll one of the payer's locks in the LP mint's reward pool
+    CheckpointRewards {
+        #[arg(long)]
+        lock_id: u64,
+    },
+    SetFeeSplit {
+        #[arg(long)]
+        pool_id: Pubkey,
+        #[arg(long)]
+        lock_id: u64,
+        /// Recipients as `<pubkey>:<bps>`, bps summing to 10000
+        #[arg(long, num_args = 1.., value_parser = parse_fee_split_recipient)]
+        recipients: Vec<FeeSplitRecipient>,
+    },
+    RemoveFeeSplit {
+        #[arg(long)]
+        pool_id: Pubkey,
+        #[arg(long)]
+        lock_id: u64,
+    },
+    /// Enable or disable allow-list mode of the pool registry (admin only)
+    SetRegistryConfig {
+        #[arg(long)]
+        allow_list_enabled: bool,
+    },
+    /// Create the pool registry and appoint its admin (program upgrade authority only)
+    InitRegistry {
+        #[arg(long)]
+        admin: Pubkey,
+    },
+    /// Hand the pool registry over to a new admin (admin only)
+    SetRegistryAdmin {
+        #[arg(long)]
+        new_admin: Pubkey,
+    },
+    /// Allow, deny or unlist a pool id or token mint (admin only)
+    SetRegistryEntry {
+        #[arg(long)]
+        key: Pubkey,
+        /// One of `allowed`, `denied` or `unlisted`
+        #[arg(long, value_parser = parse_registry_status)]
+        status: RegistryStatus,
+    },
+    /// List the lock ids of a user's active locks for a pool
+    ListLocks {
+        #[arg(long)]
+        pool_id: Pubkey,
+        #[arg(long)]
+        user: Option<Pubkey>,
+    },
+    /// Look up a lock by its program-wide ID
+    ShowLock {
+        #[arg(long)]
+        lock_id: u64,
+    },
+    VotingPower {
+        #[arg(long)]
+        pool_id: Pubkey,
+        #[arg(long)]
+        user: Option<Pubkey>,
+        #[arg(long)]
+        timestamp: Option<u64>,
+    },
+    /// Show the LP locked by the pool creator and its share of the LP supply
+    CreatorLockShare {
+        #[arg(long)]
+        pool_id: Pubkey,
+    },
+    /// Bring a lock and lock counter created by the first release up to the current layout,
+    /// and give the lock a program-wide ID if it has none
+    MigrateLock {
+        #[arg(long)]
+        pool_id: Pubkey,
+        #[arg(long)]
+        lock_id: u64,
     },
 }
 
@@ -127,6 +589,11 @@ fn main() -> Result<()> {
             pool_id,
             amount,
             duration,
+            from_open_time,
+            rolling,
+            beneficiaries,
+            label,
+            uri,
         } => {
             let pool_state: cpmm_lp_lock::states::PoolState = program.account(pool_id)?;
             let lp_mint = pool_state.lp_mint;
@@ -137,7 +604,7 @@ fn main() -> Result<()> {
             let (lp_lock_counter, _) = Pubkey::find_program_address(
                 &[
                     LP_LOCK_COUNTER_SEED.as_bytes(),
-                    &payer.pubkey().as_ref(),
+                    payer.pubkey().as_ref(),
                     lp_mint.as_ref(),
                 ],
                 &program.id(),
@@ -147,7 +614,7 @@ fn main() -> Result<()> {
             let (user_lp_lock, _) = Pubkey::find_program_address(
                 &[
                     USER_LOCK_SEED.as_bytes(),
-                    &payer.pubkey().as_ref(),
+                    payer.pubkey().as_ref(),
                     lp_mint.as_ref(),
                     lp_lock_counter_info
                         .total_lock_count
@@ -161,7 +628,7 @@ fn main() -> Result<()> {
             let (lp_lock_vault, _) = Pubkey::find_program_address(
                 &[
                     LP_LOCK_VAULT_SEED.as_bytes(),
-                    &payer.pubkey().as_ref(),
+                    payer.pubkey().as_ref(),
                     lp_mint.as_ref(),
                     lp_lock_counter_info
                         .total_lock_count
@@ -179,11 +646,30 @@ fn main() -> Result<()> {
                 pool_state.lp_mint,
                 lp_lock_counter,
                 user_lp_lock,
+                find_active_lock_page(
+                    &program.id(),
+                    &payer.pubkey(),
+                    &lp_mint,
+                    lp_lock_counter_info.total_lock_count.add(1),
+                ),
+                find_next_lock_id_index(&program),
                 lp_lock_vault,
                 pool_state.token_0_vault,
                 pool_state.token_1_vault,
+                pool_state.token_0_mint,
+                pool_state.token_1_mint,
+                find_reward_pool(&rpc_client, &program.id(), &lp_mint),
                 amount,
                 duration,
+                if from_open_time {
+                    LockMode::FromOpenTime
+                } else if rolling {
+                    LockMode::Rolling
+                } else {
+                    LockMode::Timed
+                },
+                LockMetadata { label, uri },
+                beneficiaries,
             )?;
             instructions.extend(lock_lp_instr);
             let signers = vec![&payer];
@@ -197,7 +683,12 @@ fn main() -> Result<()> {
             let signature = send_txn(&rpc_client, &txn, true)?;
             println!("{}", signature);
         }
-        RaydiumCpCommands::LockLpPermanently { pool_id, amount } => {
+        RaydiumCpCommands::LockLpPermanently {
+            pool_id,
+            amount,
+            label,
+            uri,
+        } => {
             let pool_state: cpmm_lp_lock::states::PoolState = program.account(pool_id)?;
             let lp_mint = pool_state.lp_mint;
             let owner_lp_token_account = spl_associated_token_account::get_associated_token_address(
@@ -207,7 +698,7 @@ fn main() -> Result<()> {
             let (lp_lock_counter, _) = Pubkey::find_program_address(
                 &[
                     LP_LOCK_COUNTER_SEED.as_bytes(),
-                    &payer.pubkey().as_ref(),
+                    payer.pubkey().as_ref(),
                     lp_mint.as_ref(),
                 ],
                 &program.id(),
@@ -217,7 +708,7 @@ fn main() -> Result<()> {
             let (user_lp_lock, _) = Pubkey::find_program_address(
                 &[
                     USER_LOCK_SEED.as_bytes(),
-                    &payer.pubkey().as_ref(),
+                    payer.pubkey().as_ref(),
                     lp_mint.as_ref(),
                     lp_lock_counter_info
                         .total_lock_count
@@ -231,7 +722,7 @@ fn main() -> Result<()> {
             let (lp_lock_vault, _) = Pubkey::find_program_address(
                 &[
                     LP_LOCK_VAULT_SEED.as_bytes(),
-                    &payer.pubkey().as_ref(),
+                    payer.pubkey().as_ref(),
                     lp_mint.as_ref(),
                     lp_lock_counter_info
                         .total_lock_count
@@ -249,10 +740,21 @@ fn main() -> Result<()> {
                 pool_state.lp_mint,
                 lp_lock_counter,
                 user_lp_lock,
+                find_active_lock_page(
+                    &program.id(),
+                    &payer.pubkey(),
+                    &lp_mint,
+                    lp_lock_counter_info.total_lock_count.add(1),
+                ),
+                find_next_lock_id_index(&program),
                 lp_lock_vault,
                 pool_state.token_0_vault,
                 pool_state.token_1_vault,
+                pool_state.token_0_mint,
+                pool_state.token_1_mint,
+                find_reward_pool(&rpc_client, &program.id(), &lp_mint),
                 amount,
+                LockMetadata { label, uri },
             )?;
             instructions.extend(lock_lp_instr);
             let signers = vec![&payer];
@@ -266,7 +768,16 @@ fn main() -> Result<()> {
             let signature = send_txn(&rpc_client, &txn, true)?;
             println!("{}", signature);
         }
-        RaydiumCpCommands::UnlockLp { pool_id, lock_id } => {
+        RaydiumCpCommands::DepositAndLock {
+            pool_id,
+            lp_amount,
+            maximum_token_0_amount,
+            maximum_token_1_amount,
+            duration,
+            permanent,
+            label,
+            uri,
+        } => {
             let pool_state: cpmm_lp_lock::states::PoolState = program.account(pool_id)?;
             let lp_mint = pool_state.lp_mint;
             let owner_lp_token_account = spl_associated_token_account::get_associated_token_address(
@@ -276,17 +787,23 @@ fn main() -> Result<()> {
             let (lp_lock_counter, _) = Pubkey::find_program_address(
                 &[
                     LP_LOCK_COUNTER_SEED.as_bytes(),
-                    &payer.pubkey().as_ref(),
+                    payer.pubkey().as_ref(),
                     lp_mint.as_ref(),
                 ],
                 &program.id(),
             );
+            let lp_lock_counter_info: cpmm_lp_lock::states::LpLockCounter =
+                program.account(lp_lock_counter).unwrap_or_default();
             let (user_lp_lock, _) = Pubkey::find_program_address(
                 &[
                     USER_LOCK_SEED.as_bytes(),
-                    &payer.pubkey().as_ref(),
+                    payer.pubkey().as_ref(),
                     lp_mint.as_ref(),
-                    lock_id.to_le_bytes().as_ref(),
+                    lp_lock_counter_info
+                        .total_lock_count
+                        .add(1)
+                        .to_le_bytes()
+                        .as_ref(),
                 ],
                 &program.id(),
             );
@@ -294,23 +811,64 @@ fn main() -> Result<()> {
             let (lp_lock_vault, _) = Pubkey::find_program_address(
                 &[
                     LP_LOCK_VAULT_SEED.as_bytes(),
-                    &payer.pubkey().as_ref(),
+                    payer.pubkey().as_ref(),
                     lp_mint.as_ref(),
-                    lock_id.to_le_bytes().as_ref(),
+                    lp_lock_counter_info
+                        .total_lock_count
+                        .add(1)
+                        .to_le_bytes()
+                        .as_ref(),
                 ],
                 &program.id(),
             );
             let mut instructions = Vec::new();
-            let lock_lp_instr = unlock_lp_instr(
+            // The minted LP passes through the owner's LP account, so make sure it exists
+            instructions.push(
+                spl_associated_token_account::instruction::create_associated_token_account_idempotent(
+                    &payer.pubkey(),
+                    &payer.pubkey(),
+                    &lp_mint,
+                    &spl_token::id(),
+                ),
+            );
+            let deposit_and_lock_instr = deposit_and_lock_instr(
                 &pool_config,
                 pool_id,
                 owner_lp_token_account,
                 pool_state.lp_mint,
                 lp_lock_counter,
                 user_lp_lock,
+                find_active_lock_page(
+                    &program.id(),
+                    &payer.pubkey(),
+                    &lp_mint,
+                    lp_lock_counter_info.total_lock_count.add(1),
+                ),
+                find_next_lock_id_index(&program),
                 lp_lock_vault,
+                get_associated_token_address_with_program_id(
+                    &payer.pubkey(),
+                    &pool_state.token_0_mint,
+                    &pool_state.token_0_program,
+                ),
+                get_associated_token_address_with_program_id(
+                    &payer.pubkey(),
+                    &pool_state.token_1_mint,
+                    &pool_state.token_1_program,
+                ),
+                pool_state.token_0_vault,
+                pool_state.token_1_vault,
+                pool_state.token_0_mint,
+                pool_state.token_1_mint,
+                find_reward_pool(&rpc_client, &program.id(), &lp_mint),
+                lp_amount,
+                maximum_token_0_amount,
+                maximum_token_1_amount,
+                duration,
+                permanent,
+                LockMetadata { label, uri },
             )?;
-            instructions.extend(lock_lp_instr);
+            instructions.extend(deposit_and_lock_instr);
             let signers = vec![&payer];
             let recent_hash = rpc_client.get_latest_blockhash()?;
             let txn = Transaction::new_signed_with_payer(
@@ -322,50 +880,118 @@ fn main() -> Result<()> {
             let signature = send_txn(&rpc_client, &txn, true)?;
             println!("{}", signature);
         }
-        RaydiumCpCommands::CollectFees { pool_id, lock_id } => {
+        RaydiumCpCommands::UpdateLockMetadata {
+            pool_id,
+            lock_id,
+            label,
+            uri,
+        } => {
             let pool_state: cpmm_lp_lock::states::PoolState = program.account(pool_id)?;
-            let lp_mint = pool_state.lp_mint;
-            let owner_lp_token_account = spl_associated_token_account::get_associated_token_address(
-                &payer.pubkey(),
-                &lp_mint,
-            );
-            let (lp_lock_counter, _) = Pubkey::find_program_address(
-                &[
-                    LP_LOCK_COUNTER_SEED.as_bytes(),
-                    &payer.pubkey().as_ref(),
-                    lp_mint.as_ref(),
-                ],
-                &program.id(),
-            );
             let (user_lp_lock, _) = Pubkey::find_program_address(
                 &[
                     USER_LOCK_SEED.as_bytes(),
-                    &payer.pubkey().as_ref(),
-                    lp_mint.as_ref(),
-                    lock_id.to_le_bytes().as_ref(),
-                ],
-                &program.id(),
-            );
-
-            let (lp_lock_vault, _) = Pubkey::find_program_address(
-                &[
-                    LP_LOCK_VAULT_SEED.as_bytes(),
-                    &payer.pubkey().as_ref(),
-                    lp_mint.as_ref(),
+                    payer.pubkey().as_ref(),
+                    pool_state.lp_mint.as_ref(),
                     lock_id.to_le_bytes().as_ref(),
                 ],
                 &program.id(),
             );
             let mut instructions = Vec::new();
-            let lock_lp_instr = collect_fees_instr(
+            let update_lock_metadata_instr = update_lock_metadata_instr(
                 &pool_config,
-                pool_id,
-                owner_lp_token_account,
-                pool_state.lp_mint,
-                lp_lock_counter,
                 user_lp_lock,
-                lp_lock_vault,
-                get_associated_token_address_with_program_id(
+                LockMetadata { label, uri },
+            )?;
+            instructions.extend(update_lock_metadata_instr);
+            let signers = vec![&payer];
+            let recent_hash = rpc_client.get_latest_blockhash()?;
+            let txn = Transaction::new_signed_with_payer(
+                &instructions,
+                Some(&payer.pubkey()),
+                &signers,
+                recent_hash,
+            );
+            let signature = send_txn(&rpc_client, &txn, true)?;
+            println!("{}", signature);
+        }
+        RaydiumCpCommands::HarvestFees {
+            pool_id,
+            owner,
+            lock_id,
+        } => {
+            let owner = owner.unwrap_or(payer.pubkey());
+            let pool_state: cpmm_lp_lock::states::PoolState = program.account(pool_id)?;
+            let lp_mint = pool_state.lp_mint;
+            let (lp_lock_counter, _) = Pubkey::find_program_address(
+                &[
+                    LP_LOCK_COUNTER_SEED.as_bytes(),
+                    owner.as_ref(),
+                    lp_mint.as_ref(),
+                ],
+                &program.id(),
+            );
+            let (user_lp_lock, _) = Pubkey::find_program_address(
+                &[
+                    USER_LOCK_SEED.as_bytes(),
+                    owner.as_ref(),
+                    lp_mint.as_ref(),
+                    lock_id.to_le_bytes().as_ref(),
+                ],
+                &program.id(),
+            );
+            let (lp_lock_vault, _) = Pubkey::find_program_address(
+                &[
+                    LP_LOCK_VAULT_SEED.as_bytes(),
+                    owner.as_ref(),
+                    lp_mint.as_ref(),
+                    lock_id.to_le_bytes().as_ref(),
+                ],
+                &program.id(),
+            );
+            let mut instructions = Vec::new();
+            let harvest_fees_instr = harvest_fees_instr(
+                &pool_config,
+                pool_id,
+                owner,
+                lp_mint,
+                lp_lock_counter,
+                user_lp_lock,
+                lp_lock_vault,
+                pool_state.token_0_vault,
+                pool_state.token_1_vault,
+                pool_state.token_0_mint,
+                pool_state.token_1_mint,
+                find_enrolled_reward_pool(&program, user_lp_lock)?,
+            )?;
+            instructions.extend(harvest_fees_instr);
+            let signers = vec![&payer];
+            let recent_hash = rpc_client.get_latest_blockhash()?;
+            let txn = Transaction::new_signed_with_payer(
+                &instructions,
+                Some(&payer.pubkey()),
+                &signers,
+                recent_hash,
+            );
+            let signature = send_txn(&rpc_client, &txn, true)?;
+            println!("{}", signature);
+        }
+        RaydiumCpCommands::ClaimHarvested { pool_id, lock_id } => {
+            let pool_state: cpmm_lp_lock::states::PoolState = program.account(pool_id)?;
+            let (user_lp_lock, _) = Pubkey::find_program_address(
+                &[
+                    USER_LOCK_SEED.as_bytes(),
+                    payer.pubkey().as_ref(),
+                    pool_state.lp_mint.as_ref(),
+                    lock_id.to_le_bytes().as_ref(),
+                ],
+                &program.id(),
+            );
+            let user_lock: cpmm_lp_lock::states::UserLock = program.account(user_lp_lock)?;
+            let mut instructions = Vec::new();
+            let claim_harvested_instr = claim_harvested_instr(
+                &pool_config,
+                user_lp_lock,
+                get_associated_token_address_with_program_id(
                     &payer.pubkey(),
                     &pool_state.token_0_mint,
                     &pool_state.token_0_program,
@@ -375,11 +1001,234 @@ fn main() -> Result<()> {
                     &pool_state.token_1_mint,
                     &pool_state.token_1_program,
                 ),
-                pool_state.token_0_vault,
-                pool_state.token_1_vault,
                 pool_state.token_0_mint,
                 pool_state.token_1_mint,
+                user_lock.harvester,
+            )?;
+            instructions.extend(claim_harvested_instr);
+            let signers = vec![&payer];
+            let recent_hash = rpc_client.get_latest_blockhash()?;
+            let txn = Transaction::new_signed_with_payer(
+                &instructions,
+                Some(&payer.pubkey()),
+                &signers,
+                recent_hash,
+            );
+            let signature = send_txn(&rpc_client, &txn, true)?;
+            println!("{}", signature);
+        }
+        RaydiumCpCommands::StopRolling { pool_id, lock_id } => {
+            let pool_state: cpmm_lp_lock::states::PoolState = program.account(pool_id)?;
+            let lp_mint = pool_state.lp_mint;
+            let (user_lp_lock, _) = Pubkey::find_program_address(
+                &[
+                    USER_LOCK_SEED.as_bytes(),
+                    payer.pubkey().as_ref(),
+                    lp_mint.as_ref(),
+                    lock_id.to_le_bytes().as_ref(),
+                ],
+                &program.id(),
+            );
+            let (lp_lock_counter, _) = Pubkey::find_program_address(
+                &[
+                    LP_LOCK_COUNTER_SEED.as_bytes(),
+                    payer.pubkey().as_ref(),
+                    lp_mint.as_ref(),
+                ],
+                &program.id(),
+            );
+            let mut instructions = Vec::new();
+            let stop_rolling_instr = stop_rolling_instr(
+                &pool_config,
+                user_lp_lock,
+                lp_lock_counter,
+                lp_mint,
+                find_enrolled_reward_pool(&program, user_lp_lock)?,
             )?;
+            instructions.extend(stop_rolling_instr);
+            let signers = vec![&payer];
+            let recent_hash = rpc_client.get_latest_blockhash()?;
+            let txn = Transaction::new_signed_with_payer(
+                &instructions,
+                Some(&payer.pubkey()),
+                &signers,
+                recent_hash,
+            );
+            let signature = send_txn(&rpc_client, &txn, true)?;
+            println!("{}", signature);
+        }
+        RaydiumCpCommands::SetNoticePeriod {
+            pool_id,
+            lock_id,
+            notice_period,
+        } => {
+            let pool_state: cpmm_lp_lock::states::PoolState = program.account(pool_id)?;
+            let (user_lp_lock, _) = Pubkey::find_program_address(
+                &[
+                    USER_LOCK_SEED.as_bytes(),
+                    payer.pubkey().as_ref(),
+                    pool_state.lp_mint.as_ref(),
+                    lock_id.to_le_bytes().as_ref(),
+                ],
+                &program.id(),
+            );
+            let instructions = set_notice_period_instr(&pool_config, user_lp_lock, notice_period)?;
+            let signers = vec![&payer];
+            let recent_hash = rpc_client.get_latest_blockhash()?;
+            let txn = Transaction::new_signed_with_payer(
+                &instructions,
+                Some(&payer.pubkey()),
+                &signers,
+                recent_hash,
+            );
+            let signature = send_txn(&rpc_client, &txn, true)?;
+            println!("{}", signature);
+        }
+        RaydiumCpCommands::RequestUnlock { pool_id, lock_id } => {
+            let pool_state: cpmm_lp_lock::states::PoolState = program.account(pool_id)?;
+            let (user_lp_lock, _) = Pubkey::find_program_address(
+                &[
+                    USER_LOCK_SEED.as_bytes(),
+                    payer.pubkey().as_ref(),
+                    pool_state.lp_mint.as_ref(),
+                    lock_id.to_le_bytes().as_ref(),
+                ],
+                &program.id(),
+            );
+            let instructions = request_unlock_instr(&pool_config, user_lp_lock)?;
+            let signers = vec![&payer];
+            let recent_hash = rpc_client.get_latest_blockhash()?;
+            let txn = Transaction::new_signed_with_payer(
+                &instructions,
+                Some(&payer.pubkey()),
+                &signers,
+                recent_hash,
+            );
+            let signature = send_txn(&rpc_client, &txn, true)?;
+            println!("{}", signature);
+        }
+        RaydiumCpCommands::CancelUnlockRequest { pool_id, lock_id } => {
+            let pool_state: cpmm_lp_lock::states::PoolState = program.account(pool_id)?;
+            let (user_lp_lock, _) = Pubkey::find_program_address(
+                &[
+                    USER_LOCK_SEED.as_bytes(),
+                    payer.pubkey().as_ref(),
+                    pool_state.lp_mint.as_ref(),
+                    lock_id.to_le_bytes().as_ref(),
+                ],
+                &program.id(),
+            );
+            let instructions = cancel_unlock_request_instr(&pool_config, user_lp_lock)?;
+            let signers = vec![&payer];
+            let recent_hash = rpc_client.get_latest_blockhash()?;
+            let txn = Transaction::new_signed_with_payer(
+                &instructions,
+                Some(&payer.pubkey()),
+                &signers,
+                recent_hash,
+            );
+            let signature = send_txn(&rpc_client, &txn, true)?;
+            println!("{}", signature);
+        }
+        RaydiumCpCommands::UnlockLp {
+            pool_id,
+            lock_id,
+            withdraw,
+            minimum_token_0_amount,
+            minimum_token_1_amount,
+        } => {
+            let pool_state: cpmm_lp_lock::states::PoolState = program.account(pool_id)?;
+            let lp_mint = pool_state.lp_mint;
+            let owner_lp_token_account = spl_associated_token_account::get_associated_token_address(
+                &payer.pubkey(),
+                &lp_mint,
+            );
+            let (lp_lock_counter, _) = Pubkey::find_program_address(
+                &[
+                    LP_LOCK_COUNTER_SEED.as_bytes(),
+                    payer.pubkey().as_ref(),
+                    lp_mint.as_ref(),
+                ],
+                &program.id(),
+            );
+            let (user_lp_lock, _) = Pubkey::find_program_address(
+                &[
+                    USER_LOCK_SEED.as_bytes(),
+                    payer.pubkey().as_ref(),
+                    lp_mint.as_ref(),
+                    lock_id.to_le_bytes().as_ref(),
+                ],
+                &program.id(),
+            );
+
+            let (lp_lock_vault, _) = Pubkey::find_program_address(
+                &[
+                    LP_LOCK_VAULT_SEED.as_bytes(),
+                    payer.pubkey().as_ref(),
+                    lp_mint.as_ref(),
+                    lock_id.to_le_bytes().as_ref(),
+                ],
+                &program.id(),
+            );
+            let user_lp_lock_info: cpmm_lp_lock::states::UserLock =
+                program.account(user_lp_lock)?;
+            let mut instructions = Vec::new();
+            let lock_lp_instr = if withdraw {
+                unlock_and_withdraw_instr(
+                    &pool_config,
+                    pool_id,
+                    pool_state.lp_mint,
+                    lp_lock_counter,
+                    user_lp_lock,
+                    find_held_active_lock_page(&program.id(), &payer.pubkey(), &user_lp_lock_info),
+                    lp_lock_vault,
+                    find_lock_id_index(&program.id(), user_lp_lock_info.lock_id),
+                    *user_lp_lock_info.rent_recipient(),
+                    get_associated_token_address_with_program_id(
+                        &payer.pubkey(),
+                        &pool_state.token_0_mint,
+                        &pool_state.token_0_program,
+                    ),
+                    get_associated_token_address_with_program_id(
+                        &payer.pubkey(),
+                        &pool_state.token_1_mint,
+                        &pool_state.token_1_program,
+                    ),
+                    pool_state.token_0_vault,
+                    pool_state.token_1_vault,
+                    pool_state.token_0_mint,
+                    pool_state.token_1_mint,
+                    (user_lp_lock_info.reward_pool != Pubkey::default())
+                        .then_some(user_lp_lock_info.reward_pool),
+                    minimum_token_0_amount,
+                    minimum_token_1_amount,
+                )?
+            } else {
+                unlock_lp_instr(
+                    &pool_config,
+                    pool_id,
+                    owner_lp_token_account,
+                    pool_state.lp_mint,
+                    lp_lock_counter,
+                    user_lp_lock,
+                    find_held_active_lock_page(&program.id(), &payer.pubkey(), &user_lp_lock_info),
+                    lp_lock_vault,
+                    find_lock_id_index(&program.id(), user_lp_lock_info.lock_id),
+                    *user_lp_lock_info.rent_recipient(),
+                    (user_lp_lock_info.reward_pool != Pubkey::default())
+                        .then_some(user_lp_lock_info.reward_pool),
+                    user_lp_lock_info
+                        .beneficiaries
+                        .iter()
+                        .map(|beneficiary| {
+                            spl_associated_token_account::get_associated_token_address(
+                                &beneficiary.recipient,
+                                &lp_mint,
+                            )
+                        })
+                        .collect(),
+                )?
+            };
             instructions.extend(lock_lp_instr);
             let signers = vec![&payer];
             let recent_hash = rpc_client.get_latest_blockhash()?;
@@ -392,6 +1241,996 @@ fn main() -> Result<()> {
             let signature = send_txn(&rpc_client, &txn, true)?;
             println!("{}", signature);
         }
+        RaydiumCpCommands::SyncLockVault {
+            pool_id,
+            lock_id,
+            credit,
+        } => {
+            let pool_state: cpmm_lp_lock::states::PoolState = program.account(pool_id)?;
+            let lp_mint = pool_state.lp_mint;
+            let owner_lp_token_account = spl_associated_token_account::get_associated_token_address(
+                &payer.pubkey(),
+                &lp_mint,
+            );
+            let (lp_lock_counter, _) = Pubkey::find_program_address(
+                &[
+                    LP_LOCK_COUNTER_SEED.as_bytes(),
+                    payer.pubkey().as_ref(),
+                    lp_mint.as_ref(),
+                ],
+                &program.id(),
+            );
+            let (user_lp_lock, _) = Pubkey::find_program_address(
+                &[
+                    USER_LOCK_SEED.as_bytes(),
+                    payer.pubkey().as_ref(),
+                    lp_mint.as_ref(),
+                    lock_id.to_le_bytes().as_ref(),
+                ],
+                &program.id(),
+            );
+            let (lp_lock_vault, _) = Pubkey::find_program_address(
+                &[
+                    LP_LOCK_VAULT_SEED.as_bytes(),
+                    payer.pubkey().as_ref(),
+                    lp_mint.as_ref(),
+                    lock_id.to_le_bytes().as_ref(),
+                ],
+                &program.id(),
+            );
+            let mut instructions = Vec::new();
+            let sync_lock_vault_instr = sync_lock_vault_instr(
+                &pool_config,
+                pool_id,
+                owner_lp_token_account,
+                lp_mint,
+                lp_lock_counter,
+                user_lp_lock,
+                lp_lock_vault,
+                pool_state.token_0_vault,
+                pool_state.token_1_vault,
+                find_enrolled_reward_pool(&program, user_lp_lock)?,
+                credit,
+            )?;
+            instructions.extend(sync_lock_vault_instr);
+            let signers = vec![&payer];
+            let recent_hash = rpc_client.get_latest_blockhash()?;
+            let txn = Transaction::new_signed_with_payer(
+                &instructions,
+                Some(&payer.pubkey()),
+                &signers,
+                recent_hash,
+            );
+            let signature = send_txn(&rpc_client, &txn, true)?;
+            println!("{}", signature);
+        }
+        RaydiumCpCommands::CollectFees {
+            pool_id,
+            lock_id,
+            owner,
+            output_mint,
+            minimum_amount_out,
+            as_lp,
+        } => {
+            // With `--owner`, the payer collects as the lock's collect delegate
+            let lock_owner = owner.unwrap_or(payer.pubkey());
+            let pool_state: cpmm_lp_lock::states::PoolState = program.account(pool_id)?;
+            let lp_mint = pool_state.lp_mint;
+            let owner_lp_token_account = spl_associated_token_account::get_associated_token_address(
+                &payer.pubkey(),
+                &lp_mint,
+            );
+            let (lp_lock_counter, _) = Pubkey::find_program_address(
+                &[
+                    LP_LOCK_COUNTER_SEED.as_bytes(),
+                    lock_owner.as_ref(),
+                    lp_mint.as_ref(),
+                ],
+                &program.id(),
+            );
+            let (user_lp_lock, _) = Pubkey::find_program_address(
+                &[
+                    USER_LOCK_SEED.as_bytes(),
+                    lock_owner.as_ref(),
+                    lp_mint.as_ref(),
+                    lock_id.to_le_bytes().as_ref(),
+                ],
+                &program.id(),
+            );
+
+            let (lp_lock_vault, _) = Pubkey::find_program_address(
+                &[
+                    LP_LOCK_VAULT_SEED.as_bytes(),
+                    lock_owner.as_ref(),
+                    lp_mint.as_ref(),
+                    lock_id.to_le_bytes().as_ref(),
+                ],
+                &program.id(),
+            );
+            let user_lock_info: cpmm_lp_lock::states::UserLock = program.account(user_lp_lock)?;
+            let (fee_split, fee_split_recipient_accounts) = if user_lock_info.has_fee_split {
+                let (fee_split, _) = Pubkey::find_program_address(
+                    &[FEE_SPLIT_SEED.as_bytes(), user_lp_lock.as_ref()],
+                    &program.id(),
+                );
+                let fee_split_info: cpmm_lp_lock::states::FeeSplit = program.account(fee_split)?;
+                let mut recipient_accounts = Vec::new();
+                for recipient in fee_split_info.recipients.iter() {
+                    recipient_accounts.push(get_associated_token_address_with_program_id(
+                        &recipient.recipient,
+                        &pool_state.token_0_mint,
+                        &pool_state.token_0_program,
+                    ));
+                    recipient_accounts.push(get_associated_token_address_with_program_id(
+                        &recipient.recipient,
+                        &pool_state.token_1_mint,
+                        &pool_state.token_1_program,
+                    ));
+                }
+                (Some(fee_split), recipient_accounts)
+            } else {
+                (None, Vec::new())
+            };
+            let mut instructions = Vec::new();
+            let lock_lp_instr = match (owner, output_mint) {
+                _ if as_lp => collect_fees_as_lp_instr(
+                    &pool_config,
+                    pool_id,
+                    owner_lp_token_account,
+                    pool_state.lp_mint,
+                    lp_lock_counter,
+                    user_lp_lock,
+                    lp_lock_vault,
+                    pool_state.token_0_vault,
+                    pool_state.token_1_vault,
+                    find_enrolled_reward_pool(&program, user_lp_lock)?,
+                )?,
+                (Some(_), _) => collect_fees_as_delegate_instr(
+                    &pool_config,
+                    pool_id,
+                    lock_owner,
+                    owner_lp_token_account,
+                    pool_state.lp_mint,
+                    lp_lock_counter,
+                    user_lp_lock,
+                    lp_lock_vault,
+                    // A fee split pays every recipient directly, so the owner's accounts are unused
+                    fee_split.is_none().then(|| {
+                        get_associated_token_address_with_program_id(
+                            &lock_owner,
+                            &pool_state.token_0_mint,
+                            &pool_state.token_0_program,
+                        )
+                    }),
+                    fee_split.is_none().then(|| {
+                        get_associated_token_address_with_program_id(
+                            &lock_owner,
+                            &pool_state.token_1_mint,
+                            &pool_state.token_1_program,
+                        )
+                    }),
+                    pool_state.token_0_vault,
+                    pool_state.token_1_vault,
+                    pool_state.token_0_mint,
+                    pool_state.token_1_mint,
+                    find_enrolled_reward_pool(&program, user_lp_lock)?,
+                    fee_split,
+                    fee_split_recipient_accounts,
+                )?,
+                (None, Some(output_mint)) => collect_fees_single_sided_instr(
+                    &pool_config,
+                    pool_id,
+                    owner_lp_token_account,
+                    pool_state.lp_mint,
+                    lp_lock_counter,
+                    user_lp_lock,
+                    lp_lock_vault,
+                    get_associated_token_address_with_program_id(
+                        &lock_owner,
+                        &pool_state.token_0_mint,
+                        &pool_state.token_0_program,
+                    ),
+                    get_associated_token_address_with_program_id(
+                        &lock_owner,
+                        &pool_state.token_1_mint,
+                        &pool_state.token_1_program,
+                    ),
+                    pool_state.token_0_vault,
+                    pool_state.token_1_vault,
+                    pool_state.token_0_mint,
+                    pool_state.token_1_mint,
+                    find_enrolled_reward_pool(&program, user_lp_lock)?,
+                    fee_split,
+                    fee_split_recipient_accounts,
+                    pool_state.amm_config,
+                    pool_state.observation_key,
+                    output_mint,
+                    minimum_amount_out,
+                )?,
+                (None, None) => collect_fees_instr(
+                    &pool_config,
+                    pool_id,
+                    owner_lp_token_account,
+                    pool_state.lp_mint,
+                    lp_lock_counter,
+                    user_lp_lock,
+                    lp_lock_vault,
+                    // A fee split pays every recipient directly, so the owner's accounts are unused
+                    fee_split.is_none().then(|| {
+                        get_associated_token_address_with_program_id(
+                            &lock_owner,
+                            &pool_state.token_0_mint,
+                            &pool_state.token_0_program,
+                        )
+                    }),
+                    fee_split.is_none().then(|| {
+                        get_associated_token_address_with_program_id(
+                            &lock_owner,
+                            &pool_state.token_1_mint,
+                            &pool_state.token_1_program,
+                        )
+                    }),
+                    pool_state.token_0_vault,
+                    pool_state.token_1_vault,
+                    pool_state.token_0_mint,
+                    pool_state.token_1_mint,
+                    find_enrolled_reward_pool(&program, user_lp_lock)?,
+                    fee_split,
+                    fee_split_recipient_accounts,
+                )?,
+            };
+            instructions.extend(lock_lp_instr);
+            let signers = vec![&payer];
+            let recent_hash = rpc_client.get_latest_blockhash()?;
+            let txn = Transaction::new_signed_with_payer(
+                &instructions,
+                Some(&payer.pubkey()),
+                &signers,
+                recent_hash,
+            );
+            let signature = send_txn(&rpc_client, &txn, true)?;
+            println!("{}", signature);
+        }
+        RaydiumCpCommands::SetCollectDelegate {
+            pool_id,
+            lock_id,
+            delegate,
+            tip_bps,
+        } => {
+            let pool_state: cpmm_lp_lock::states::PoolState = program.account(pool_id)?;
+            let (user_lp_lock, _) = Pubkey::find_program_address(
+                &[
+                    USER_LOCK_SEED.as_bytes(),
+                    payer.pubkey().as_ref(),
+                    pool_state.lp_mint.as_ref(),
+                    lock_id.to_le_bytes().as_ref(),
+                ],
+                &program.id(),
+            );
+            let mut instructions = Vec::new();
+            let set_collect_delegate_instr = set_collect_delegate_instr(
+                &pool_config,
+                user_lp_lock,
+                delegate.unwrap_or_default(),
+                tip_bps,
+            )?;
+            instructions.extend(set_collect_delegate_instr);
+            let signers = vec![&payer];
+            let recent_hash = rpc_client.get_latest_blockhash()?;
+            let txn = Transaction::new_signed_with_payer(
+                &instructions,
+                Some(&payer.pubkey()),
+                &signers,
+                recent_hash,
+            );
+            let signature = send_txn(&rpc_client, &txn, true)?;
+            println!("{}", signature);
+        }
+        RaydiumCpCommands::InitRewardPool {
+            pool_id,
+            campaign,
+            reward_mint,
+            emission_rate,
+            end_time,
+        } => {
+            let pool_state: cpmm_lp_lock::states::PoolState = program.account(pool_id)?;
+            let reward_token_program = rpc_client.get_account(&reward_mint)?.owner;
+            let campaign = campaign.unwrap_or_else(|| {
+                reward_campaign_count(&rpc_client, &program.id(), &pool_state.lp_mint)
+            });
+            let reward_pool = reward_pool_address(&program.id(), &pool_state.lp_mint, campaign);
+            let (reward_vault, _) = Pubkey::find_program_address(
+                &[REWARD_VAULT_SEED.as_bytes(), reward_pool.as_ref()],
+                &program.id(),
+            );
+            let mut instructions = Vec::new();
+            let init_reward_pool_instr = initialize_reward_pool_instr(
+                &pool_config,
+                pool_id,
+                pool_state.lp_mint,
+                reward_pool,
+                reward_mint,
+                reward_vault,
+                get_associated_token_address_with_program_id(
+                    &payer.pubkey(),
+                    &reward_mint,
+                    &reward_token_program,
+                ),
+                reward_token_program,
+                campaign,
+                emission_rate,
+                end_time,
+            )?;
+            instructions.extend(init_reward_pool_instr);
+            let signers = vec![&payer];
+            let recent_hash = rpc_client.get_latest_blockhash()?;
+            let txn = Transaction::new_signed_with_payer(
+                &instructions,
+                Some(&payer.pubkey()),
+                &signers,
+                recent_hash,
+            );
+            let signature = send_txn(&rpc_client, &txn, true)?;
+            println!("{}", signature);
+        }
+        RaydiumCpCommands::SweepRewardPool { pool_id, campaign } => {
+            let pool_state: cpmm_lp_lock::states::PoolState = program.account(pool_id)?;
+            let reward_pool = reward_pool_address(&program.id(), &pool_state.lp_mint, campaign);
+            let reward_pool_info: cpmm_lp_lock::states::RewardPool =
+                program.account(reward_pool)?;
+            let reward_token_program = rpc_client.get_account(&reward_pool_info.reward_mint)?.owner;
+            let mut instructions = Vec::new();
+            let sweep_reward_pool_instr = sweep_reward_pool_instr(
+                &pool_config,
+                reward_pool,
+                reward_pool_info.reward_vault,
+                reward_pool_info.reward_mint,
+                get_associated_token_address_with_program_id(
+                    &payer.pubkey(),
+                    &reward_pool_info.reward_mint,
+                    &reward_token_program,
+                ),
+                reward_token_program,
+            )?;
+            instructions.extend(sweep_reward_pool_instr);
+            let signers = vec![&payer];
+            let recent_hash = rpc_client.get_latest_blockhash()?;
+            let txn = Transaction::new_signed_with_payer(
+                &instructions,
+                Some(&payer.pubkey()),
+                &signers,
+                recent_hash,
+            );
+            let signature = send_txn(&rpc_client, &txn, true)?;
+            println!("{}", signature);
+        }
+        RaydiumCpCommands::ClaimRewards { pool_id, lock_id } => {
+            let pool_state: cpmm_lp_lock::states::PoolState = program.account(pool_id)?;
+            let lp_mint = pool_state.lp_mint;
+            let (user_lp_lock, _) = Pubkey::find_program_address(
+                &[
+                    USER_LOCK_SEED.as_bytes(),
+                    payer.pubkey().as_ref(),
+                    lp_mint.as_ref(),
+                    lock_id.to_le_bytes().as_ref(),
+                ],
+                &program.id(),
+            );
+            let reward_pool = match find_enrolled_reward_pool(&program, user_lp_lock)? {
+                Some(reward_pool) => reward_pool,
+                None => find_reward_pool(&rpc_client, &program.id(), &lp_mint)
+                    .ok_or_else(|| format_err!("no reward pool for LP mint {}", lp_mint))?,
+            };
+            let reward_pool_info: cpmm_lp_lock::states::RewardPool =
+                program.account(reward_pool)?;
+            let reward_token_program = rpc_client.get_account(&reward_pool_info.reward_mint)?.owner;
+            let mut instructions = Vec::new();
+            let claim_rewards_instr = claim_rewards_instr(
+                &pool_config,
+                user_lp_lock,
+                reward_pool,
+                reward_pool_info.reward_vault,
+                reward_pool_info.reward_mint,
+                get_associated_token_address_with_program_id(
+                    &payer.pubkey(),
+                    &reward_pool_info.reward_mint,
+                    &reward_token_program,
+                ),
+                reward_token_program,
+            )?;
+            instructions.extend(claim_rewards_instr);
+            let signers = vec![&payer];
+            let recent_hash = rpc_client.get_latest_blockhash()?;
+            let txn = Transaction::new_signed_with_payer(
+                &instructions,
+                Some(&payer.pubkey()),
+                &signers,
+                recent_hash,
+            );
+            let signature = send_txn(&rpc_client, &txn, true)?;
+            println!("{}", signature);
+        }
+        RaydiumCpCommands::ClaimRewardReceipt { pool_id, lock_id } => {
+            let pool_state: cpmm_lp_lock::states::PoolState = program.account(pool_id)?;
+            let (user_lp_lock, _) = Pubkey::find_program_address(
+                &[
+                    USER_LOCK_SEED.as_bytes(),
+                    payer.pubkey().as_ref(),
+                    pool_state.lp_mint.as_ref(),
+                    lock_id.to_le_bytes().as_ref(),
+                ],
+                &program.id(),
+            );
+            let (reward_receipt, _) = Pubkey::find_program_address(
+                &[REWARD_RECEIPT_SEED.as_bytes(), user_lp_lock.as_ref()],
+                &program.id(),
+            );
+            let reward_receipt_info: cpmm_lp_lock::states::RewardReceipt =
+                program.account(reward_receipt)?;
+            let reward_pool_info: cpmm_lp_lock::states::RewardPool =
+                program.account(reward_receipt_info.reward_pool)?;
+            let reward_token_program = rpc_client.get_account(&reward_pool_info.reward_mint)?.owner;
+            let mut instructions = Vec::new();
+            let claim_reward_receipt_instr = claim_reward_receipt_instr(
+                &pool_config,
+                reward_receipt,
+                reward_receipt_info.reward_pool,
+                reward_pool_info.reward_vault,
+                reward_pool_info.reward_mint,
+                get_associated_token_address_with_program_id(
+                    &payer.pubkey(),
+                    &reward_pool_info.reward_mint,
+                    &reward_token_program,
+                ),
+                reward_token_program,
+            )?;
+            instructions.extend(claim_reward_receipt_instr);
+            let signers = vec![&payer];
+            let recent_hash = rpc_client.get_latest_blockhash()?;
+            let txn = Transaction::new_signed_with_payer(
+                &instructions,
+                Some(&payer.pubkey()),
+                &signers,
+                recent_hash,
+            );
+            let signature = send_txn(&rpc_client, &txn, true)?;
+            println!("{}", signature);
+        }
+        RaydiumCpCommands::SetFeeSplit {
+            pool_id,
+            lock_id,
+            recipients,
+        } => {
+            let pool_state: cpmm_lp_lock::states::PoolState = program.account(pool_id)?;
+            let (user_lp_lock, _) = Pubkey::find_program_address(
+                &[
+                    USER_LOCK_SEED.as_bytes(),
+                    payer.pubkey().as_ref(),
+                    pool_state.lp_mint.as_ref(),
+                    lock_id.to_le_bytes().as_ref(),
+                ],
+                &program.id(),
+            );
+            let (fee_split, _) = Pubkey::find_program_address(
+                &[FEE_SPLIT_SEED.as_bytes(), user_lp_lock.as_ref()],
+                &program.id(),
+            );
+            let mut instructions = Vec::new();
+            let set_fee_split_instr =
+                set_fee_split_instr(&pool_config, user_lp_lock, fee_split, recipients)?;
+            instructions.extend(set_fee_split_instr);
+            let signers = vec![&payer];
+            let recent_hash = rpc_client.get_latest_blockhash()?;
+            let txn = Transaction::new_signed_with_payer(
+                &instructions,
+                Some(&payer.pubkey()),
+                &signers,
+                recent_hash,
+            );
+            let signature = send_txn(&rpc_client, &txn, true)?;
+            println!("{}", signature);
+        }
+        RaydiumCpCommands::RemoveFeeSplit { pool_id, lock_id } => {
+            let pool_state: cpmm_lp_lock::states::PoolState = program.account(pool_id)?;
+            let (user_lp_lock, _) = Pubkey::find_program_address(
+                &[
+                    USER_LOCK_SEED.as_bytes(),
+                    payer.pubkey().as_ref(),
+                    pool_state.lp_mint.as_ref(),
+                    lock_id.to_le_bytes().as_ref(),
+                ],
+                &program.id(),
+            );
+            let (fee_split, _) = Pubkey::find_program_address(
+                &[FEE_SPLIT_SEED.as_bytes(), user_lp_lock.as_ref()],
+                &program.id(),
+            );
+            let mut instructions = Vec::new();
+            let remove_fee_split_instr =
+                remove_fee_split_instr(&pool_config, user_lp_lock, fee_split)?;
+            instructions.extend(remove_fee_split_instr);
+            let signers = vec![&payer];
+            let recent_hash = rpc_client.get_latest_blockhash()?;
+            let txn = Transaction::new_signed_with_payer(
+                &instructions,
+                Some(&payer.pubkey()),
+                &signers,
+                recent_hash,
+            );
+            let signature = send_txn(&rpc_client, &txn, true)?;
+            println!("{}", signature);
+        }
+        RaydiumCpCommands::SetRegistryConfig { allow_list_enabled } => {
+            let mut instructions = Vec::new();
+            let set_registry_config_instr =
+                set_registry_config_instr(&pool_config, allow_list_enabled)?;
+            instructions.extend(set_registry_config_instr);
+            let signers = vec![&payer];
+            let recent_hash = rpc_client.get_latest_blockhash()?;
+            let txn = Transaction::new_signed_with_payer(
+                &instructions,
+                Some(&payer.pubkey()),
+                &signers,
+                recent_hash,
+            );
+            let signature = send_txn(&rpc_client, &txn, true)?;
+            println!("{}", signature);
+        }
+        RaydiumCpCommands::InitRegistry { admin } => {
+            let instructions = initialize_registry_instr(&pool_config, admin)?;
+            let signers = vec![&payer];
+            let recent_hash = rpc_client.get_latest_blockhash()?;
+            let txn = Transaction::new_signed_with_payer(
+                &instructions,
+                Some(&payer.pubkey()),
+                &signers,
+                recent_hash,
+            );
+            let signature = send_txn(&rpc_client, &txn, true)?;
+            println!("{}", signature);
+        }
+        RaydiumCpCommands::SetRegistryAdmin { new_admin } => {
+            let instructions = set_registry_admin_instr(&pool_config, new_admin)?;
+            let signers = vec![&payer];
+            let recent_hash = rpc_client.get_latest_blockhash()?;
+            let txn = Transaction::new_signed_with_payer(
+                &instructions,
+                Some(&payer.pubkey()),
+                &signers,
+                recent_hash,
+            );
+            let signature = send_txn(&rpc_client, &txn, true)?;
+            println!("{}", signature);
+        }
+        RaydiumCpCommands::SetRegistryEntry { key, status } => {
+            let mut instructions = Vec::new();
+            let set_registry_entry_instr = set_registry_entry_instr(&pool_config, key, status)?;
+            instructions.extend(set_registry_entry_instr);
+            let signers = vec![&payer];
+            let recent_hash = rpc_client.get_latest_blockhash()?;
+            let txn = Transaction::new_signed_with_payer(
+                &instructions,
+                Some(&payer.pubkey()),
+                &signers,
+                recent_hash,
+            );
+            let signature = send_txn(&rpc_client, &txn, true)?;
+            println!("{}", signature);
+        }
+        RaydiumCpCommands::ListLocks { pool_id, user } => {
+            let pool_state: cpmm_lp_lock::states::PoolState = program.account(pool_id)?;
+            let lp_mint = pool_state.lp_mint;
+            let user = user.unwrap_or(payer.pubkey());
+            let (lp_lock_counter, _) = Pubkey::find_program_address(
+                &[
+                    LP_LOCK_COUNTER_SEED.as_bytes(),
+                    user.as_ref(),
+                    lp_mint.as_ref(),
+                ],
+                &program.id(),
+            );
+            let lp_lock_counter_info: cpmm_lp_lock::states::LpLockCounter =
+                program.account(lp_lock_counter).unwrap_or_default();
+            let mut lock_count = 1;
+            while lock_count <= lp_lock_counter_info.total_lock_count {
+                let active_lock_page =
+                    find_active_lock_page(&program.id(), &user, &lp_mint, lock_count);
+                let active_lock_page_info: ActiveLockPage = program.account(active_lock_page)?;
+                for active_lock_count in active_lock_page_info.active_lock_counts() {
+                    println!("{}", active_lock_count);
+                }
+                lock_count += LOCKS_PER_ACTIVE_LOCK_PAGE;
+            }
+            // Locks received from other wallets are tracked in pages of their creator
+            let received_pages = program.accounts::<ActiveLockPage>(vec![
+                RpcFilterType::DataSize(ActiveLockPage::LEN as u64),
+                RpcFilterType::Memcmp(Memcmp::new_base58_encoded(9, user.as_ref())),
+                RpcFilterType::Memcmp(Memcmp::new_base58_encoded(41, lp_mint.as_ref())),
+            ])?;
+            for (_, active_lock_page_info) in received_pages {
+                if active_lock_page_info.creator == Pubkey::default() {
+                    continue;
+                }
+                for active_lock_count in active_lock_page_info.active_lock_counts() {
+                    println!(
+                        "{} (created by {})",
+                        active_lock_count, active_lock_page_info.creator
+                    );
+                }
+            }
+        }
+        RaydiumCpCommands::GrantLien {
+            lock_id,
+            lienholder,
+        } => {
+            let (user_lp_lock, _) = find_lock_by_id(&program, lock_id)?;
+            let instructions = grant_lien_instr(&pool_config, user_lp_lock, lienholder)?;
+            let signers = vec![&payer];
+            let recent_hash = rpc_client.get_latest_blockhash()?;
+            let txn = Transaction::new_signed_with_payer(
+                &instructions,
+                Some(&payer.pubkey()),
+                &signers,
+                recent_hash,
+            );
+            let signature = send_txn(&rpc_client, &txn, true)?;
+            println!("{}", signature);
+        }
+        RaydiumCpCommands::ReleaseLien { lock_id } => {
+            let (user_lp_lock, _) = find_lock_by_id(&program, lock_id)?;
+            let instructions = release_lien_instr(&pool_config, user_lp_lock)?;
+            let signers = vec![&payer];
+            let recent_hash = rpc_client.get_latest_blockhash()?;
+            let txn = Transaction::new_signed_with_payer(
+                &instructions,
+                Some(&payer.pubkey()),
+                &signers,
+                recent_hash,
+            );
+            let signature = send_txn(&rpc_client, &txn, true)?;
+            println!("{}", signature);
+        }
+        RaydiumCpCommands::Foreclose { lock_id, new_owner } => {
+            let (user_lp_lock, user_lock) = find_lock_by_id(&program, lock_id)?;
+            let new_owner = new_owner.unwrap_or(payer.pubkey());
+            let instructions = foreclose_instr(
+                &pool_config,
+                user_lp_lock,
+                user_lock.user,
+                user_lock.lp_mint,
+                find_held_active_lock_page(&program.id(), &user_lock.user, &user_lock),
+                new_owner,
+                find_held_active_lock_page(&program.id(), &new_owner, &user_lock),
+            )?;
+            let signers = vec![&payer];
+            let recent_hash = rpc_client.get_latest_blockhash()?;
+            let txn = Transaction::new_signed_with_payer(
+                &instructions,
+                Some(&payer.pubkey()),
+                &signers,
+                recent_hash,
+            );
+            let signature = send_txn(&rpc_client, &txn, true)?;
+            println!("{}", signature);
+        }
+        RaydiumCpCommands::ListLock {
+            lock_id,
+            price,
+            price_mint,
+        } => {
+            let (user_lp_lock, _) = find_lock_by_id(&program, lock_id)?;
+            let instructions = list_lock_instr(
+                &pool_config,
+                user_lp_lock,
+                price_mint.unwrap_or_default(),
+                price,
+            )?;
+            let signers = vec![&payer];
+            let recent_hash = rpc_client.get_latest_blockhash()?;
+            let txn = Transaction::new_signed_with_payer(
+                &instructions,
+                Some(&payer.pubkey()),
+                &signers,
+                recent_hash,
+            );
+            let signature = send_txn(&rpc_client, &txn, true)?;
+            println!("{}", signature);
+        }
+        RaydiumCpCommands::UpdateListing {
+            lock_id,
+            price,
+            price_mint,
+        } => {
+            let (user_lp_lock, _) = find_lock_by_id(&program, lock_id)?;
+            let instructions = update_listing_instr(
+                &pool_config,
+                user_lp_lock,
+                price_mint.unwrap_or_default(),
+                price,
+            )?;
+            let signers = vec![&payer];
+            let recent_hash = rpc_client.get_latest_blockhash()?;
+            let txn = Transaction::new_signed_with_payer(
+                &instructions,
+                Some(&payer.pubkey()),
+                &signers,
+                recent_hash,
+            );
+            let signature = send_txn(&rpc_client, &txn, true)?;
+            println!("{}", signature);
+        }
+        RaydiumCpCommands::CancelListing { lock_id } => {
+            let (user_lp_lock, _) = find_lock_by_id(&program, lock_id)?;
+            let instructions = cancel_listing_instr(&pool_config, user_lp_lock)?;
+            let signers = vec![&payer];
+            let recent_hash = rpc_client.get_latest_blockhash()?;
+            let txn = Transaction::new_signed_with_payer(
+                &instructions,
+                Some(&payer.pubkey()),
+                &signers,
+                recent_hash,
+            );
+            let signature = send_txn(&rpc_client, &txn, true)?;
+            println!("{}", signature);
+        }
+        RaydiumCpCommands::BuyLock { lock_id, max_price } => {
+            let (user_lp_lock, user_lock) = find_lock_by_id(&program, lock_id)?;
+            let (lock_listing, _) = Pubkey::find_program_address(
+                &[LOCK_LISTING_SEED.as_bytes(), user_lp_lock.as_ref()],
+                &program.id(),
+            );
+            let lock_listing_info: LockListing = program.account(lock_listing)?;
+            let price_mint = lock_listing_info.price_mint;
+            let mut instructions = Vec::new();
+            let price_token_program = if price_mint == Pubkey::default() {
+                None
+            } else {
+                let token_program = rpc_client.get_account(&price_mint)?.owner;
+                // The seller may not hold the price token yet
+                instructions.push(
+                    spl_associated_token_account::instruction::create_associated_token_account_idempotent(
+                        &payer.pubkey(),
+                        &lock_listing_info.seller,
+                        &price_mint,
+                        &token_program,
+                    ),
+                );
+                Some(token_program)
+            };
+            let buy_lock_instr = buy_lock_instr(
+                &pool_config,
+                user_lp_lock,
+                lock_listing_info.seller,
+                user_lock.lp_mint,
+                find_held_active_lock_page(&program.id(), &lock_listing_info.seller, &user_lock),
+                find_held_active_lock_page(&program.id(), &payer.pubkey(), &user_lock),
+                price_mint,
+                price_token_program,
+                max_price,
+            )?;
+            instructions.extend(buy_lock_instr);
+            let signers = vec![&payer];
+            let recent_hash = rpc_client.get_latest_blockhash()?;
+            let txn = Transaction::new_signed_with_payer(
+                &instructions,
+                Some(&payer.pubkey()),
+                &signers,
+                recent_hash,
+            );
+            let signature = send_txn(&rpc_client, &txn, true)?;
+            println!("{}", signature);
+        }
+        RaydiumCpCommands::CheckpointRewards { lock_id } => {
+            let (user_lp_lock, user_lock) = find_lock_by_id(&program, lock_id)?;
+            let reward_pool = if user_lock.reward_pool != Pubkey::default() {
+                user_lock.reward_pool
+            } else {
+                find_reward_pool(&rpc_client, &program.id(), &user_lock.lp_mint).ok_or_else(
+                    || format_err!("no reward pool for LP mint {}", user_lock.lp_mint),
+                )?
+            };
+            let instructions = checkpoint_rewards_instr(&pool_config, user_lp_lock, reward_pool)?;
+            let signers = vec![&payer];
+            let recent_hash = rpc_client.get_latest_blockhash()?;
+            let txn = Transaction::new_signed_with_payer(
+                &instructions,
+                Some(&payer.pubkey()),
+                &signers,
+                recent_hash,
+            );
+            let signature = send_txn(&rpc_client, &txn, true)?;
+            println!("{}", signature);
+        }
+        RaydiumCpCommands::ShowLock { lock_id } => {
+            let (user_lp_lock, user_lock) = find_lock_by_id(&program, lock_id)?;
+            let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+            println!("{}", user_lp_lock);
+            println!("{:#?}", user_lock);
+            println!(
+                "effective unlock time: {}",
+                user_lock.effective_unlock_time(now)
+            );
+            if user_lock.unlock_requested_at != 0 {
+                println!(
+                    "unlockable at: {}",
+                    user_lock.unlockable_at(user_lock.unlock_requested_at)
+                );
+            }
+        }
+        RaydiumCpCommands::VotingPower {
+            pool_id,
+            user,
+            timestamp,
+        } => {
+            let pool_state: cpmm_lp_lock::states::PoolState = program.account(pool_id)?;
+            let lp_mint = pool_state.lp_mint;
+            let user = user.unwrap_or(payer.pubkey());
+            let timestamp = match timestamp {
+                Some(timestamp) => timestamp,
+                None => SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs(),
+            };
+            let (lp_lock_counter, _) = Pubkey::find_program_address(
+                &[
+                    LP_LOCK_COUNTER_SEED.as_bytes(),
+                    user.as_ref(),
+                    lp_mint.as_ref(),
+                ],
+                &program.id(),
+            );
+            let lp_lock_counter_info: cpmm_lp_lock::states::LpLockCounter =
+                program.account(lp_lock_counter)?;
+
+            // Every active timed lock of the user is passed, including locks received from
+            // others, which are seeded by their creator
+            let timed_locks: Vec<Pubkey> = program
+                .accounts::<cpmm_lp_lock::states::UserLock>(vec![
+                    RpcFilterType::DataSize(cpmm_lp_lock::states::UserLock::LEN as u64),
+                    RpcFilterType::Memcmp(Memcmp::new_base58_encoded(9, user.as_ref())),
+                    RpcFilterType::Memcmp(Memcmp::new_base58_encoded(41, lp_mint.as_ref())),
+                ])?
+                .into_iter()
+                .filter(|(_, user_lock)| {
+                    !user_lock.is_unlocked
+                        && !user_lock.is_locked_permanently
+                        && !user_lock.is_rolling
+                })
+                .map(|(user_lp_lock, _)| user_lp_lock)
+                .collect();
+            if timed_locks.len() as u64 != lp_lock_counter_info.voting_power.decaying_count {
+                return Err(format_err!(
+                    "found {} of the user's {} timed locks; migrate the rest first",
+                    timed_locks.len(),
+                    lp_lock_counter_info.voting_power.decaying_count
+                ));
+            }
+
+            let instructions = get_voting_power_instr(
+                &pool_config,
+                user,
+                lp_mint,
+                lp_lock_counter,
+                timed_locks,
+                timestamp,
+            )?;
+            let signers = vec![&payer];
+            let recent_hash = rpc_client.get_latest_blockhash()?;
+            let txn = Transaction::new_signed_with_payer(
+                &instructions,
+                Some(&payer.pubkey()),
+                &signers,
+                recent_hash,
+            );
+            let result =
+                simulate_transaction(&rpc_client, &txn, false, CommitmentConfig::confirmed())?
+                    .value;
+            if let Some(err) = result.err {
+                return Err(format_err!("simulation failed: {:?}", err));
+            }
+            let return_data = result
+                .return_data
+                .ok_or(format_err!("no return data from get_voting_power"))?;
+            let bytes = base64::engine::general_purpose::STANDARD.decode(&return_data.data.0)?;
+            let voting_power = u64::from_le_bytes(
+                bytes
+                    .as_slice()
+                    .try_into()
+                    .map_err(|_| format_err!("invalid return data"))?,
+            );
+            println!("{}", voting_power);
+        }
+        RaydiumCpCommands::CreatorLockShare { pool_id } => {
+            let pool_state: cpmm_lp_lock::states::PoolState = program.account(pool_id)?;
+            let instructions =
+                get_creator_lock_share_instr(&pool_config, pool_id, pool_state.lp_mint)?;
+            let signers = vec![&payer];
+            let recent_hash = rpc_client.get_latest_blockhash()?;
+            let txn = Transaction::new_signed_with_payer(
+                &instructions,
+                Some(&payer.pubkey()),
+                &signers,
+                recent_hash,
+            );
+            let result =
+                simulate_transaction(&rpc_client, &txn, false, CommitmentConfig::confirmed())?
+                    .value;
+            if let Some(err) = result.err {
+                return Err(format_err!("simulation failed: {:?}", err));
+            }
+            let return_data = result
+                .return_data
+                .ok_or(format_err!("no return data from get_creator_lock_share"))?;
+            let bytes = base64::engine::general_purpose::STANDARD.decode(&return_data.data.0)?;
+            let share = CreatorLockShare::try_from_slice(&bytes)?;
+            println!("pool creator: {}", share.pool_creator);
+            println!("creator locked amount: {}", share.creator_locked_amount);
+            println!("lp supply: {}", share.lp_supply);
+            println!("creator locked share (bps): {}", share.share_bps);
+        }
+        RaydiumCpCommands::MigrateLock { pool_id, lock_id } => {
+            let pool_state: cpmm_lp_lock::states::PoolState = program.account(pool_id)?;
+            let lp_mint = pool_state.lp_mint;
+            let (lp_lock_counter, _) = Pubkey::find_program_address(
+                &[
+                    LP_LOCK_COUNTER_SEED.as_bytes(),
+                    payer.pubkey().as_ref(),
+                    lp_mint.as_ref(),
+                ],
+                &program.id(),
+            );
+            let (user_lp_lock, _) = Pubkey::find_program_address(
+                &[
+                    USER_LOCK_SEED.as_bytes(),
+                    payer.pubkey().as_ref(),
+                    lp_mint.as_ref(),
+                    lock_id.to_le_bytes().as_ref(),
+                ],
+                &program.id(),
+            );
+            let mut instructions = Vec::new();
+            if rpc_client.get_account(&lp_lock_counter)?.data.len() == LpLockCounterV0::LEN {
+                instructions.extend(migrate_lp_lock_counter_instr(
+                    &pool_config,
+                    lp_lock_counter,
+                )?);
+            }
+            let user_lp_lock_data = rpc_client.get_account(&user_lp_lock)?.data;
+            let needs_lock_id = if user_lp_lock_data.len() == UserLockV0::LEN {
+                instructions.extend(migrate_user_lock_instr(
+                    &pool_config,
+                    user_lp_lock,
+                    payer.pubkey(),
+                    lp_mint,
+                    lp_lock_counter,
+                    lock_id,
+                )?);
+                // Legacy locks never had an ID
+                !UserLockV0::deserialize(&mut &user_lp_lock_data[8..])?.is_unlocked
+            } else {
+                let user_lock: cpmm_lp_lock::states::UserLock = program.account(user_lp_lock)?;
+                user_lock.lock_id == 0 && !user_lock.is_unlocked
+            };
+            if needs_lock_id {
+                instructions.extend(backfill_lock_id_instr(
+                    &pool_config,
+                    user_lp_lock,
+                    find_next_lock_id_index(&program),
+                )?);
+            }
+            if instructions.is_empty() {
+                println!("already migrated");
+                return Ok(());
+            }
+            let signers = vec![&payer];
+            let recent_hash = rpc_client.get_latest_blockhash()?;
+            let txn = Transaction::new_signed_with_payer(
+                &instructions,
+                Some(&payer.pubkey()),
+                &signers,
+                recent_hash,
+            );
+            let signature = send_txn(&rpc_client, &txn, true)?;
+            println!("{}", signature);
+        }
     }
     Ok(())
 }
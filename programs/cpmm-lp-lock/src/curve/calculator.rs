@@ -109,7 +109,7 @@ impl CurveCalculator {
             source_amount_less_fees,
             swap_source_amount,
             swap_destination_amount,
-        );
+        )?;
 
         Some(SwapResult {
             new_swap_source_amount: swap_source_amount.checked_add(source_amount)?,
@@ -221,7 +221,8 @@ pub mod test {
             source_token_amount,
             swap_source_amount,
             swap_destination_amount,
-        );
+        )
+        .unwrap();
 
         let (swap_token_0_amount, swap_token_1_amount) = match trade_direction {
             TradeDirection::ZeroForOne => (swap_source_amount, swap_destination_amount),
@@ -16,17 +16,18 @@ impl ConstantProductCurve {
     /// This is guaranteed to work for all values such that:
     ///  - 1 <= swap_source_amount * swap_destination_amount <= u128::MAX
     ///  - 1 <= source_amount <= u64::MAX
+    ///
+    /// Returns `None` outside of these bounds.
     pub fn swap_base_input_without_fees(
         source_amount: u128,
         swap_source_amount: u128,
         swap_destination_amount: u128,
-    ) -> u128 {
+    ) -> Option<u128> {
         // (x + delta_x) * (y - delta_y) = x * y
         // delta_y = (delta_x * y) / (x + delta_x)
-        let numerator = source_amount.checked_mul(swap_destination_amount).unwrap();
-        let denominator = swap_source_amount.checked_add(source_amount).unwrap();
-        let destinsation_amount_swapped = numerator.checked_div(denominator).unwrap();
-        destinsation_amount_swapped
+        let numerator = source_amount.checked_mul(swap_destination_amount)?;
+        let denominator = swap_source_amount.checked_add(source_amount)?;
+        numerator.checked_div(denominator)
     }
 
     pub fn swap_base_output_without_fees(
@@ -101,7 +102,7 @@ mod tests {
                 check_curve_value_from_swap, check_pool_value_from_deposit,
                 check_pool_value_from_withdraw, total_and_intermediate,
             },
-            RoundDirection, TradeDirection,
+            CurveCalculator, RoundDirection, TradeDirection,
         },
         proptest::prelude::*,
     };
@@ -153,6 +154,14 @@ mod tests {
         assert!(results.is_none());
     }
 
+    #[test]
+    fn fail_swap_base_input() {
+        assert!(ConstantProductCurve::swap_base_input_without_fees(2, 1, u128::MAX).is_none());
+        assert!(ConstantProductCurve::swap_base_input_without_fees(1, u128::MAX, 1).is_none());
+        assert!(ConstantProductCurve::swap_base_input_without_fees(0, 0, 1).is_none());
+        assert!(CurveCalculator::swap_base_input(2, 1_000, u128::MAX, 0, 0, 0).is_none());
+    }
+
     fn test_truncation(
         source_amount: u128,
         swap_source_amount: u128,
@@ -165,7 +174,8 @@ mod tests {
             source_amount,
             swap_source_amount,
             swap_destination_amount,
-        );
+        )
+        .unwrap();
         assert_eq!(source_amount, expected_source_amount_swapped);
         assert_eq!(
             destination_amount_swapped,
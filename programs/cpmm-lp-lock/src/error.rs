@@ -61,4 +61,179 @@ pub enum ErrorCode {
 
     #[msg("Zero liquidity in the pool")]
     ZeroLiquidity,
+
+    #[msg("The LP mint's reward pool must be provided for a lock earning rewards")]
+    RewardPoolRequired,
+
+    #[msg("Only the pool creator can perform this action")]
+    NotPoolCreator,
+
+    #[msg("Reward emission rate must be non-zero and end time must be in the future")]
+    InvalidRewardSchedule,
+
+    #[msg("No rewards to claim")]
+    NoRewardsToClaim,
+
+    #[msg("Voting power cannot be queried before the last checkpoint")]
+    VotingPowerTimestampTooEarly,
+
+    #[msg("Lock is not an active timed lock of the queried user and LP mint")]
+    InvalidVotingPowerLock,
+
+    #[msg("Fee split must have 1 to 5 distinct recipients with non-zero shares summing to 10,000 bps")]
+    InvalidFeeSplit,
+
+    #[msg("The lock's fee split and the recipients' token accounts must be provided")]
+    FeeSplitRequired,
+
+    #[msg("Recipient token account does not match the fee split")]
+    InvalidFeeSplitRecipient,
+
+    #[msg("Account does not match the Raydium pool state")]
+    IncorrectPoolAccount,
+
+    #[msg("Pool is on the deny-list")]
+    PoolDenied,
+
+    #[msg("Pool token mint is on the deny-list")]
+    MintDenied,
+
+    #[msg("Pool is not on the allow-list")]
+    PoolNotAllowed,
+
+    #[msg("Lock vault holds no LP beyond the locked amount")]
+    NoLockVaultSurplus,
+
+    #[msg("Lock label must be at most 64 bytes without control characters and URI at most 200 printable ASCII characters")]
+    InvalidLockMetadata,
+
+    #[msg("Active lock page does not track this lock")]
+    IncorrectActiveLockPage,
+
+    #[msg("Pool trading is disabled")]
+    PoolSwapDisabled,
+
+    #[msg("Lock is not rolling")]
+    LockNotRolling,
+
+    #[msg("Keeper tip exceeds the maximum")]
+    InvalidKeeperTip,
+
+    #[msg("No harvested fees to claim")]
+    NoHarvestedFees,
+
+    #[msg("Invalid unlock beneficiaries")]
+    InvalidBeneficiaries,
+    #[msg("Beneficiary token account does not match the lock's beneficiaries")]
+    InvalidBeneficiaryAccount,
+    #[msg("Lock has unlock beneficiaries")]
+    LockHasBeneficiaries,
+
+    #[msg("Notice period can only be increased, up to the maximum")]
+    InvalidNoticePeriod,
+
+    #[msg("Lock has no notice period")]
+    NoNoticePeriod,
+
+    #[msg("Unlock has not been requested")]
+    UnlockNotRequested,
+
+    #[msg("Unlock has already been requested")]
+    UnlockAlreadyRequested,
+
+    #[msg("Notice period has not elapsed")]
+    NoticePeriodNotElapsed,
+
+    #[msg("Lock is encumbered by a lien")]
+    LockHasLien,
+
+    #[msg("Lock has no lien")]
+    NoLien,
+
+    #[msg("Signer is not the lock's lienholder")]
+    InvalidLienholder,
+
+    #[msg("Lock is listed for sale")]
+    LockIsListed,
+
+    #[msg("Listing price must be greater than zero")]
+    InvalidListingPrice,
+
+    #[msg("Listing price differs from the price agreed by the buyer")]
+    ListingPriceChanged,
+
+    #[msg("Payment accounts do not match the listing")]
+    InvalidPaymentAccount,
+
+    #[msg("Lock has a fee split")]
+    LockHasFeeSplit,
+
+    #[msg("Pool fees exceed the vault balance")]
+    PoolFeesExceedVault,
+
+    #[msg("Lock principal exceeds the current value of the locked LP")]
+    PrincipalExceedsValue,
+
+    #[msg("Pool deposits are disabled")]
+    PoolDepositDisabled,
+
+    #[msg("Seller cannot buy its own listing")]
+    BuyerIsSeller,
+
+    #[msg("Lock cannot be handed to its current owner")]
+    InvalidNewOwner,
+
+    #[msg("Account is not a legacy account of the expected type")]
+    InvalidLegacyAccount,
+
+    #[msg("Account is already on the current layout")]
+    AccountAlreadyMigrated,
+
+    #[msg("Account is not the owner of the lock")]
+    IncorrectLockOwner,
+
+    #[msg("Lock has unclaimed rewards")]
+    UnclaimedRewards,
+
+    #[msg("Lock has unclaimed harvested fees")]
+    UnclaimedHarvestedFees,
+
+    #[msg("Lock ID index account is required")]
+    LockIdIndexRequired,
+
+    #[msg("Signer is not the program's upgrade authority")]
+    NotUpgradeAuthority,
+
+    #[msg("Signer is not the registry admin")]
+    NotRegistryAdmin,
+
+    #[msg("Unlock cannot be requested before the notice period preceding the unlock time")]
+    UnlockRequestTooEarly,
+
+    #[msg("Pool withdrawals are disabled")]
+    PoolWithdrawDisabled,
+
+    #[msg("Owner token accounts are required when the lock has no fee split")]
+    OwnerTokenAccountRequired,
+
+    #[msg("Lock already has a program-wide ID")]
+    LockIdAlreadyAssigned,
+
+    #[msg("Reward mint must not have a freeze authority")]
+    RewardMintFreezable,
+
+    #[msg("Reward receipt account is required")]
+    RewardReceiptRequired,
+
+    #[msg("Reward pool is not the one the lock is enrolled in")]
+    IncorrectRewardPool,
+
+    #[msg("Signer is not the reward pool authority")]
+    NotRewardPoolAuthority,
+
+    #[msg("Reward campaign has not ended")]
+    RewardCampaignActive,
+
+    #[msg("Every active timed lock of the user must be passed to get_voting_power")]
+    IncompleteVotingPowerLocks,
 }
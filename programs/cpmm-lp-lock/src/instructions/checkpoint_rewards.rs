@@ -0,0 +1,59 @@
+use crate::error::ErrorCode;
+use crate::states::*;
+use crate::utils::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct CheckpointRewards<'info> {
+    /// Anyone can checkpoint an enrolled lock, only its owner can enroll it
+    pub signer: Signer<'info>,
+
+    #[account(mut)]
+    pub user_lp_lock: Box<Account<'info, UserLock>>,
+
+    #[account(
+        mut,
+        seeds = [
+            REWARD_POOL_SEED.as_bytes(),
+            user_lp_lock.lp_mint.as_ref(),
+            reward_pool.campaign.to_le_bytes().as_ref()
+        ],
+        bump = reward_pool.bump,
+    )]
+    pub reward_pool: Box<Account<'info, RewardPool>>,
+}
+
+/// Settle a lock's rewards and re-weight it by its remaining duration. Anyone can call this
+/// for a lock enrolled in the pool, e.g. to stop an expired lock from earning with the
+/// multiplier of its last checkpoint. A lock that is not enrolled yet is enrolled, which
+/// only its owner can do.
+pub fn checkpoint_rewards(ctx: Context<CheckpointRewards>) -> Result<()> {
+    let user_lock = &mut ctx.accounts.user_lp_lock;
+    let reward_pool_key = ctx.accounts.reward_pool.key();
+    let block_timestamp = block_timestamp()?;
+    if user_lock.reward_pool == Pubkey::default() {
+        require_keys_eq!(
+            ctx.accounts.signer.key(),
+            user_lock.user,
+            ErrorCode::IncorrectLockOwner
+        );
+        ctx.accounts
+            .reward_pool
+            .enroll(user_lock, reward_pool_key, block_timestamp)?;
+    } else {
+        require_keys_eq!(
+            user_lock.reward_pool,
+            reward_pool_key,
+            ErrorCode::IncorrectRewardPool
+        );
+        ctx.accounts.reward_pool.checkpoint(user_lock, block_timestamp)?;
+    }
+
+    emit!(RewardCheckpointEvent {
+        lock_id: user_lock.lock_id,
+        lp_mint: user_lock.lp_mint,
+        reward_weight: user_lock.reward_weight,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,162 @@
+use crate::error::ErrorCode;
+use crate::states::*;
+use crate::utils::{close_token_account, transfer_from_pool_vault_to_user};
+use crate::LOCK_FEE_VAULT_SEED;
+use anchor_lang::prelude::*;
+use anchor_spl::token::Token;
+use anchor_spl::token_2022::Token2022;
+use anchor_spl::token_interface::{Mint, TokenAccount};
+
+#[derive(Accounts)]
+pub struct ClaimHarvested<'info> {
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = user_lp_lock.user == owner.key(),
+    )]
+    pub user_lp_lock: Box<Account<'info, UserLock>>,
+
+    /// CHECK: lock vault authority
+    #[account(
+        seeds = [
+            crate::AUTH_SEED.as_bytes(),
+        ],
+     bump,
+    )]
+    pub lock_vault_authority: UncheckedAccount<'info>,
+
+    /// The lock's vault holding harvested token_0 fees
+    #[account(
+        mut,
+        token::authority = lock_vault_authority,
+        seeds = [
+            LOCK_FEE_VAULT_SEED.as_bytes(),
+            user_lp_lock.key().as_ref(),
+            vault_0_mint.key().as_ref()
+        ],
+        bump,
+    )]
+    pub lock_fee_vault_0: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The lock's vault holding harvested token_1 fees
+    #[account(
+        mut,
+        token::authority = lock_vault_authority,
+        seeds = [
+            LOCK_FEE_VAULT_SEED.as_bytes(),
+            user_lp_lock.key().as_ref(),
+            vault_1_mint.key().as_ref()
+        ],
+        bump,
+    )]
+    pub lock_fee_vault_1: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The owner's token account for receive token_0
+    #[account(
+        mut,
+        token::mint = vault_0_mint,
+        token::authority = owner
+    )]
+    pub token_0_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The owner's token account for receive token_1
+    #[account(
+        mut,
+        token::mint = vault_1_mint,
+        token::authority = owner
+    )]
+    pub token_1_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// token Program
+    pub token_program: Program<'info, Token>,
+
+    /// Token program 2022
+    pub token_program_2022: Program<'info, Token2022>,
+
+    /// The mint of token_0, receiving any transfer fees withheld in the fee vault
+    #[account(
+        mut,
+        address = lock_fee_vault_0.mint
+    )]
+    pub vault_0_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The mint of token_1, receiving any transfer fees withheld in the fee vault
+    #[account(
+        mut,
+        address = lock_fee_vault_1.mint
+    )]
+    pub vault_1_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// CHECK: Account that paid rent for the fee vaults, refunded as they are closed
+    #[account(
+        mut,
+        address = user_lp_lock.harvester,
+    )]
+    pub harvester: UncheckedAccount<'info>,
+}
+
+/// Drain the fees harvested into the lock's fee vaults to the owner and close the vaults,
+/// refunding their rent to the harvester that opened them.
+pub fn claim_harvested(ctx: Context<ClaimHarvested>) -> Result<()> {
+    let accounts = ctx.accounts;
+    let token_0_amount = accounts.lock_fee_vault_0.amount;
+    let token_1_amount = accounts.lock_fee_vault_1.amount;
+    require!(
+        token_0_amount > 0 || token_1_amount > 0,
+        ErrorCode::NoHarvestedFees
+    );
+
+    let signer_seeds: &[&[&[u8]]] =
+        &[&[crate::AUTH_SEED.as_bytes(), &[ctx.bumps.lock_vault_authority]]];
+    for (lock_fee_vault, token_account, mint, amount) in [
+        (
+            &accounts.lock_fee_vault_0,
+            &accounts.token_0_account,
+            &accounts.vault_0_mint,
+            token_0_amount,
+        ),
+        (
+            &accounts.lock_fee_vault_1,
+            &accounts.token_1_account,
+            &accounts.vault_1_mint,
+            token_1_amount,
+        ),
+    ] {
+        let token_program = if *mint.to_account_info().owner == Token2022::id() {
+            accounts.token_program_2022.to_account_info()
+        } else {
+            accounts.token_program.to_account_info()
+        };
+        transfer_from_pool_vault_to_user(
+            accounts.lock_vault_authority.to_account_info(),
+            lock_fee_vault.to_account_info(),
+            token_account.to_account_info(),
+            mint.to_account_info(),
+            token_program.clone(),
+            amount,
+            mint.decimals,
+            signer_seeds,
+        )?;
+        close_token_account(
+            &accounts.lock_vault_authority.to_account_info(),
+            &lock_fee_vault.to_account_info(),
+            &accounts.harvester.to_account_info(),
+            &mint.to_account_info(),
+            &token_program,
+            signer_seeds,
+        )?;
+    }
+
+    accounts.user_lp_lock.harvest_pending = false;
+    accounts.user_lp_lock.harvester = Pubkey::default();
+
+    emit!(ClaimHarvestedEvent {
+        lock_id: accounts.user_lp_lock.lock_id,
+        user: accounts.owner.key(),
+        token_0_amount,
+        token_1_amount,
+    });
+
+    Ok(())
+}
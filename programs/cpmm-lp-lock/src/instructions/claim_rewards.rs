@@ -0,0 +1,199 @@
+use crate::error::ErrorCode;
+use crate::states::*;
+use crate::utils::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+#[derive(Accounts)]
+pub struct ClaimRewards<'info> {
+    pub owner: Signer<'info>,
+
+    /// CHECK: lock vault authority
+    #[account(
+        seeds = [
+            crate::AUTH_SEED.as_bytes(),
+        ],
+     bump,
+    )]
+    pub lock_vault_authority: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        constraint = user_lp_lock.user == owner.key(),
+    )]
+    pub user_lp_lock: Box<Account<'info, UserLock>>,
+
+    #[account(
+        mut,
+        seeds = [
+            REWARD_POOL_SEED.as_bytes(),
+            user_lp_lock.lp_mint.as_ref(),
+            reward_pool.campaign.to_le_bytes().as_ref()
+        ],
+        bump = reward_pool.bump,
+    )]
+    pub reward_pool: Box<Account<'info, RewardPool>>,
+
+    /// The vault that holds the undistributed rewards
+    #[account(
+        mut,
+        address = reward_pool.reward_vault,
+    )]
+    pub reward_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Reward token mint
+    #[account(
+        address = reward_pool.reward_mint,
+    )]
+    pub reward_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The owner's token account to receive rewards
+    #[account(
+        mut,
+        token::mint = reward_mint,
+        token::authority = owner,
+        token::token_program = reward_token_program,
+    )]
+    pub owner_reward_token: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Reward token program
+    pub reward_token_program: Interface<'info, TokenInterface>,
+}
+
+/// Checkpoint the lock and pay out its settled rewards, if any.
+/// Also enrolls locks created before the reward pool existed, and withdraws locks from a
+/// campaign that has ended so that they can be enrolled in the next one.
+pub fn claim_rewards(ctx: Context<ClaimRewards>) -> Result<()> {
+    let user_lock = &mut ctx.accounts.user_lp_lock;
+    let reward_pool = &mut ctx.accounts.reward_pool;
+
+    let reward_pool_key = reward_pool.key();
+    let block_timestamp = block_timestamp()?;
+    if user_lock.reward_pool == Pubkey::default() {
+        reward_pool.enroll(user_lock, reward_pool_key, block_timestamp)?;
+    } else {
+        require_keys_eq!(
+            user_lock.reward_pool,
+            reward_pool_key,
+            ErrorCode::IncorrectRewardPool
+        );
+        reward_pool.checkpoint(user_lock, block_timestamp)?;
+    }
+
+    user_lock.last_updated = block_timestamp;
+    let amount = std::mem::take(&mut user_lock.rewards_owed);
+    reward_pool.release(amount)?;
+    if block_timestamp >= reward_pool.end_time {
+        reward_pool.unenroll(user_lock)?;
+    }
+    // Nothing to pay yet, but the checkpoint above still enrolls the lock
+    if amount == 0 {
+        return Ok(());
+    }
+
+    transfer_from_pool_vault_to_user(
+        ctx.accounts.lock_vault_authority.to_account_info(),
+        ctx.accounts.reward_vault.to_account_info(),
+        ctx.accounts.owner_reward_token.to_account_info(),
+        ctx.accounts.reward_mint.to_account_info(),
+        ctx.accounts.reward_token_program.to_account_info(),
+        amount,
+        ctx.accounts.reward_mint.decimals,
+        &[&[
+            crate::AUTH_SEED.as_bytes(),
+            &[ctx.bumps.lock_vault_authority],
+        ]],
+    )?;
+
+    emit!(ClaimRewardsEvent {
+        user: user_lock.user,
+        lp_mint: user_lock.lp_mint,
+        reward_mint: reward_pool.reward_mint,
+        amount,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ClaimRewardReceipt<'info> {
+    /// Owner of the unlocked lock, receiving the receipt's rent
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// CHECK: lock vault authority
+    #[account(
+        seeds = [
+            crate::AUTH_SEED.as_bytes(),
+        ],
+     bump,
+    )]
+    pub lock_vault_authority: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        has_one = owner,
+        has_one = reward_pool,
+        close = owner,
+    )]
+    pub reward_receipt: Box<Account<'info, RewardReceipt>>,
+
+    #[account(mut)]
+    pub reward_pool: Box<Account<'info, RewardPool>>,
+
+    /// The vault that holds the undistributed rewards
+    #[account(
+        mut,
+        address = reward_pool.reward_vault,
+    )]
+    pub reward_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Reward token mint
+    #[account(
+        address = reward_pool.reward_mint,
+    )]
+    pub reward_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The owner's token account to receive rewards
+    #[account(
+        mut,
+        token::mint = reward_mint,
+        token::authority = owner,
+        token::token_program = reward_token_program,
+    )]
+    pub owner_reward_token: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Reward token program
+    pub reward_token_program: Interface<'info, TokenInterface>,
+}
+
+/// Pay out the rewards a lock left unclaimed when it was unlocked and close its receipt.
+pub fn claim_reward_receipt(ctx: Context<ClaimRewardReceipt>) -> Result<()> {
+    let reward_receipt = &ctx.accounts.reward_receipt;
+    let amount = reward_receipt.amount;
+    ctx.accounts.reward_pool.release(amount)?;
+    if amount > 0 {
+        transfer_from_pool_vault_to_user(
+            ctx.accounts.lock_vault_authority.to_account_info(),
+            ctx.accounts.reward_vault.to_account_info(),
+            ctx.accounts.owner_reward_token.to_account_info(),
+            ctx.accounts.reward_mint.to_account_info(),
+            ctx.accounts.reward_token_program.to_account_info(),
+            amount,
+            ctx.accounts.reward_mint.decimals,
+            &[&[
+                crate::AUTH_SEED.as_bytes(),
+                &[ctx.bumps.lock_vault_authority],
+            ]],
+        )?;
+    }
+
+    emit!(ClaimRewardsEvent {
+        user: reward_receipt.owner,
+        lp_mint: ctx.accounts.reward_pool.lp_mint,
+        reward_mint: ctx.accounts.reward_pool.reward_mint,
+        amount,
+    });
+
+    Ok(())
+}
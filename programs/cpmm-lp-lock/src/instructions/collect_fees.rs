@@ -2,25 +2,18 @@ use crate::curve::CurveCalculator;
 use crate::curve::RoundDirection;
 use crate::error::ErrorCode;
 use crate::states::*;
+use crate::utils::block_timestamp;
+use crate::utils::get_amount_after_transfer_fee;
 use crate::utils::transfer_from_pool_vault_to_user;
-use crate::utils::U128;
+use crate::utils::transfer_from_user_to_pool_vault;
+use crate::utils::raydium;
+use crate::utils::liquidity;
 use crate::LP_LOCK_VAULT_SEED;
-use anchor_lang::prelude::borsh::BorshDeserialize;
-use anchor_lang::prelude::borsh::BorshSerialize;
 use anchor_lang::prelude::*;
 use anchor_spl::token::Token;
 use anchor_spl::token_2022::Token2022;
 use anchor_spl::memo::spl_memo;
 use anchor_spl::token_interface::{Mint, TokenAccount};
-use anchor_lang::{solana_program::clock};
-use anchor_lang::{solana_program::{instruction::Instruction, program::{invoke}}};
-
-#[derive(BorshSerialize, BorshDeserialize)]
-pub struct Withdraw {
-    lp_token_amount: u64,
-    minimum_token_0_amount: u64,
-    minimum_token_1_amount: u64,
-}
 
 #[derive(Accounts)]
 pub struct CollectFees<'info> {
@@ -76,6 +69,16 @@ pub struct CollectFees<'info> {
     )]
     pub lp_lock_counter: Box<Account<'info, LpLockCounter>>,
 
+    #[account(
+        mut,
+        seeds = [
+            POOL_LOCK_STATS_SEED.as_bytes(),
+            lp_mint.key().as_ref()
+        ],
+        bump = pool_lock_stats.bump,
+    )]
+    pub pool_lock_stats: Box<Account<'info, PoolLockStats>>,
+
     #[account(
         mut,
         constraint = user_lp_lock.user == owner.key(),
@@ -90,7 +93,7 @@ pub struct CollectFees<'info> {
         token::authority = lock_vault_authority ,
         seeds = [
             LP_LOCK_VAULT_SEED.as_bytes(),
-            owner.key().as_ref(),
+            user_lp_lock.pda_owner().as_ref(),
             lp_mint.key().as_ref(),
             user_lp_lock.lock_count.to_le_bytes().as_ref()
         ],
@@ -98,21 +101,21 @@ pub struct CollectFees<'info> {
     )]
     pub lp_lock_vault: Box<InterfaceAccount<'info, TokenAccount>>,
 
-    /// The owner's token account for receive token_0
+    /// The owner's token account for receive token_0, required unless the lock has a fee split
     #[account(
         mut,
         token::mint = token_0_vault.mint,
         token::authority = owner
     )]
-    pub token_0_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub token_0_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
 
-    /// The owner's token account for receive token_1
+    /// The owner's token account for receive token_1, required unless the lock has a fee split
     #[account(
         mut,
         token::mint = token_1_vault.mint,
         token::authority = owner
     )]
-    pub token_1_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub token_1_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
 
     /// The address that holds pool tokens for token_0
     #[account(mut)]
@@ -154,35 +157,323 @@ pub struct CollectFees<'info> {
     )]
     pub raydium_cpmm_program: AccountInfo<'info>,
 
+    /// Reward pool the lock is enrolled in, required if it has one
+    #[account(
+        mut,
+        address = user_lp_lock.reward_pool @ ErrorCode::IncorrectRewardPool,
+    )]
+    pub reward_pool: Option<Box<Account<'info, RewardPool>>>,
+
+    /// Fee split of the lock, required if the lock has one.
+    /// Each recipient's token_0 and token_1 accounts follow in `remaining_accounts`, in recipient order.
+    #[account(
+        seeds = [
+            FEE_SPLIT_SEED.as_bytes(),
+            user_lp_lock.key().as_ref()
+        ],
+        bump = fee_split.bump,
+    )]
+    pub fee_split: Option<Box<Account<'info, FeeSplit>>>,
+
     pub system_program: Program<'info, System>,
 }
 
-pub fn collect_fees(ctx: Context<CollectFees>) -> Result<()> {
-    let user_lock = &mut ctx.accounts.user_lp_lock;
-    let lp_lock_counter = &mut ctx.accounts.lp_lock_counter;
+pub fn collect_fees<'info>(ctx: Context<'_, '_, '_, 'info, CollectFees<'info>>) -> Result<()> {
+    let accounts = ctx.accounts;
+    let (lp_tokens_to_burn, token_0_amount, token_1_amount) =
+        take_fee_lp(accounts, ctx.bumps.lock_vault_authority)?;
+
+    let (token_0_received, token_1_received) = match accounts.fee_split.as_deref() {
+        // Each recipient gets its own withdraw, so the shares never pass through the owner's
+        // wallet and Token-2022 transfer fees are charged once, as in `collect_fees_as_delegate`
+        Some(fee_split) => {
+            require_eq!(
+                ctx.remaining_accounts.len(),
+                fee_split.recipients.len() * 2,
+                ErrorCode::FeeSplitRequired
+            );
+            let lp_payouts = fee_split.split_amount(lp_tokens_to_burn)?;
+            let mut destinations = Vec::with_capacity(fee_split.recipients.len());
+            for (i, recipient) in fee_split.recipients.iter().enumerate() {
+                let recipient_token_0 = &ctx.remaining_accounts[2 * i];
+                let recipient_token_1 = &ctx.remaining_accounts[2 * i + 1];
+                check_recipient_token_account(
+                    recipient_token_0,
+                    recipient,
+                    &accounts.vault_0_mint,
+                    ErrorCode::InvalidFeeSplitRecipient,
+                )?;
+                check_recipient_token_account(
+                    recipient_token_1,
+                    recipient,
+                    &accounts.vault_1_mint,
+                    ErrorCode::InvalidFeeSplitRecipient,
+                )?;
+                destinations.push((
+                    recipient.recipient,
+                    recipient_token_0.clone(),
+                    recipient_token_1.clone(),
+                    lp_payouts[i],
+                ));
+            }
+
+            let mut payouts = Vec::with_capacity(destinations.len());
+            let (mut token_0_received, mut token_1_received) = (0u64, 0u64);
+            for (recipient, recipient_token_0, recipient_token_1, lp_token_amount) in destinations {
+                let (token_0_amount, token_1_amount) = if lp_token_amount == 0 {
+                    (0, 0)
+                } else {
+                    withdraw_fee_lp(
+                        accounts,
+                        recipient_token_0,
+                        recipient_token_1,
+                        lp_token_amount,
+                        0,
+                        0,
+                    )?
+                };
+                token_0_received = token_0_received
+                    .checked_add(token_0_amount)
+                    .ok_or(ErrorCode::Overflow)?;
+                token_1_received = token_1_received
+                    .checked_add(token_1_amount)
+                    .ok_or(ErrorCode::Overflow)?;
+                payouts.push(FeeSplitPayout {
+                    recipient,
+                    token_0_amount,
+                    token_1_amount,
+                });
+            }
+
+            emit!(FeeSplitPayoutEvent {
+                user_lock: accounts.user_lp_lock.key(),
+                lp_mint: accounts.lp_mint.key(),
+                token_0_mint: accounts.vault_0_mint.key(),
+                token_1_mint: accounts.vault_1_mint.key(),
+                payouts,
+            });
+            (token_0_received, token_1_received)
+        }
+        None => {
+            let (token_0_account, token_1_account) = owner_token_accounts(accounts)?;
+            withdraw_fee_lp(
+                accounts,
+                token_0_account,
+                token_1_account,
+                lp_tokens_to_burn,
+                token_0_amount,
+                token_1_amount,
+            )?
+        }
+    };
+
+    record_fees_collected(accounts, token_0_received, token_1_received)
+}
+
+/// Burn the LP tokens of the lock that represent accrued fees through the Raydium `withdraw`
+/// into the owner's token accounts, returning the token_0 and token_1 amounts received,
+/// net of any transfer fee.
+pub(crate) fn withdraw_fees(
+    accounts: &mut CollectFees<'_>,
+    lock_vault_authority_bump: u8,
+) -> Result<(u64, u64)> {
+    let (lp_tokens_to_burn, token_0_amount, token_1_amount) =
+        take_fee_lp(accounts, lock_vault_authority_bump)?;
+    let (token_0_account, token_1_account) = owner_token_accounts(accounts)?;
+    let received = withdraw_fee_lp(
+        accounts,
+        token_0_account,
+        token_1_account,
+        lp_tokens_to_burn,
+        token_0_amount,
+        token_1_amount,
+    )?;
+    record_fees_collected(accounts, received.0, received.1)?;
+    Ok(received)
+}
 
+/// Settle the lock's accrued fees and move the fee LP from the lock vault into the owner's
+/// LP token account, ready to be withdrawn.
+/// Returns the LP tokens to burn and the token_0 and token_1 amounts they withdraw,
+/// net of any transfer fee.
+fn take_fee_lp(
+    accounts: &mut CollectFees<'_>,
+    lock_vault_authority_bump: u8,
+) -> Result<(u64, u64, u64)> {
+    require!(
+        !accounts.user_lp_lock.has_fee_split || accounts.fee_split.is_some(),
+        ErrorCode::FeeSplitRequired
+    );
+
+    let pool_state_info = &accounts.pool_state;
+    let pool_state = PoolState::try_deserialize(&mut &pool_state_info.data.borrow()[..])?;
+    require_eq!(pool_state.lp_mint,accounts.lp_mint.key(), ErrorCode::IncorrectLpMint);
+    require_keys_eq!(
+        pool_state.token_0_vault,
+        accounts.token_0_vault.key(),
+        ErrorCode::IncorrectPoolAccount
+    );
+    require_keys_eq!(
+        pool_state.token_1_vault,
+        accounts.token_1_vault.key(),
+        ErrorCode::IncorrectPoolAccount
+    );
+    // Raydium would otherwise reject the withdraw deep in the CPI; `collect_fees_as_lp` still works
+    pool_state.require_enabled(PoolStatusBitIndex::Withdraw)?;
+
+    let (lp_tokens_to_burn, token_0_amount, token_1_amount) = settle_fees(
+        &mut accounts.user_lp_lock,
+        &mut accounts.lp_lock_counter,
+        &mut accounts.pool_lock_stats,
+        accounts.reward_pool.as_deref_mut().map(|r| &mut **r),
+        &pool_state,
+        [&accounts.token_0_vault, &accounts.token_1_vault],
+        [&accounts.vault_0_mint, &accounts.vault_1_mint],
+        block_timestamp()?,
+    )?;
+
+    transfer_from_pool_vault_to_user(
+        accounts.lock_vault_authority.to_account_info(),
+        accounts.lp_lock_vault.to_account_info(),
+        accounts.owner_lp_token.to_account_info(),
+        accounts.lp_mint.to_account_info(),
+        accounts.token_program.to_account_info(),
+        lp_tokens_to_burn,
+        accounts.lp_mint.decimals,
+        &[&[crate::AUTH_SEED.as_bytes(), &[lock_vault_authority_bump]]],
+    )?;
+
+    Ok((lp_tokens_to_burn, token_0_amount, token_1_amount))
+}
+
+/// The owner's token_0 and token_1 accounts, which are only optional when a fee split pays
+/// the recipients directly.
+fn owner_token_accounts<'info>(
+    accounts: &CollectFees<'info>,
+) -> Result<(AccountInfo<'info>, AccountInfo<'info>)> {
+    match (&accounts.token_0_account, &accounts.token_1_account) {
+        (Some(token_0_account), Some(token_1_account)) => Ok((
+            token_0_account.to_account_info(),
+            token_1_account.to_account_info(),
+        )),
+        _ => err!(ErrorCode::OwnerTokenAccountRequired),
+    }
+}
+
+/// Burn `lp_token_amount` of the fee LP held in the owner's LP token account through the
+/// Raydium `withdraw` into `token_0_account` and `token_1_account`.
+/// Returns the token_0 and token_1 amounts they received, net of any transfer fee.
+fn withdraw_fee_lp<'info>(
+    accounts: &mut CollectFees<'info>,
+    token_0_account: AccountInfo<'info>,
+    token_1_account: AccountInfo<'info>,
+    lp_token_amount: u64,
+    minimum_token_0_amount: u64,
+    minimum_token_1_amount: u64,
+) -> Result<(u64, u64)> {
+    let token_0_vault_before = accounts.token_0_vault.amount;
+    let token_1_vault_before = accounts.token_1_vault.amount;
+
+    raydium::withdraw(
+        raydium::WithdrawAccounts {
+            owner: accounts.owner.to_account_info(),
+            authority: accounts.authority.to_account_info(),
+            pool_state: accounts.pool_state.to_account_info(),
+            owner_lp_token: accounts.owner_lp_token.to_account_info(),
+            token_0_account,
+            token_1_account,
+            token_0_vault: accounts.token_0_vault.to_account_info(),
+            token_1_vault: accounts.token_1_vault.to_account_info(),
+            token_program: accounts.token_program.to_account_info(),
+            token_program_2022: accounts.token_program_2022.to_account_info(),
+            vault_0_mint: accounts.vault_0_mint.to_account_info(),
+            vault_1_mint: accounts.vault_1_mint.to_account_info(),
+            lp_mint: accounts.lp_mint.to_account_info(),
+            memo_program: accounts.memo_program.to_account_info(),
+        },
+        lp_token_amount,
+        minimum_token_0_amount,
+        minimum_token_1_amount,
+        &[],
+    )?;
+
+    accounts.token_0_vault.reload()?;
+    accounts.token_1_vault.reload()?;
+    let token_0_received = get_amount_after_transfer_fee(
+        &accounts.vault_0_mint.to_account_info(),
+        token_0_vault_before
+            .checked_sub(accounts.token_0_vault.amount)
+            .ok_or(ErrorCode::UnderflowError)?,
+    )?;
+    let token_1_received = get_amount_after_transfer_fee(
+        &accounts.vault_1_mint.to_account_info(),
+        token_1_vault_before
+            .checked_sub(accounts.token_1_vault.amount)
+            .ok_or(ErrorCode::UnderflowError)?,
+    )?;
+
+    Ok((token_0_received, token_1_received))
+}
+
+/// Credit the lock with the fees its owner or fee-split recipients received and report them.
+fn record_fees_collected(
+    accounts: &mut CollectFees<'_>,
+    token_0_amount: u64,
+    token_1_amount: u64,
+) -> Result<()> {
+    accounts
+        .user_lp_lock
+        .record_fees_collected(token_0_amount, token_1_amount)?;
+    emit!(
+        CollectFeesEvent {
+            lock_id: accounts.user_lp_lock.lock_id,
+            user: accounts.owner.key(),
+            lp_mint: accounts.lp_mint.key(),
+            token_0_amount,
+            token_1_amount,
+        }
+    );
+    Ok(())
+}
+
+/// Work out the LP tokens of `user_lock` that represent accrued fees, shrink the lock to its
+/// principal and update the aggregates and reward pool accordingly.
+/// Returns the LP tokens to burn and the token_0 and token_1 amounts they withdraw,
+/// net of any transfer fee. Callers credit `token_*_fees_collected` with what was paid out.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn settle_fees(
+    user_lock: &mut UserLock,
+    lp_lock_counter: &mut LpLockCounter,
+    pool_lock_stats: &mut PoolLockStats,
+    reward_pool: Option<&mut RewardPool>,
+    pool_state: &PoolState,
+    token_vaults: [&InterfaceAccount<TokenAccount>; 2],
+    vault_mints: [&InterfaceAccount<Mint>; 2],
+    block_timestamp: u64,
+) -> Result<(u64, u64, u64)> {
     require_eq!(
         user_lock.is_unlocked,
         false,
         ErrorCode::LockAlreadyUnlocked
     );
 
-    let pool_state_info = &ctx.accounts.pool_state;
-    let pool_state = PoolState::try_deserialize(&mut &pool_state_info.data.borrow()[..])?;
-    require_eq!(pool_state.lp_mint,ctx.accounts.lp_mint.key(), ErrorCode::IncorrectLpMint);
-    require_eq!(pool_state.token_0_vault,ctx.accounts.token_0_vault.key());
-    require_eq!(pool_state.token_1_vault,ctx.accounts.token_1_vault.key());
-
     let (total_token_0_amount, total_token_1_amount) = pool_state.vault_amount_without_fee(
-        ctx.accounts.token_0_vault.amount,
-        ctx.accounts.token_1_vault.amount,
-    );
+        token_vaults[0].amount,
+        token_vaults[1].amount,
+    )?;
 
     msg!("Total token 0 amount in the pool: {}", total_token_0_amount);
     msg!("Total token 1 amount in the pool: {}", total_token_1_amount);
 
+    let (updated_principal_lp_tokens, lp_tokens_to_burn) = split_locked_lp(
+        user_lock,
+        pool_state,
+        total_token_0_amount,
+        total_token_1_amount,
+    )?;
+
     let results = CurveCalculator::lp_tokens_to_trading_tokens(
-        u128::from(user_lock.lock_amount),
+        u128::from(lp_tokens_to_burn),
         u128::from(pool_state.lp_supply),
         u128::from(total_token_0_amount),
         u128::from(total_token_1_amount),
@@ -190,35 +481,53 @@ pub fn collect_fees(ctx: Context<CollectFees>) -> Result<()> {
     )
     .ok_or(ErrorCode::ZeroTradingTokens)?;
 
-    msg!("Locked LP amount: {}", user_lock.lock_amount);
-    msg!("Token 0 amount belongs to locked LP: {}", results.token_0_amount);
-    msg!("Token 1 amount belongs to locked LP: {}", results.token_1_amount);
+    msg!("Token 0 amount belongs to lp to burn: {}", results.token_0_amount);
+    msg!("Token 1 amount belongs to lp to burn {}", results.token_1_amount);
 
-    let liquidity = U128::from(results.token_0_amount)
-        .checked_mul(results.token_1_amount.into())
-        .unwrap()
-        .integer_sqrt()
-        .as_u64();
+    let token_0_amount =
+        u64::try_from(results.token_0_amount).map_err(|_| error!(ErrorCode::Overflow))?;
+    let token_0_amount = std::cmp::min(total_token_0_amount, token_0_amount);
 
-    msg!("Liquidity belongs to locked LP: {}", liquidity);
+    let token_1_amount =
+        u64::try_from(results.token_1_amount).map_err(|_| error!(ErrorCode::Overflow))?;
+    let token_1_amount = std::cmp::min(total_token_1_amount, token_1_amount);
 
-    require_gt!(liquidity, 0, ErrorCode::ZeroLiquidity);
+    require!(
+        token_0_amount > 0 && token_1_amount > 0,
+        ErrorCode::ZeroTradingTokens
+    );
 
-    msg!("Principal liquidity : {}", user_lock.principal_liquidity);
-    let updated_principal_lp_tokens = U128::from(user_lock.principal_liquidity)
-        .checked_mul(user_lock.lock_amount.into())
-        .unwrap()
-        .checked_div(liquidity.into())
-        .unwrap().as_u64();
-    msg!("Updated principal LP tokens: {}", updated_principal_lp_tokens);
-    let lp_tokens_to_burn = user_lock.lock_amount
-        .checked_sub(updated_principal_lp_tokens)
-        .ok_or(ErrorCode::Overflow)?;
-    msg!("LP tokens to burn: {}", lp_tokens_to_burn);
-    require_gt!(lp_tokens_to_burn, 0, ErrorCode::ZeroLpTokensToBurn);
+    // Token-2022 transfer fees are withheld on the way to the owner, so account for the net amounts
+    let token_0_amount =
+        get_amount_after_transfer_fee(&vault_mints[0].to_account_info(), token_0_amount)?;
+    let token_1_amount =
+        get_amount_after_transfer_fee(&vault_mints[1].to_account_info(), token_1_amount)?;
 
+    msg!("Final token 0 amount to receive: {}", token_0_amount);
+    msg!("Final token 1 amount to receive: {}", token_1_amount);
+
+    shrink_to_principal(
+        user_lock,
+        lp_lock_counter,
+        pool_lock_stats,
+        reward_pool,
+        updated_principal_lp_tokens,
+        block_timestamp,
+    )?;
+
+    Ok((lp_tokens_to_burn, token_0_amount, token_1_amount))
+}
+
+/// Value the locked LP against the pool reserves and split it into the LP still backing the
+/// principal and the LP that represents accrued fees.
+pub(crate) fn split_locked_lp(
+    user_lock: &UserLock,
+    pool_state: &PoolState,
+    total_token_0_amount: u64,
+    total_token_1_amount: u64,
+) -> Result<(u64, u64)> {
     let results = CurveCalculator::lp_tokens_to_trading_tokens(
-        u128::from(lp_tokens_to_burn),
+        u128::from(user_lock.lock_amount),
         u128::from(pool_state.lp_supply),
         u128::from(total_token_0_amount),
         u128::from(total_token_1_amount),
@@ -226,133 +535,336 @@ pub fn collect_fees(ctx: Context<CollectFees>) -> Result<()> {
     )
     .ok_or(ErrorCode::ZeroTradingTokens)?;
 
-    msg!("Token 0 amount belongs to lp to burn: {}", results.token_0_amount);
-    msg!("Token 1 amount belongs to lp to burn {}", results.token_1_amount);
+    msg!("Locked LP amount: {}", user_lock.lock_amount);
+    msg!("Token 0 amount belongs to locked LP: {}", results.token_0_amount);
+    msg!("Token 1 amount belongs to locked LP: {}", results.token_1_amount);
 
-    let token_0_amount = u64::try_from(results.token_0_amount).unwrap();
-    let token_0_amount = std::cmp::min(total_token_0_amount, token_0_amount);
+    let liquidity = liquidity(
+        u64::try_from(results.token_0_amount).map_err(|_| error!(ErrorCode::Overflow))?,
+        u64::try_from(results.token_1_amount).map_err(|_| error!(ErrorCode::Overflow))?,
+    )?;
 
-    let token_1_amount = u64::try_from(results.token_1_amount).unwrap();
-    let token_1_amount = std::cmp::min(total_token_1_amount, token_1_amount);
+    msg!("Liquidity belongs to locked LP: {}", liquidity);
 
-    msg!("Final token 0 amount to receive: {}", token_0_amount);
-    msg!("Final token 1 amount to receive: {}", token_1_amount);
+    msg!("Principal liquidity : {}", user_lock.principal_liquidity);
+    let (updated_principal_lp_tokens, lp_tokens_to_burn) =
+        user_lock.split_fee_lp_tokens(liquidity)?;
+    msg!("Updated principal LP tokens: {}", updated_principal_lp_tokens);
+    msg!("LP tokens to burn: {}", lp_tokens_to_burn);
 
-    require!(
-        token_0_amount > 0 && token_1_amount > 0,
-        ErrorCode::ZeroTradingTokens
-    );
+    Ok((updated_principal_lp_tokens, lp_tokens_to_burn))
+}
 
+/// Shrink `user_lock` to `principal_lp_tokens` once its fee LP has been taken out, and
+/// checkpoint the owner's and the pool's aggregates and the reward pool.
+pub(crate) fn shrink_to_principal(
+    user_lock: &mut UserLock,
+    lp_lock_counter: &mut LpLockCounter,
+    pool_lock_stats: &mut PoolLockStats,
+    reward_pool: Option<&mut RewardPool>,
+    principal_lp_tokens: u64,
+    block_timestamp: u64,
+) -> Result<()> {
     lp_lock_counter.total_lock_amount = lp_lock_counter
     .total_lock_amount
     .checked_sub(user_lock.lock_amount)
     .ok_or(ErrorCode::UnderflowError)?;
+    let (counter_voting_power, stats_voting_power) =
+        (lp_lock_counter.voting_power, pool_lock_stats.voting_power);
+    lp_lock_counter.voting_power.remove_lock(user_lock, block_timestamp)?;
+    pool_lock_stats.remove_lock(user_lock, block_timestamp)?;
 
     // update user lock
-    user_lock.lock_amount = updated_principal_lp_tokens;
+    user_lock.lock_amount = principal_lp_tokens;
+
+    lp_lock_counter.voting_power.add_lock(user_lock, block_timestamp)?;
+    pool_lock_stats.add_lock(user_lock, block_timestamp)?;
+    // A harvest that took no fee LP leaves the voting power as it was
+    lp_lock_counter
+        .voting_power
+        .keep_timestamp_if_unchanged(&counter_voting_power);
+    pool_lock_stats
+        .voting_power
+        .keep_timestamp_if_unchanged(&stats_voting_power);
     
     lp_lock_counter.total_lock_amount = lp_lock_counter
     .total_lock_amount
     .checked_add(user_lock.lock_amount)
     .ok_or(ErrorCode::Overflow)?;
 
-    user_lock.token_0_fees_collected = user_lock
-        .token_0_fees_collected
-        .checked_add(token_0_amount)
-        .ok_or(ErrorCode::Overflow)?;
-    user_lock.token_1_fees_collected = user_lock
-        .token_1_fees_collected
-        .checked_add(token_1_amount)
-        .ok_or(ErrorCode::Overflow)?;
-    user_lock.last_updated = clock::Clock::get()?.unix_timestamp.try_into().unwrap();
+    user_lock.last_updated = block_timestamp;
 
-    transfer_from_pool_vault_to_user(
-        ctx.accounts.lock_vault_authority.to_account_info(),
-        ctx.accounts.lp_lock_vault.to_account_info(),
-        ctx.accounts.owner_lp_token.to_account_info(),
-        ctx.accounts.lp_mint.to_account_info(),
-        ctx.accounts.token_program.to_account_info(),
-        lp_tokens_to_burn,
-        ctx.accounts.lp_mint.decimals,
-        &[&[crate::AUTH_SEED.as_bytes(), &[ctx.bumps.lock_vault_authority]]],
-    )?;
-
-    let params = Withdraw {
-        lp_token_amount: lp_tokens_to_burn,
-        minimum_token_0_amount: 0,
-        minimum_token_1_amount: 0,
-    };
+    match reward_pool {
+        Some(reward_pool) => reward_pool.checkpoint(user_lock, block_timestamp)?,
+        None => require_keys_eq!(
+            user_lock.reward_pool,
+            Pubkey::default(),
+            ErrorCode::RewardPoolRequired
+        ),
+    }
 
-    let discriminator =
-        anchor_lang::solana_program::hash::hash(b"global:withdraw").to_bytes()[..8].to_vec();
-    let mut data = discriminator;
-    data.extend(params.try_to_vec()?);
-
-    let authority = ctx.accounts.authority.key();
-    let pool_state = ctx.accounts.pool_state.key();
-    let owner_lp_token = ctx.accounts.owner_lp_token.key();
-    let token_0_account = ctx.accounts.token_0_account.key();
-    let token_1_account = ctx.accounts.token_1_account.key();
-    let token_0_vault = ctx.accounts.token_0_vault.key();
-    let token_1_vault = ctx.accounts.token_1_vault.key();
-    let token_program = ctx.accounts.token_program.key();
-    let token_program_2022 = ctx.accounts.token_program_2022.key();
-    let vault_0_mint = ctx.accounts.vault_0_mint.key();
-    let vault_1_mint = ctx.accounts.vault_1_mint.key();
-    let lp_mint = ctx.accounts.lp_mint.key();
-    let memo_program = ctx.accounts.memo_program.key();
-
-    let accounts = vec![
-        AccountMeta::new(user_lock.user, true),
-        AccountMeta::new_readonly(authority, false),
-        AccountMeta::new(pool_state, false),
-        AccountMeta::new(owner_lp_token, false),
-        AccountMeta::new(token_0_account, false),
-        AccountMeta::new(token_1_account, false),
-        AccountMeta::new(token_0_vault, false),
-        AccountMeta::new(token_1_vault, false),
-        AccountMeta::new_readonly(token_program, false),
-        AccountMeta::new_readonly(token_program_2022, false),
-        AccountMeta::new_readonly(vault_0_mint, false),
-        AccountMeta::new_readonly(vault_1_mint, false),
-        AccountMeta::new(lp_mint, false),
-        AccountMeta::new_readonly(memo_program, false),
-    ];
-
-    let ix = Instruction {
-        program_id: crate::raydium_cpmm::id(),
-        accounts,
-        data 
-    };
-
-    let accounts = Box::new(vec![
-        ctx.accounts.owner.to_account_info(),
-        ctx.accounts.authority.to_account_info(),
-        ctx.accounts.pool_state.to_account_info(),
-        ctx.accounts.owner_lp_token.to_account_info(),
-        ctx.accounts.token_0_account.to_account_info(),
-        ctx.accounts.token_1_account.to_account_info(),
-        ctx.accounts.token_0_vault.to_account_info(),
-        ctx.accounts.token_1_vault.to_account_info(),
-        ctx.accounts.token_program.to_account_info(),
-        ctx.accounts.token_program_2022.to_account_info(),
-        ctx.accounts.vault_0_mint.to_account_info(),
-        ctx.accounts.vault_1_mint.to_account_info(),
-        ctx.accounts.lp_mint.to_account_info(),
-        ctx.accounts.memo_program.to_account_info()
-    ]);
-
-    invoke(&ix, &*accounts)?;
+    Ok(())
+}
 
-    emit!(
-        CollectFeesEvent {
-            user: ctx.accounts.owner.key(),
-            lp_mint: ctx.accounts.lp_mint.key(),
-            token_0_amount,
-            token_1_amount,
-        }
+/// Pay fees the owner has already received out to the lock's fee-split recipients, for
+/// `collect_fees_single_sided`, whose swap has to be paid into the owner's accounts.
+/// `remaining_accounts` holds each recipient's token_0 and token_1 accounts, in recipient order.
+pub(crate) fn distribute_fee_split<'info>(
+    accounts: &CollectFees<'info>,
+    remaining_accounts: &[AccountInfo<'info>],
+    token_0_amount: u64,
+    token_1_amount: u64,
+) -> Result<()> {
+    let fee_split = accounts
+        .fee_split
+        .as_deref()
+        .ok_or(ErrorCode::FeeSplitRequired)?;
+    require_eq!(
+        remaining_accounts.len(),
+        fee_split.recipients.len() * 2,
+        ErrorCode::FeeSplitRequired
     );
+    let token_0_payouts = fee_split.split_amount(token_0_amount)?;
+    let token_1_payouts = fee_split.split_amount(token_1_amount)?;
+    let (owner_token_0, owner_token_1) = owner_token_accounts(accounts)?;
+
+    let [token_0_program, token_1_program] =
+        [&accounts.vault_0_mint, &accounts.vault_1_mint].map(|mint| {
+            if *mint.to_account_info().owner == Token2022::id() {
+                accounts.token_program_2022.to_account_info()
+            } else {
+                accounts.token_program.to_account_info()
+            }
+        });
+
+    let mut payouts = Vec::with_capacity(fee_split.recipients.len());
+    for (i, recipient) in fee_split.recipients.iter().enumerate() {
+        let recipient_token_0 = &remaining_accounts[2 * i];
+        let recipient_token_1 = &remaining_accounts[2 * i + 1];
+        check_recipient_token_account(
+            recipient_token_0,
+            recipient,
+            &accounts.vault_0_mint,
+            ErrorCode::InvalidFeeSplitRecipient,
+        )?;
+        check_recipient_token_account(
+            recipient_token_1,
+            recipient,
+            &accounts.vault_1_mint,
+            ErrorCode::InvalidFeeSplitRecipient,
+        )?;
+
+        transfer_from_user_to_pool_vault(
+            accounts.owner.to_account_info(),
+            owner_token_0.clone(),
+            recipient_token_0.clone(),
+            accounts.vault_0_mint.to_account_info(),
+            token_0_program.clone(),
+            token_0_payouts[i],
+            accounts.vault_0_mint.decimals,
+        )?;
+        transfer_from_user_to_pool_vault(
+            accounts.owner.to_account_info(),
+            owner_token_1.clone(),
+            recipient_token_1.clone(),
+            accounts.vault_1_mint.to_account_info(),
+            token_1_program.clone(),
+            token_1_payouts[i],
+            accounts.vault_1_mint.decimals,
+        )?;
+
+        // Recipients receive their payouts net of Token-2022 transfer fees
+        payouts.push(FeeSplitPayout {
+            recipient: recipient.recipient,
+            token_0_amount: get_amount_after_transfer_fee(
+                &accounts.vault_0_mint.to_account_info(),
+                token_0_payouts[i],
+            )?,
+            token_1_amount: get_amount_after_transfer_fee(
+                &accounts.vault_1_mint.to_account_info(),
+                token_1_payouts[i],
+            )?,
+        });
+    }
+
+    emit!(FeeSplitPayoutEvent {
+        user_lock: accounts.user_lp_lock.key(),
+        lp_mint: accounts.lp_mint.key(),
+        token_0_mint: accounts.vault_0_mint.key(),
+        token_1_mint: accounts.vault_1_mint.key(),
+        payouts,
+    });
 
+    Ok(())
+}
 
+/// Check that `recipient_token` is a token account of `mint` owned by `recipient`,
+/// failing with `error` otherwise.
+pub(crate) fn check_recipient_token_account(
+    recipient_token: &AccountInfo,
+    recipient: &FeeSplitRecipient,
+    mint: &InterfaceAccount<Mint>,
+    error: ErrorCode,
+) -> Result<()> {
+    if *recipient_token.owner != *mint.to_account_info().owner {
+        return Err(error.into());
+    }
+    let token_account = TokenAccount::try_deserialize(&mut &recipient_token.data.borrow()[..])?;
+    if token_account.owner != recipient.recipient || token_account.mint != mint.key() {
+        return Err(error.into());
+    }
     Ok(())
 }
+
+#[cfg(test)]
+pub mod collect_fees_test {
+    use super::*;
+    use anchor_lang::solana_program::program_pack::Pack;
+    use anchor_spl::token::spl_token;
+
+    /// Key, lamports and data behind an SPL Token account info
+    struct TestAccount {
+        key: Pubkey,
+        lamports: u64,
+        data: Vec<u8>,
+    }
+
+    impl TestAccount {
+        fn new<T: Pack>(state: T) -> Self {
+            let mut data = vec![0u8; T::LEN];
+            state.pack_into_slice(&mut data);
+            TestAccount {
+                key: Pubkey::new_unique(),
+                lamports: 0,
+                data,
+            }
+        }
+
+        fn info(&mut self) -> AccountInfo<'_> {
+            AccountInfo::new(
+                &self.key,
+                false,
+                false,
+                &mut self.lamports,
+                &mut self.data,
+                &spl_token::ID,
+                false,
+                0,
+            )
+        }
+    }
+
+    fn new_mint() -> TestAccount {
+        TestAccount::new(spl_token::state::Mint {
+            decimals: 6,
+            is_initialized: true,
+            ..Default::default()
+        })
+    }
+
+    fn new_vault(mint: Pubkey, amount: u64) -> TestAccount {
+        TestAccount::new(spl_token::state::Account {
+            mint,
+            amount,
+            state: spl_token::state::AccountState::Initialized,
+            ..Default::default()
+        })
+    }
+
+    /// A lock and the aggregates it has been added to
+    fn new_lock(
+        lock_amount: u64,
+        principal_liquidity: u64,
+    ) -> (UserLock, LpLockCounter, PoolLockStats) {
+        let user_lock = UserLock {
+            lock_amount,
+            principal_liquidity,
+            is_locked_permanently: true,
+            ..Default::default()
+        };
+        let mut lp_lock_counter = LpLockCounter {
+            total_lock_amount: lock_amount,
+            ..Default::default()
+        };
+        let mut pool_lock_stats = PoolLockStats::default();
+        lp_lock_counter.voting_power.add_lock(&user_lock, 0).unwrap();
+        pool_lock_stats.add_lock(&user_lock, 0).unwrap();
+        (user_lock, lp_lock_counter, pool_lock_stats)
+    }
+
+    #[test]
+    fn fees_taken_as_lp_leave_the_same_principal_as_withdrawn_fees() {
+        // The lock holds 10% of the supply, worth 100_000 of each token when it was opened,
+        // and the reserves have since grown by 21% from fees
+        let pool_state = PoolState {
+            lp_supply: 1_000_000,
+            ..Default::default()
+        };
+        let (total_token_0_amount, total_token_1_amount) = (1_210_000, 1_210_000);
+        let principal_liquidity = liquidity(100_000, 100_000).unwrap();
+
+        let mut mint_0 = new_mint();
+        let mut mint_1 = new_mint();
+        let mut vault_0 = new_vault(mint_0.key, total_token_0_amount);
+        let mut vault_1 = new_vault(mint_1.key, total_token_1_amount);
+        let (mint_0_info, mint_1_info) = (mint_0.info(), mint_1.info());
+        let (vault_0_info, vault_1_info) = (vault_0.info(), vault_1.info());
+        let vault_mints = [
+            InterfaceAccount::<Mint>::try_from(&mint_0_info).unwrap(),
+            InterfaceAccount::<Mint>::try_from(&mint_1_info).unwrap(),
+        ];
+        let token_vaults = [
+            InterfaceAccount::<TokenAccount>::try_from(&vault_0_info).unwrap(),
+            InterfaceAccount::<TokenAccount>::try_from(&vault_1_info).unwrap(),
+        ];
+
+        let (mut withdrawn_lock, mut withdrawn_counter, mut withdrawn_stats) =
+            new_lock(100_000, principal_liquidity);
+        let (lp_tokens_to_burn, token_0_amount, token_1_amount) = settle_fees(
+            &mut withdrawn_lock,
+            &mut withdrawn_counter,
+            &mut withdrawn_stats,
+            None,
+            &pool_state,
+            [&token_vaults[0], &token_vaults[1]],
+            [&vault_mints[0], &vault_mints[1]],
+            1_000,
+        )
+        .unwrap();
+
+        let (mut as_lp_lock, mut as_lp_counter, mut as_lp_stats) =
+            new_lock(100_000, principal_liquidity);
+        let (principal_lp_tokens, fee_lp_tokens) = split_locked_lp(
+            &as_lp_lock,
+            &pool_state,
+            total_token_0_amount,
+            total_token_1_amount,
+        )
+        .unwrap();
+        shrink_to_principal(
+            &mut as_lp_lock,
+            &mut as_lp_counter,
+            &mut as_lp_stats,
+            None,
+            principal_lp_tokens,
+            1_000,
+        )
+        .unwrap();
+
+        // 100_000 * 100_000 / 121_000
+        assert_eq!(principal_lp_tokens, 82_644);
+        assert_eq!(as_lp_lock.lock_amount, withdrawn_lock.lock_amount);
+        assert_eq!(fee_lp_tokens, lp_tokens_to_burn);
+        assert_eq!((token_0_amount, token_1_amount), (21_000, 21_000));
+        assert_eq!(as_lp_counter.total_lock_amount, withdrawn_counter.total_lock_amount);
+        assert_eq!(
+            as_lp_counter.voting_power.permanent_amount,
+            withdrawn_counter.voting_power.permanent_amount
+        );
+        assert_eq!(
+            as_lp_stats.voting_power.permanent_amount,
+            withdrawn_stats.voting_power.permanent_amount
+        );
+        assert_eq!(as_lp_lock.last_updated, withdrawn_lock.last_updated);
+    }
+}
@@ -0,0 +1,355 @@
+use crate::error::ErrorCode;
+use crate::instructions::collect_fees::{check_recipient_token_account, settle_fees};
+use crate::states::*;
+use crate::utils::block_timestamp;
+use crate::utils::get_amount_after_transfer_fee;
+use crate::utils::transfer_from_pool_vault_to_user;
+use crate::utils::raydium;
+use crate::utils::BASIS_POINTS_DENOMINATOR;
+use crate::LP_LOCK_VAULT_SEED;
+use anchor_lang::prelude::*;
+use anchor_spl::token::Token;
+use anchor_spl::token_2022::Token2022;
+use anchor_spl::memo::spl_memo;
+use anchor_spl::token_interface::{Mint, TokenAccount};
+
+#[derive(Accounts)]
+pub struct CollectFeesAsDelegate<'info> {
+    /// Keeper the owner has set as the lock's collect delegate
+    pub collect_delegate: Signer<'info>,
+
+    /// CHECK: owner of the lock, receiving the collected fees
+    #[account(
+        address = user_lp_lock.user
+    )]
+    pub owner: UncheckedAccount<'info>,
+
+    /// CHECK: pool vault and lp mint authority
+    #[account(
+        address = crate::vault_and_lp_mint_auth::id()
+    )]
+    pub authority: UncheckedAccount<'info>,
+
+    /// CHECK: lock vault authority
+    #[account(
+        seeds = [
+            crate::AUTH_SEED.as_bytes(),
+        ],
+     bump,
+    )]
+    pub lock_vault_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Raydium pool state account
+    #[account(
+        mut,
+        owner = crate::raydium_cpmm::id()
+    )]
+    pub pool_state: UncheckedAccount<'info>,
+
+    /// Lp token mint
+    #[account(
+        mut,
+        mint::authority = authority,
+    )]
+    pub lp_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [
+            LP_LOCK_COUNTER_SEED.as_bytes(),
+            owner.key().as_ref(),
+            lp_mint.key().as_ref()
+        ],
+        bump,
+    )]
+    pub lp_lock_counter: Box<Account<'info, LpLockCounter>>,
+
+    #[account(
+        mut,
+        seeds = [
+            POOL_LOCK_STATS_SEED.as_bytes(),
+            lp_mint.key().as_ref()
+        ],
+        bump = pool_lock_stats.bump,
+    )]
+    pub pool_lock_stats: Box<Account<'info, PoolLockStats>>,
+
+    #[account(
+        mut,
+        constraint = user_lp_lock.lp_mint == lp_mint.key(),
+        constraint = user_lp_lock.collect_delegate != Pubkey::default(),
+        constraint = user_lp_lock.collect_delegate == collect_delegate.key(),
+    )]
+    pub user_lp_lock: Box<Account<'info, UserLock>>,
+
+    /// CHECK The vault that holds the locked LP tokens
+    #[account(
+        mut,
+        token::mint = lp_mint,
+        token::authority = lock_vault_authority,
+        seeds = [
+            LP_LOCK_VAULT_SEED.as_bytes(),
+            user_lp_lock.pda_owner().as_ref(),
+            lp_mint.key().as_ref(),
+            user_lp_lock.lock_count.to_le_bytes().as_ref()
+        ],
+        bump,
+    )]
+    pub lp_lock_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The owner's token account for receive token_0, required unless the lock has a fee split
+    #[account(
+        mut,
+        token::mint = token_0_vault.mint,
+        token::authority = owner
+    )]
+    pub token_0_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// The owner's token account for receive token_1, required unless the lock has a fee split
+    #[account(
+        mut,
+        token::mint = token_1_vault.mint,
+        token::authority = owner
+    )]
+    pub token_1_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// The delegate's LP token account receiving the keeper tip. The tip is skipped if omitted.
+    #[account(
+        mut,
+        token::mint = lp_mint,
+        token::authority = collect_delegate,
+    )]
+    pub keeper_lp_token: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// The address that holds pool tokens for token_0
+    #[account(mut)]
+    pub token_0_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The address that holds pool tokens for token_1
+    #[account(mut)]
+    pub token_1_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// token Program
+    pub token_program: Program<'info, Token>,
+
+    /// Token program 2022
+    pub token_program_2022: Program<'info, Token2022>,
+
+    /// The mint of token_0 vault
+    #[account(
+        address = token_0_vault.mint
+    )]
+    pub vault_0_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The mint of token_1 vault
+    #[account(
+        address = token_1_vault.mint
+    )]
+    pub vault_1_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// memo program
+    /// CHECK:
+    #[account(
+        address = spl_memo::id()
+    )]
+    pub memo_program: UncheckedAccount<'info>,
+
+    /// CHECK: This account is owned by another program
+    #[account(
+        address = crate::raydium_cpmm::id()
+    )]
+    pub raydium_cpmm_program: AccountInfo<'info>,
+
+    /// Reward pool the lock is enrolled in, required if it has one
+    #[account(
+        mut,
+        address = user_lp_lock.reward_pool @ ErrorCode::IncorrectRewardPool,
+    )]
+    pub reward_pool: Option<Box<Account<'info, RewardPool>>>,
+
+    /// Fee split of the lock, required if the lock has one.
+    /// Each recipient's token_0 and token_1 accounts follow in `remaining_accounts`, in recipient order.
+    #[account(
+        seeds = [
+            FEE_SPLIT_SEED.as_bytes(),
+            user_lp_lock.key().as_ref()
+        ],
+        bump = fee_split.bump,
+    )]
+    pub fee_split: Option<Box<Account<'info, FeeSplit>>>,
+}
+
+/// Collect the fees of a lock as its collect delegate. The fee LP is burned straight from the
+/// lock vault into the owner's token accounts, or the fee-split recipients' if the lock has a
+/// fee split, after paying the delegate its `keeper_tip_bps` share in LP.
+pub fn collect_fees_as_delegate<'info>(
+    ctx: Context<'_, '_, '_, 'info, CollectFeesAsDelegate<'info>>,
+) -> Result<()> {
+    let accounts = ctx.accounts;
+    require!(
+        !accounts.user_lp_lock.has_fee_split || accounts.fee_split.is_some(),
+        ErrorCode::FeeSplitRequired
+    );
+
+    let pool_state_info = &accounts.pool_state;
+    let pool_state = PoolState::try_deserialize(&mut &pool_state_info.data.borrow()[..])?;
+    require_eq!(pool_state.lp_mint, accounts.lp_mint.key(), ErrorCode::IncorrectLpMint);
+    require_keys_eq!(
+        pool_state.token_0_vault,
+        accounts.token_0_vault.key(),
+        ErrorCode::IncorrectPoolAccount
+    );
+    require_keys_eq!(
+        pool_state.token_1_vault,
+        accounts.token_1_vault.key(),
+        ErrorCode::IncorrectPoolAccount
+    );
+    pool_state.require_enabled(PoolStatusBitIndex::Withdraw)?;
+
+    let (lp_tokens_to_burn, _, _) = settle_fees(
+        &mut accounts.user_lp_lock,
+        &mut accounts.lp_lock_counter,
+        &mut accounts.pool_lock_stats,
+        accounts.reward_pool.as_deref_mut().map(|r| &mut **r),
+        &pool_state,
+        [&accounts.token_0_vault, &accounts.token_1_vault],
+        [&accounts.vault_0_mint, &accounts.vault_1_mint],
+        block_timestamp()?,
+    )?;
+
+    let signer_seeds: &[&[&[u8]]] =
+        &[&[crate::AUTH_SEED.as_bytes(), &[ctx.bumps.lock_vault_authority]]];
+
+    let mut lp_tokens_to_withdraw = lp_tokens_to_burn;
+    if let Some(keeper_lp_token) = accounts.keeper_lp_token.as_ref() {
+        let tip = (u128::from(lp_tokens_to_burn)
+            * u128::from(accounts.user_lp_lock.keeper_tip_bps)
+            / u128::from(BASIS_POINTS_DENOMINATOR)) as u64;
+        if tip > 0 {
+            transfer_from_pool_vault_to_user(
+                accounts.lock_vault_authority.to_account_info(),
+                accounts.lp_lock_vault.to_account_info(),
+                keeper_lp_token.to_account_info(),
+                accounts.lp_mint.to_account_info(),
+                accounts.token_program.to_account_info(),
+                tip,
+                accounts.lp_mint.decimals,
+                signer_seeds,
+            )?;
+            lp_tokens_to_withdraw -= tip;
+
+            emit!(KeeperTipEvent {
+                lock_id: accounts.user_lp_lock.lock_id,
+                keeper: accounts.collect_delegate.key(),
+                lp_amount: tip,
+            });
+        }
+    }
+
+    // Each destination gets its own withdraw, so nothing passes through the owner's wallet
+    let mut destinations = Vec::new();
+    match accounts.fee_split.as_deref() {
+        Some(fee_split) => {
+            require_eq!(
+                ctx.remaining_accounts.len(),
+                fee_split.recipients.len() * 2,
+                ErrorCode::FeeSplitRequired
+            );
+            let lp_payouts = fee_split.split_amount(lp_tokens_to_withdraw)?;
+            for (i, recipient) in fee_split.recipients.iter().enumerate() {
+                let recipient_token_0 = &ctx.remaining_accounts[2 * i];
+                let recipient_token_1 = &ctx.remaining_accounts[2 * i + 1];
+                check_recipient_token_account(
+                    recipient_token_0,
+                    recipient,
+                    &accounts.vault_0_mint,
+                    ErrorCode::InvalidFeeSplitRecipient,
+                )?;
+                check_recipient_token_account(
+                    recipient_token_1,
+                    recipient,
+                    &accounts.vault_1_mint,
+                    ErrorCode::InvalidFeeSplitRecipient,
+                )?;
+                destinations.push((
+                    recipient_token_0.clone(),
+                    recipient_token_1.clone(),
+                    lp_payouts[i],
+                ));
+            }
+        }
+        None => match (&accounts.token_0_account, &accounts.token_1_account) {
+            (Some(token_0_account), Some(token_1_account)) => destinations.push((
+                token_0_account.to_account_info(),
+                token_1_account.to_account_info(),
+                lp_tokens_to_withdraw,
+            )),
+            _ => return err!(ErrorCode::OwnerTokenAccountRequired),
+        },
+    }
+
+    // Report what the destinations received, i.e. the fees net of the keeper tip
+    let mut token_0_amount = 0u64;
+    let mut token_1_amount = 0u64;
+    for (token_0_account, token_1_account, lp_token_amount) in destinations {
+        if lp_token_amount == 0 {
+            continue;
+        }
+        let token_0_vault_before = accounts.token_0_vault.amount;
+        let token_1_vault_before = accounts.token_1_vault.amount;
+        raydium::withdraw(
+            raydium::WithdrawAccounts {
+                owner: accounts.lock_vault_authority.to_account_info(),
+                authority: accounts.authority.to_account_info(),
+                pool_state: accounts.pool_state.to_account_info(),
+                owner_lp_token: accounts.lp_lock_vault.to_account_info(),
+                token_0_account,
+                token_1_account,
+                token_0_vault: accounts.token_0_vault.to_account_info(),
+                token_1_vault: accounts.token_1_vault.to_account_info(),
+                token_program: accounts.token_program.to_account_info(),
+                token_program_2022: accounts.token_program_2022.to_account_info(),
+                vault_0_mint: accounts.vault_0_mint.to_account_info(),
+                vault_1_mint: accounts.vault_1_mint.to_account_info(),
+                lp_mint: accounts.lp_mint.to_account_info(),
+                memo_program: accounts.memo_program.to_account_info(),
+            },
+            lp_token_amount,
+            0,
+            0,
+            signer_seeds,
+        )?;
+
+        accounts.token_0_vault.reload()?;
+        accounts.token_1_vault.reload()?;
+        token_0_amount = token_0_amount
+            .checked_add(get_amount_after_transfer_fee(
+                &accounts.vault_0_mint.to_account_info(),
+                token_0_vault_before - accounts.token_0_vault.amount,
+            )?)
+            .ok_or(ErrorCode::Overflow)?;
+        token_1_amount = token_1_amount
+            .checked_add(get_amount_after_transfer_fee(
+                &accounts.vault_1_mint.to_account_info(),
+                token_1_vault_before - accounts.token_1_vault.amount,
+            )?)
+            .ok_or(ErrorCode::Overflow)?;
+    }
+
+    // The keeper's tip is not part of the fees the owner collected
+    accounts
+        .user_lp_lock
+        .record_fees_collected(token_0_amount, token_1_amount)?;
+
+    emit!(
+        CollectFeesEvent {
+            lock_id: accounts.user_lp_lock.lock_id,
+            user: accounts.owner.key(),
+            lp_mint: accounts.lp_mint.key(),
+            token_0_amount,
+            token_1_amount,
+        }
+    );
+
+    Ok(())
+}
@@ -0,0 +1,168 @@
+use crate::error::ErrorCode;
+use crate::instructions::collect_fees::{shrink_to_principal, split_locked_lp};
+use crate::states::*;
+use crate::utils::block_timestamp;
+use crate::utils::transfer_from_pool_vault_to_user;
+use crate::LP_LOCK_VAULT_SEED;
+use anchor_lang::prelude::*;
+use anchor_spl::token::Token;
+use anchor_spl::token_interface::{Mint, TokenAccount};
+
+#[derive(Accounts)]
+pub struct CollectFeesAsLp<'info> {
+    pub owner: Signer<'info>,
+
+    /// CHECK: lock vault authority
+    #[account(
+        seeds = [
+            crate::AUTH_SEED.as_bytes(),
+        ],
+        bump,
+    )]
+    pub lock_vault_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Raydium pool state account
+    #[account(
+        owner = crate::raydium_cpmm::id()
+    )]
+    pub pool_state: UncheckedAccount<'info>,
+
+    /// The owner's LP token account receiving the fee LP
+    #[account(
+        mut,
+        token::mint = lp_mint,
+        token::authority = owner,
+        token::token_program = token_program,
+    )]
+    pub owner_lp_token: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Lp token mint
+    pub lp_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [
+            LP_LOCK_COUNTER_SEED.as_bytes(),
+            owner.key().as_ref(),
+            lp_mint.key().as_ref()
+        ],
+        bump,
+    )]
+    pub lp_lock_counter: Box<Account<'info, LpLockCounter>>,
+
+    #[account(
+        mut,
+        seeds = [
+            POOL_LOCK_STATS_SEED.as_bytes(),
+            lp_mint.key().as_ref()
+        ],
+        bump = pool_lock_stats.bump,
+    )]
+    pub pool_lock_stats: Box<Account<'info, PoolLockStats>>,
+
+    #[account(
+        mut,
+        constraint = user_lp_lock.user == owner.key(),
+        constraint = user_lp_lock.lp_mint == lp_mint.key(),
+    )]
+    pub user_lp_lock: Box<Account<'info, UserLock>>,
+
+    /// CHECK The vault that holds the locked LP tokens
+    #[account(
+        mut,
+        token::mint = lp_mint,
+        token::authority = lock_vault_authority,
+        seeds = [
+            LP_LOCK_VAULT_SEED.as_bytes(),
+            user_lp_lock.pda_owner().as_ref(),
+            lp_mint.key().as_ref(),
+            user_lp_lock.lock_count.to_le_bytes().as_ref()
+        ],
+        bump,
+    )]
+    pub lp_lock_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The address that holds pool tokens for token_0
+    pub token_0_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The address that holds pool tokens for token_1
+    pub token_1_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// token Program
+    pub token_program: Program<'info, Token>,
+
+    /// Reward pool the lock is enrolled in, required if it has one
+    #[account(
+        mut,
+        address = user_lp_lock.reward_pool @ ErrorCode::IncorrectRewardPool,
+    )]
+    pub reward_pool: Option<Box<Account<'info, RewardPool>>>,
+}
+
+/// Pay the fee LP of a lock out to the owner as LP tokens instead of burning it for the
+/// underlying tokens. This still works while Raydium has withdrawals disabled on the pool.
+pub fn collect_fees_as_lp(ctx: Context<CollectFeesAsLp>) -> Result<()> {
+    let accounts = ctx.accounts;
+    require_eq!(
+        accounts.user_lp_lock.is_unlocked,
+        false,
+        ErrorCode::LockAlreadyUnlocked
+    );
+    // Fee-split recipients are paid in the underlying tokens, which this path never withdraws
+    require_eq!(
+        accounts.user_lp_lock.has_fee_split,
+        false,
+        ErrorCode::LockHasFeeSplit
+    );
+
+    let pool_state = PoolState::try_deserialize(&mut &accounts.pool_state.data.borrow()[..])?;
+    require_eq!(pool_state.lp_mint, accounts.lp_mint.key(), ErrorCode::IncorrectLpMint);
+    require_keys_eq!(
+        pool_state.token_0_vault,
+        accounts.token_0_vault.key(),
+        ErrorCode::IncorrectPoolAccount
+    );
+    require_keys_eq!(
+        pool_state.token_1_vault,
+        accounts.token_1_vault.key(),
+        ErrorCode::IncorrectPoolAccount
+    );
+
+    let (total_token_0_amount, total_token_1_amount) = pool_state
+        .vault_amount_without_fee(accounts.token_0_vault.amount, accounts.token_1_vault.amount)?;
+    let (principal_lp_tokens, fee_lp_tokens) = split_locked_lp(
+        &accounts.user_lp_lock,
+        &pool_state,
+        total_token_0_amount,
+        total_token_1_amount,
+    )?;
+
+    shrink_to_principal(
+        &mut accounts.user_lp_lock,
+        &mut accounts.lp_lock_counter,
+        &mut accounts.pool_lock_stats,
+        accounts.reward_pool.as_deref_mut().map(|r| &mut **r),
+        principal_lp_tokens,
+        block_timestamp()?,
+    )?;
+
+    transfer_from_pool_vault_to_user(
+        accounts.lock_vault_authority.to_account_info(),
+        accounts.lp_lock_vault.to_account_info(),
+        accounts.owner_lp_token.to_account_info(),
+        accounts.lp_mint.to_account_info(),
+        accounts.token_program.to_account_info(),
+        fee_lp_tokens,
+        accounts.lp_mint.decimals,
+        &[&[crate::AUTH_SEED.as_bytes(), &[ctx.bumps.lock_vault_authority]]],
+    )?;
+
+    emit!(CollectFeesAsLpEvent {
+        lock_id: accounts.user_lp_lock.lock_id,
+        user: accounts.owner.key(),
+        lp_mint: accounts.lp_mint.key(),
+        lp_amount: fee_lp_tokens,
+    });
+
+    Ok(())
+}
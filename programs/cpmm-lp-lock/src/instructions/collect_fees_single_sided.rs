@@ -0,0 +1,218 @@
+use crate::curve::CurveCalculator;
+use crate::error::ErrorCode;
+use crate::instructions::collect_fees::*;
+use crate::states::*;
+use crate::utils::get_amount_after_transfer_fee;
+use crate::utils::raydium;
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::Token2022;
+
+#[derive(Accounts)]
+pub struct CollectFeesSingleSided<'info> {
+    pub collect_fees: CollectFees<'info>,
+
+    /// CHECK: Raydium amm config of the pool
+    #[account(
+        owner = crate::raydium_cpmm::id()
+    )]
+    pub amm_config: UncheckedAccount<'info>,
+
+    /// CHECK: Raydium oracle observation account of the pool
+    #[account(
+        mut,
+        owner = crate::raydium_cpmm::id()
+    )]
+    pub observation_state: UncheckedAccount<'info>,
+}
+
+/// Collect the lock's fees like `collect_fees`, then swap the other side into `output_mint`
+/// through the same pool so the owner is paid in one token only.
+/// Fails unless at least `minimum_amount_out` of `output_mint` is received in total.
+pub fn collect_fees_single_sided<'info>(
+    ctx: Context<'_, '_, '_, 'info, CollectFeesSingleSided<'info>>,
+    output_mint: Pubkey,
+    minimum_amount_out: u64,
+) -> Result<()> {
+    let accounts = &mut ctx.accounts.collect_fees;
+    let zero_for_one = if output_mint == accounts.vault_1_mint.key() {
+        true
+    } else {
+        require_keys_eq!(
+            output_mint,
+            accounts.vault_0_mint.key(),
+            ErrorCode::InvalidInput
+        );
+        false
+    };
+
+    let (token_0_received, token_1_received) =
+        withdraw_fees(accounts, ctx.bumps.collect_fees.lock_vault_authority)?;
+
+    let pool_state = PoolState::try_deserialize(&mut &accounts.pool_state.data.borrow()[..])?;
+    require_keys_eq!(
+        pool_state.amm_config,
+        ctx.accounts.amm_config.key(),
+        ErrorCode::IncorrectPoolAccount
+    );
+    require_keys_eq!(
+        pool_state.observation_key,
+        ctx.accounts.observation_state.key(),
+        ErrorCode::IncorrectPoolAccount
+    );
+    pool_state.require_enabled(PoolStatusBitIndex::Swap)?;
+    let amm_config =
+        AmmConfig::try_deserialize(&mut &ctx.accounts.amm_config.data.borrow()[..])?;
+
+    // Quote the swap against the pool reserves left after the withdraw
+    accounts.token_0_vault.reload()?;
+    accounts.token_1_vault.reload()?;
+    let (total_token_0_amount, total_token_1_amount) = pool_state
+        .vault_amount_without_fee(accounts.token_0_vault.amount, accounts.token_1_vault.amount)?;
+
+    // `withdraw_fees` paid the owner's token accounts, so both are present
+    let (Some(token_0_account), Some(token_1_account)) =
+        (&accounts.token_0_account, &accounts.token_1_account)
+    else {
+        return err!(ErrorCode::OwnerTokenAccountRequired);
+    };
+
+    let (
+        input_token_account,
+        output_token_account,
+        input_vault,
+        output_vault,
+        input_mint,
+        output_mint,
+        amount_in,
+        output_received,
+        input_vault_amount,
+        output_vault_amount,
+    ) = if zero_for_one {
+        (
+            token_0_account,
+            token_1_account,
+            &accounts.token_0_vault,
+            &accounts.token_1_vault,
+            &accounts.vault_0_mint,
+            &accounts.vault_1_mint,
+            token_0_received,
+            token_1_received,
+            total_token_0_amount,
+            total_token_1_amount,
+        )
+    } else {
+        (
+            token_1_account,
+            token_0_account,
+            &accounts.token_1_vault,
+            &accounts.token_0_vault,
+            &accounts.vault_1_mint,
+            &accounts.vault_0_mint,
+            token_1_received,
+            token_0_received,
+            total_token_1_amount,
+            total_token_0_amount,
+        )
+    };
+
+    let actual_amount_in =
+        get_amount_after_transfer_fee(&input_mint.to_account_info(), amount_in)?;
+    let result = CurveCalculator::swap_base_input(
+        u128::from(actual_amount_in),
+        u128::from(input_vault_amount),
+        u128::from(output_vault_amount),
+        amm_config.trade_fee_rate,
+        amm_config.protocol_fee_rate,
+        amm_config.fund_fee_rate,
+    )
+    .ok_or(ErrorCode::ZeroTradingTokens)?;
+    let amount_out = u64::try_from(result.destination_amount_swapped)
+        .map_err(|_| error!(ErrorCode::Overflow))?;
+    let amount_out = get_amount_after_transfer_fee(&output_mint.to_account_info(), amount_out)?;
+    require_gt!(amount_out, 0, ErrorCode::ZeroTradingTokens);
+
+    let swap_minimum_amount_out = minimum_amount_out.saturating_sub(output_received);
+    require_gte!(
+        amount_out,
+        swap_minimum_amount_out,
+        ErrorCode::ExceededSlippage
+    );
+
+    let token_program_of = |mint: &AccountInfo<'info>| {
+        if *mint.owner == Token2022::id() {
+            accounts.token_program_2022.to_account_info()
+        } else {
+            accounts.token_program.to_account_info()
+        }
+    };
+    let output_balance_before = output_token_account.amount;
+    raydium::swap_base_input(
+        raydium::SwapBaseInputAccounts {
+            payer: accounts.owner.to_account_info(),
+            authority: accounts.authority.to_account_info(),
+            amm_config: ctx.accounts.amm_config.to_account_info(),
+            pool_state: accounts.pool_state.to_account_info(),
+            input_token_account: input_token_account.to_account_info(),
+            output_token_account: output_token_account.to_account_info(),
+            input_vault: input_vault.to_account_info(),
+            output_vault: output_vault.to_account_info(),
+            input_token_program: token_program_of(&input_mint.to_account_info()),
+            output_token_program: token_program_of(&output_mint.to_account_info()),
+            input_token_mint: input_mint.to_account_info(),
+            output_token_mint: output_mint.to_account_info(),
+            observation_state: ctx.accounts.observation_state.to_account_info(),
+        },
+        amount_in,
+        swap_minimum_amount_out,
+        &[],
+    )?;
+
+    let input_mint = input_mint.key();
+    let output_mint = output_mint.key();
+    let output_token_account = if zero_for_one {
+        &mut accounts.token_1_account
+    } else {
+        &mut accounts.token_0_account
+    }
+    .as_mut()
+    .ok_or(ErrorCode::OwnerTokenAccountRequired)?;
+    output_token_account.reload()?;
+    let swap_amount_out = output_token_account
+        .amount
+        .checked_sub(output_balance_before)
+        .ok_or(ErrorCode::UnderflowError)?;
+    let total_amount_out = output_received
+        .checked_add(swap_amount_out)
+        .ok_or(ErrorCode::Overflow)?;
+    require_gte!(
+        total_amount_out,
+        minimum_amount_out,
+        ErrorCode::ExceededSlippage
+    );
+
+    if accounts.fee_split.is_some() {
+        let (token_0_amount, token_1_amount) = if zero_for_one {
+            (0, total_amount_out)
+        } else {
+            (total_amount_out, 0)
+        };
+        distribute_fee_split(
+            accounts,
+            ctx.remaining_accounts,
+            token_0_amount,
+            token_1_amount,
+        )?;
+    }
+
+    emit!(CollectFeesSingleSidedEvent {
+        user: accounts.owner.key(),
+        lp_mint: accounts.lp_mint.key(),
+        input_mint,
+        output_mint,
+        amount_in,
+        amount_out: swap_amount_out,
+        total_amount_out,
+    });
+
+    Ok(())
+}
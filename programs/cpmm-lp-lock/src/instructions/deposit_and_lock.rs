@@ -0,0 +1,444 @@
+use crate::error::ErrorCode;
+use crate::instructions::lock_lp::{assign_lock_id, check_pool_registry, lp_lock_event, open_lock};
+use crate::states::*;
+use crate::utils::raydium;
+use crate::utils::*;
+use crate::LP_LOCK_VAULT_SEED;
+use anchor_lang::prelude::*;
+use anchor_spl::token::Token;
+use anchor_spl::token_2022::Token2022;
+use anchor_spl::token_interface::{Mint, TokenAccount};
+use std::ops::Add;
+
+#[derive(Accounts)]
+pub struct DepositAndLock<'info> {
+    pub owner: Signer<'info>,
+
+    /// Pays rent for the accounts created by the lock, may be the owner
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: pool vault and lp mint authority
+    #[account(
+        address = crate::vault_and_lp_mint_auth::id()
+    )]
+    pub authority: UncheckedAccount<'info>,
+
+    /// CHECK: lock vault authority
+    #[account(
+        seeds = [
+            crate::AUTH_SEED.as_bytes(),
+        ],
+        bump,
+    )]
+    pub lock_vault_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Raydium pool state account
+    #[account(
+        mut,
+        owner = crate::raydium_cpmm::id()
+    )]
+    pub pool_state: UncheckedAccount<'info>,
+
+    /// Owner lp token account the minted LP passes through on its way to the lock vault
+    #[account(
+        mut,
+        token::mint = lp_mint,
+        token::authority = owner,
+        token::token_program = token_program,
+    )]
+    pub owner_lp_token: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The owner's token account to deposit token_0 from
+    #[account(
+        mut,
+        token::mint = token_0_vault.mint,
+        token::authority = owner
+    )]
+    pub token_0_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The owner's token account to deposit token_1 from
+    #[account(
+        mut,
+        token::mint = token_1_vault.mint,
+        token::authority = owner
+    )]
+    pub token_1_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The address that holds pool tokens for token_0
+    #[account(mut)]
+    pub token_0_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The address that holds pool tokens for token_1
+    #[account(mut)]
+    pub token_1_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// token Program
+    pub token_program: Program<'info, Token>,
+
+    /// Token program 2022
+    pub token_program_2022: Program<'info, Token2022>,
+
+    /// The mint of token_0 vault
+    #[account(
+        address = token_0_vault.mint
+    )]
+    pub vault_0_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The mint of token_1 vault
+    #[account(
+        address = token_1_vault.mint
+    )]
+    pub vault_1_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// CHECK: pool registry settings, uninitialized until the admin configures the registry
+    #[account(
+        seeds = [
+            REGISTRY_CONFIG_SEED.as_bytes(),
+        ],
+        bump,
+    )]
+    pub registry_config: UncheckedAccount<'info>,
+
+    /// CHECK: registry entry of the pool, uninitialized when the pool has no verdict
+    #[account(
+        seeds = [
+            REGISTRY_ENTRY_SEED.as_bytes(),
+            pool_state.key().as_ref()
+        ],
+        bump,
+    )]
+    pub pool_registry_entry: UncheckedAccount<'info>,
+
+    /// CHECK: registry entry of the token_0 mint, uninitialized when the mint has no verdict
+    #[account(
+        seeds = [
+            REGISTRY_ENTRY_SEED.as_bytes(),
+            vault_0_mint.key().as_ref()
+        ],
+        bump,
+    )]
+    pub vault_0_mint_registry_entry: UncheckedAccount<'info>,
+
+    /// CHECK: registry entry of the token_1 mint, uninitialized when the mint has no verdict
+    #[account(
+        seeds = [
+            REGISTRY_ENTRY_SEED.as_bytes(),
+            vault_1_mint.key().as_ref()
+        ],
+        bump,
+    )]
+    pub vault_1_mint_registry_entry: UncheckedAccount<'info>,
+
+    /// Lp token mint
+    #[account(
+        mut,
+        mint::authority = authority,
+    )]
+    pub lp_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        init_if_needed,
+        seeds = [
+            LP_LOCK_COUNTER_SEED.as_bytes(),
+            owner.key().as_ref(),
+            lp_mint.key().as_ref()
+        ],
+        bump,
+        payer = payer,
+        space = LpLockCounter::LEN,
+    )]
+    pub lp_lock_counter: Box<Account<'info, LpLockCounter>>,
+
+    #[account(
+        init,
+        seeds = [
+            USER_LOCK_SEED.as_bytes(),
+            owner.key().as_ref(),
+            lp_mint.key().as_ref(),
+            lp_lock_counter.total_lock_count.add(1).to_le_bytes().as_ref()
+        ],
+        bump,
+        payer = payer,
+        space = UserLock::LEN,
+    )]
+    pub user_lp_lock: Box<Account<'info, UserLock>>,
+
+    #[account(
+        init_if_needed,
+        seeds = [
+            POOL_LOCK_STATS_SEED.as_bytes(),
+            lp_mint.key().as_ref()
+        ],
+        bump,
+        payer = payer,
+        space = PoolLockStats::LEN,
+    )]
+    pub pool_lock_stats: Box<Account<'info, PoolLockStats>>,
+
+    /// Assigns the new lock its program-wide ID. Every lock creation writes this one account,
+    /// so lock creations across all pools are serialized on its write lock.
+    #[account(
+        init_if_needed,
+        seeds = [
+            GLOBAL_LOCK_COUNTER_SEED.as_bytes(),
+        ],
+        bump,
+        payer = payer,
+        space = GlobalLockCounter::LEN,
+    )]
+    pub global_lock_counter: Box<Account<'info, GlobalLockCounter>>,
+
+    /// Maps the new lock's program-wide ID to its address
+    #[account(
+        init,
+        seeds = [
+            LOCK_ID_SEED.as_bytes(),
+            global_lock_counter.total_lock_count.add(1).to_le_bytes().as_ref()
+        ],
+        bump,
+        payer = payer,
+        space = LockIdIndex::LEN,
+    )]
+    pub lock_id_index: Box<Account<'info, LockIdIndex>>,
+
+    /// Active-lock index page tracking the new lock
+    #[account(
+        init_if_needed,
+        seeds = [
+            ACTIVE_LOCK_PAGE_SEED.as_bytes(),
+            owner.key().as_ref(),
+            lp_mint.key().as_ref(),
+            ActiveLockPage::page_of(lp_lock_counter.total_lock_count.add(1)).to_le_bytes().as_ref()
+        ],
+        bump,
+        payer = payer,
+        space = ActiveLockPage::LEN,
+    )]
+    pub active_lock_page: Box<Account<'info, ActiveLockPage>>,
+
+    /// CHECK The vault that holds the locked LP tokens
+    #[account(
+        mut,
+        seeds = [
+            LP_LOCK_VAULT_SEED.as_bytes(),
+            owner.key().as_ref(),
+            lp_mint.key().as_ref(),
+            lp_lock_counter.total_lock_count.add(1).to_le_bytes().as_ref()
+        ],
+        bump,
+    )]
+    pub lp_lock_vault: UncheckedAccount<'info>,
+
+    /// Reward pool of the LP mint to enroll the new lock in, if any
+    #[account(
+        mut,
+        seeds = [
+            REWARD_POOL_SEED.as_bytes(),
+            lp_mint.key().as_ref(),
+            reward_pool.campaign.to_le_bytes().as_ref()
+        ],
+        bump = reward_pool.bump,
+    )]
+    pub reward_pool: Option<Box<Account<'info, RewardPool>>>,
+
+    /// CHECK: This account is owned by another program
+    #[account(
+        address = crate::raydium_cpmm::id()
+    )]
+    pub raydium_cpmm_program: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Deposit the owner's token_0/token_1 into the Raydium pool for exactly `lp_token_amount` LP
+/// and lock the minted LP in a new lock within the same instruction.
+/// The lock's principals are the token amounts the pool actually received.
+pub fn deposit_and_lock(
+    ctx: Context<DepositAndLock>,
+    lp_token_amount: u64,
+    maximum_token_0_amount: u64,
+    maximum_token_1_amount: u64,
+    lock_duration: u64,
+    lock_permanent: bool,
+    metadata: LockMetadata,
+) -> Result<CreatedLock> {
+    require!(lp_token_amount > 100, ErrorCode::InitLpAmountTooLess);
+    metadata.validate()?;
+
+    if !lock_permanent {
+        require!(
+            lock_duration < MAX_LOCK_DURATION,
+            ErrorCode::LockDurationTooLong
+        );
+    }
+
+    let pool_state = PoolState::try_deserialize(&mut &ctx.accounts.pool_state.data.borrow()[..])?;
+    require_eq!(
+        pool_state.lp_mint,
+        ctx.accounts.lp_mint.key(),
+        ErrorCode::IncorrectLpMint
+    );
+    require_keys_eq!(
+        pool_state.token_0_vault,
+        ctx.accounts.token_0_vault.key(),
+        ErrorCode::IncorrectPoolAccount
+    );
+    require_keys_eq!(
+        pool_state.token_1_vault,
+        ctx.accounts.token_1_vault.key(),
+        ErrorCode::IncorrectPoolAccount
+    );
+    pool_state.require_enabled(PoolStatusBitIndex::Deposit)?;
+
+    check_pool_registry(
+        &ctx.accounts.registry_config,
+        &ctx.accounts.pool_registry_entry,
+        [&ctx.accounts.vault_0_mint, &ctx.accounts.vault_1_mint],
+        [
+            &ctx.accounts.vault_0_mint_registry_entry,
+            &ctx.accounts.vault_1_mint_registry_entry,
+        ],
+    )?;
+
+    let lp_lock_counter = &mut ctx.accounts.lp_lock_counter;
+    let user_lock = &mut ctx.accounts.user_lp_lock;
+
+    // Check if lp lock counter is initialized in the same transaction
+    lp_lock_counter.initialize_if_needed(ctx.accounts.owner.key(), ctx.accounts.lp_mint.key());
+
+    let pool_lock_stats = &mut ctx.accounts.pool_lock_stats;
+    if pool_lock_stats.lp_mint == Pubkey::default() {
+        pool_lock_stats.bump = ctx.bumps.pool_lock_stats;
+        pool_lock_stats.lp_mint = ctx.accounts.lp_mint.key();
+    }
+
+    let new_lock_count = lp_lock_counter
+        .total_lock_count
+        .checked_add(1)
+        .ok_or(ErrorCode::Overflow)?;
+
+    create_token_account(
+        &ctx.accounts.lock_vault_authority.to_account_info(),
+        &ctx.accounts.payer.to_account_info(),
+        &ctx.accounts.lp_lock_vault.to_account_info(),
+        &ctx.accounts.lp_mint.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        &ctx.accounts.token_program.to_account_info(),
+        &[&[
+            LP_LOCK_VAULT_SEED.as_bytes(),
+            ctx.accounts.owner.key().as_ref(),
+            ctx.accounts.lp_mint.key().as_ref(),
+            new_lock_count.to_le_bytes().as_ref(),
+            &[ctx.bumps.lp_lock_vault][..],
+        ][..]],
+    )?;
+
+    let token_0_vault_before = ctx.accounts.token_0_vault.amount;
+    let token_1_vault_before = ctx.accounts.token_1_vault.amount;
+
+    raydium::deposit(
+        raydium::DepositAccounts {
+            owner: ctx.accounts.owner.to_account_info(),
+            authority: ctx.accounts.authority.to_account_info(),
+            pool_state: ctx.accounts.pool_state.to_account_info(),
+            owner_lp_token: ctx.accounts.owner_lp_token.to_account_info(),
+            token_0_account: ctx.accounts.token_0_account.to_account_info(),
+            token_1_account: ctx.accounts.token_1_account.to_account_info(),
+            token_0_vault: ctx.accounts.token_0_vault.to_account_info(),
+            token_1_vault: ctx.accounts.token_1_vault.to_account_info(),
+            token_program: ctx.accounts.token_program.to_account_info(),
+            token_program_2022: ctx.accounts.token_program_2022.to_account_info(),
+            vault_0_mint: ctx.accounts.vault_0_mint.to_account_info(),
+            vault_1_mint: ctx.accounts.vault_1_mint.to_account_info(),
+            lp_mint: ctx.accounts.lp_mint.to_account_info(),
+        },
+        lp_token_amount,
+        maximum_token_0_amount,
+        maximum_token_1_amount,
+        &[],
+    )?;
+
+    // Move the minted LP into the lock vault before the instruction ends
+    transfer_from_user_to_pool_vault(
+        ctx.accounts.owner.to_account_info(),
+        ctx.accounts.owner_lp_token.to_account_info(),
+        ctx.accounts.lp_lock_vault.to_account_info(),
+        ctx.accounts.lp_mint.to_account_info(),
+        ctx.accounts.token_program.to_account_info(),
+        lp_token_amount,
+        ctx.accounts.lp_mint.decimals,
+    )?;
+
+    ctx.accounts.token_0_vault.reload()?;
+    ctx.accounts.token_1_vault.reload()?;
+    let principal_token_0 = ctx
+        .accounts
+        .token_0_vault
+        .amount
+        .checked_sub(token_0_vault_before)
+        .ok_or(ErrorCode::UnderflowError)?;
+    let principal_token_1 = ctx
+        .accounts
+        .token_1_vault
+        .amount
+        .checked_sub(token_1_vault_before)
+        .ok_or(ErrorCode::UnderflowError)?;
+    require!(
+        principal_token_0 > 0 && principal_token_1 > 0,
+        ErrorCode::ZeroTradingTokens
+    );
+
+    user_lock.bump = ctx.bumps.user_lp_lock;
+    user_lock.user = ctx.accounts.owner.key();
+    user_lock.creator = ctx.accounts.owner.key();
+    user_lock.version = UserLock::VERSION;
+    user_lock.lp_mint = ctx.accounts.lp_mint.key();
+    user_lock.lock_count = new_lock_count;
+    user_lock.lock_amount = lp_token_amount;
+    user_lock.is_locked_permanently = lock_permanent;
+    user_lock.rent_payer = ctx.accounts.payer.key();
+    user_lock.label = metadata.label;
+    user_lock.uri = metadata.uri;
+    user_lock.is_creator_lock = user_lock.user == pool_state.pool_creator;
+
+    ctx.accounts
+        .active_lock_page
+        .activate(ctx.bumps.active_lock_page, user_lock)?;
+
+    assign_lock_id(
+        &mut ctx.accounts.global_lock_counter,
+        ctx.bumps.global_lock_counter,
+        &mut ctx.accounts.lock_id_index,
+        ctx.bumps.lock_id_index,
+        user_lock,
+    )?;
+
+    open_lock(
+        user_lock,
+        lp_lock_counter,
+        pool_lock_stats,
+        ctx.accounts
+            .reward_pool
+            .as_deref_mut()
+            .map(|reward_pool| (reward_pool.key(), &mut **reward_pool)),
+        principal_token_0,
+        principal_token_1,
+        lock_duration,
+    )?;
+
+    emit!(lp_lock_event(user_lock));
+    emit!(DepositAndLockEvent {
+        user: user_lock.user,
+        lp_mint: user_lock.lp_mint,
+        lp_amount: lp_token_amount,
+        token_0_amount: principal_token_0,
+        token_1_amount: principal_token_1,
+    });
+
+    Ok(CreatedLock {
+        user_lock: user_lock.key(),
+        lock_count: user_lock.lock_count,
+        lock_id: user_lock.lock_id,
+    })
+}
@@ -0,0 +1,38 @@
+use crate::error::ErrorCode;
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct GetCreatorLockShare<'info> {
+    /// CHECK: Raydium pool state account
+    #[account(
+        owner = crate::raydium_cpmm::id()
+    )]
+    pub pool_state: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [
+            POOL_LOCK_STATS_SEED.as_bytes(),
+            pool_lock_stats.lp_mint.as_ref()
+        ],
+        bump = pool_lock_stats.bump,
+    )]
+    pub pool_lock_stats: Box<Account<'info, PoolLockStats>>,
+}
+
+/// LP locked by the pool creator and its share of the pool's `lp_supply`, set as return data.
+pub fn get_creator_lock_share(ctx: Context<GetCreatorLockShare>) -> Result<CreatorLockShare> {
+    let pool_state_info = &ctx.accounts.pool_state;
+    let pool_state = PoolState::try_deserialize(&mut &pool_state_info.data.borrow()[..])?;
+    require_eq!(
+        pool_state.lp_mint,
+        ctx.accounts.pool_lock_stats.lp_mint,
+        ErrorCode::IncorrectLpMint
+    );
+
+    Ok(CreatorLockShare::new(
+        pool_state.pool_creator,
+        ctx.accounts.pool_lock_stats.creator_locked_amount,
+        pool_state.lp_supply,
+    ))
+}
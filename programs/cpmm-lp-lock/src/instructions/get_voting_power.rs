@@ -0,0 +1,67 @@
+use crate::error::ErrorCode;
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(user: Pubkey, lp_mint: Pubkey)]
+pub struct GetVotingPower<'info> {
+    #[account(
+        seeds = [
+            LP_LOCK_COUNTER_SEED.as_bytes(),
+            user.as_ref(),
+            lp_mint.as_ref()
+        ],
+        bump,
+    )]
+    pub lp_lock_counter: Box<Account<'info, LpLockCounter>>,
+}
+
+/// Voting power of `user`'s locks of `lp_mint` at `timestamp`, set as return data.
+/// No history is kept: `timestamp` must not precede the last change to the user's locks,
+/// so governance should read the power at the time it snapshots rather than after the fact.
+/// Every active timed lock of the user must be passed in `remaining_accounts`, so that the
+/// result does not depend on which expired locks the caller chose to pass.
+pub fn get_voting_power(
+    ctx: Context<GetVotingPower>,
+    user: Pubkey,
+    lp_mint: Pubkey,
+    timestamp: u64,
+) -> Result<u64> {
+    let voting_power = &ctx.accounts.lp_lock_counter.voting_power;
+    require_eq!(
+        ctx.remaining_accounts.len() as u64,
+        voting_power.decaying_count,
+        ErrorCode::IncompleteVotingPowerLocks
+    );
+
+    let mut timed_locks: Vec<Pubkey> = Vec::with_capacity(ctx.remaining_accounts.len());
+    let mut expired_correction: u128 = 0;
+
+    for user_lock_info in ctx.remaining_accounts.iter() {
+        require!(
+            user_lock_info.owner == &crate::id() && !timed_locks.contains(user_lock_info.key),
+            ErrorCode::InvalidVotingPowerLock
+        );
+        timed_locks.push(user_lock_info.key());
+
+        let user_lock = UserLock::try_deserialize(&mut &user_lock_info.data.borrow()[..])?;
+        require!(
+            user_lock.user == user
+                && user_lock.lp_mint == lp_mint
+                && !user_lock.is_unlocked
+                && !user_lock.is_locked_permanently
+                && !user_lock.is_rolling,
+            ErrorCode::InvalidVotingPowerLock
+        );
+        if user_lock.unlock_time < timestamp {
+            expired_correction = expired_correction
+                .checked_add(
+                    u128::from(user_lock.lock_amount)
+                        * u128::from(timestamp - user_lock.unlock_time),
+                )
+                .ok_or(ErrorCode::Overflow)?;
+        }
+    }
+
+    voting_power.voting_power_at(timestamp, expired_correction)
+}
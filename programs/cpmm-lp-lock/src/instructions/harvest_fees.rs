@@ -0,0 +1,276 @@
+use crate::error::ErrorCode;
+use crate::instructions::collect_fees::settle_fees;
+use crate::states::*;
+use crate::utils::block_timestamp;
+use crate::utils::create_token_account;
+use crate::utils::raydium;
+use crate::{LOCK_FEE_VAULT_SEED, LP_LOCK_VAULT_SEED};
+use anchor_lang::prelude::*;
+use anchor_spl::token::Token;
+use anchor_spl::token_2022::Token2022;
+use anchor_spl::memo::spl_memo;
+use anchor_spl::token_interface::{Mint, TokenAccount};
+
+#[derive(Accounts)]
+pub struct HarvestFees<'info> {
+    /// Anyone cranking the harvest; pays rent for the lock's fee vaults if they are not open,
+    /// and gets it back when `claim_harvested` closes them
+    #[account(mut)]
+    pub harvester: Signer<'info>,
+
+    /// CHECK: owner of the lock
+    #[account(
+        address = user_lp_lock.user
+    )]
+    pub owner: UncheckedAccount<'info>,
+
+    /// CHECK: pool vault and lp mint authority
+    #[account(
+        address = crate::vault_and_lp_mint_auth::id()
+    )]
+    pub authority: UncheckedAccount<'info>,
+
+    /// CHECK: lock vault authority
+    #[account(
+        seeds = [
+            crate::AUTH_SEED.as_bytes(),
+        ],
+     bump,
+    )]
+    pub lock_vault_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Raydium pool state account
+    #[account(
+        mut,
+        owner = crate::raydium_cpmm::id()
+    )]
+    pub pool_state: UncheckedAccount<'info>,
+
+    /// Lp token mint
+    #[account(
+        mut,
+        mint::authority = authority,
+    )]
+    pub lp_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [
+            LP_LOCK_COUNTER_SEED.as_bytes(),
+            owner.key().as_ref(),
+            lp_mint.key().as_ref()
+        ],
+        bump,
+    )]
+    pub lp_lock_counter: Box<Account<'info, LpLockCounter>>,
+
+    #[account(
+        mut,
+        seeds = [
+            POOL_LOCK_STATS_SEED.as_bytes(),
+            lp_mint.key().as_ref()
+        ],
+        bump = pool_lock_stats.bump,
+    )]
+    pub pool_lock_stats: Box<Account<'info, PoolLockStats>>,
+
+    #[account(
+        mut,
+        constraint = user_lp_lock.lp_mint == lp_mint.key(),
+    )]
+    pub user_lp_lock: Box<Account<'info, UserLock>>,
+
+    /// CHECK The vault that holds the locked LP tokens
+    #[account(
+        mut,
+        token::mint = lp_mint,
+        token::authority = lock_vault_authority,
+        seeds = [
+            LP_LOCK_VAULT_SEED.as_bytes(),
+            user_lp_lock.pda_owner().as_ref(),
+            lp_mint.key().as_ref(),
+            user_lp_lock.lock_count.to_le_bytes().as_ref()
+        ],
+        bump,
+    )]
+    pub lp_lock_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: The lock's vault holding harvested token_0 fees, created by the harvest that finds it closed
+    #[account(
+        mut,
+        seeds = [
+            LOCK_FEE_VAULT_SEED.as_bytes(),
+            user_lp_lock.key().as_ref(),
+            vault_0_mint.key().as_ref()
+        ],
+        bump,
+    )]
+    pub lock_fee_vault_0: UncheckedAccount<'info>,
+
+    /// CHECK: The lock's vault holding harvested token_1 fees, created by the harvest that finds it closed
+    #[account(
+        mut,
+        seeds = [
+            LOCK_FEE_VAULT_SEED.as_bytes(),
+            user_lp_lock.key().as_ref(),
+            vault_1_mint.key().as_ref()
+        ],
+        bump,
+    )]
+    pub lock_fee_vault_1: UncheckedAccount<'info>,
+
+    /// The address that holds pool tokens for token_0
+    #[account(mut)]
+    pub token_0_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The address that holds pool tokens for token_1
+    #[account(mut)]
+    pub token_1_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// token Program
+    pub token_program: Program<'info, Token>,
+
+    /// Token program 2022
+    pub token_program_2022: Program<'info, Token2022>,
+
+    /// The mint of token_0 vault
+    #[account(
+        address = token_0_vault.mint
+    )]
+    pub vault_0_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The mint of token_1 vault
+    #[account(
+        address = token_1_vault.mint
+    )]
+    pub vault_1_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// memo program
+    /// CHECK:
+    #[account(
+        address = spl_memo::id()
+    )]
+    pub memo_program: UncheckedAccount<'info>,
+
+    /// CHECK: This account is owned by another program
+    #[account(
+        address = crate::raydium_cpmm::id()
+    )]
+    pub raydium_cpmm_program: AccountInfo<'info>,
+
+    /// Reward pool the lock is enrolled in, required if it has one
+    #[account(
+        mut,
+        address = user_lp_lock.reward_pool @ ErrorCode::IncorrectRewardPool,
+    )]
+    pub reward_pool: Option<Box<Account<'info, RewardPool>>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Burn the fee LP of a lock into its program-owned fee vaults. Anyone can call this;
+/// only the owner can take the fees out with `claim_harvested`.
+pub fn harvest_fees(ctx: Context<HarvestFees>) -> Result<()> {
+    let accounts = ctx.accounts;
+
+    // Harvested fees are claimed by the owner alone, which would bypass the lock's fee split
+    require_eq!(
+        accounts.user_lp_lock.has_fee_split,
+        false,
+        ErrorCode::LockHasFeeSplit
+    );
+
+    let pool_state_info = &accounts.pool_state;
+    let pool_state = PoolState::try_deserialize(&mut &pool_state_info.data.borrow()[..])?;
+    require_eq!(pool_state.lp_mint, accounts.lp_mint.key(), ErrorCode::IncorrectLpMint);
+    require_keys_eq!(
+        pool_state.token_0_vault,
+        accounts.token_0_vault.key(),
+        ErrorCode::IncorrectPoolAccount
+    );
+    require_keys_eq!(
+        pool_state.token_1_vault,
+        accounts.token_1_vault.key(),
+        ErrorCode::IncorrectPoolAccount
+    );
+    pool_state.require_enabled(PoolStatusBitIndex::Withdraw)?;
+
+    let (lp_tokens_to_burn, token_0_amount, token_1_amount) = settle_fees(
+        &mut accounts.user_lp_lock,
+        &mut accounts.lp_lock_counter,
+        &mut accounts.pool_lock_stats,
+        accounts.reward_pool.as_deref_mut().map(|r| &mut **r),
+        &pool_state,
+        [&accounts.token_0_vault, &accounts.token_1_vault],
+        [&accounts.vault_0_mint, &accounts.vault_1_mint],
+        block_timestamp()?,
+    )?;
+
+    accounts
+        .user_lp_lock
+        .record_fees_collected(token_0_amount, token_1_amount)?;
+    accounts.user_lp_lock.harvest_pending = true;
+
+    let user_lock_key = accounts.user_lp_lock.key();
+    for (lock_fee_vault, mint, bump) in [
+        (&accounts.lock_fee_vault_0, &accounts.vault_0_mint, ctx.bumps.lock_fee_vault_0),
+        (&accounts.lock_fee_vault_1, &accounts.vault_1_mint, ctx.bumps.lock_fee_vault_1),
+    ] {
+        if !lock_fee_vault.data_is_empty() {
+            continue;
+        }
+        let token_program = if *mint.to_account_info().owner == Token2022::id() {
+            accounts.token_program_2022.to_account_info()
+        } else {
+            accounts.token_program.to_account_info()
+        };
+        create_token_account(
+            &accounts.lock_vault_authority.to_account_info(),
+            &accounts.harvester.to_account_info(),
+            &lock_fee_vault.to_account_info(),
+            &mint.to_account_info(),
+            &accounts.system_program.to_account_info(),
+            &token_program,
+            &[&[
+                LOCK_FEE_VAULT_SEED.as_bytes(),
+                user_lock_key.as_ref(),
+                mint.key().as_ref(),
+                &[bump][..],
+            ][..]],
+        )?;
+        accounts.user_lp_lock.harvester = accounts.harvester.key();
+    }
+
+    raydium::withdraw(
+        raydium::WithdrawAccounts {
+            owner: accounts.lock_vault_authority.to_account_info(),
+            authority: accounts.authority.to_account_info(),
+            pool_state: accounts.pool_state.to_account_info(),
+            owner_lp_token: accounts.lp_lock_vault.to_account_info(),
+            token_0_account: accounts.lock_fee_vault_0.to_account_info(),
+            token_1_account: accounts.lock_fee_vault_1.to_account_info(),
+            token_0_vault: accounts.token_0_vault.to_account_info(),
+            token_1_vault: accounts.token_1_vault.to_account_info(),
+            token_program: accounts.token_program.to_account_info(),
+            token_program_2022: accounts.token_program_2022.to_account_info(),
+            vault_0_mint: accounts.vault_0_mint.to_account_info(),
+            vault_1_mint: accounts.vault_1_mint.to_account_info(),
+            lp_mint: accounts.lp_mint.to_account_info(),
+            memo_program: accounts.memo_program.to_account_info(),
+        },
+        lp_tokens_to_burn,
+        token_0_amount,
+        token_1_amount,
+        &[&[crate::AUTH_SEED.as_bytes(), &[ctx.bumps.lock_vault_authority]]],
+    )?;
+
+    emit!(HarvestFeesEvent {
+        lock_id: accounts.user_lp_lock.lock_id,
+        harvester: accounts.harvester.key(),
+        lp_mint: accounts.lp_mint.key(),
+        token_0_amount,
+        token_1_amount,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,170 @@
+use crate::error::ErrorCode;
+use crate::states::*;
+use crate::utils::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+#[derive(Accounts)]
+#[instruction(campaign: u64)]
+pub struct InitializeRewardPool<'info> {
+    /// Pool creator funding the rewards
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// CHECK: lock vault authority
+    #[account(
+        seeds = [
+            crate::AUTH_SEED.as_bytes(),
+        ],
+     bump,
+    )]
+    pub lock_vault_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Raydium pool state account
+    #[account(
+        owner = crate::raydium_cpmm::id()
+    )]
+    pub pool_state: UncheckedAccount<'info>,
+
+    /// Lp token mint
+    pub lp_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        init,
+        seeds = [
+            REWARD_POOL_SEED.as_bytes(),
+            lp_mint.key().as_ref(),
+            campaign.to_le_bytes().as_ref()
+        ],
+        bump,
+        payer = authority,
+        space = RewardPool::LEN,
+    )]
+    pub reward_pool: Box<Account<'info, RewardPool>>,
+
+    /// Reward token mint
+    #[account(
+        mint::token_program = reward_token_program,
+    )]
+    pub reward_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// CHECK The vault that holds the undistributed rewards
+    #[account(
+        mut,
+        seeds = [
+            REWARD_VAULT_SEED.as_bytes(),
+            reward_pool.key().as_ref()
+        ],
+        bump,
+    )]
+    pub reward_vault: UncheckedAccount<'info>,
+
+    /// The authority's token account the rewards are funded from
+    #[account(
+        mut,
+        token::mint = reward_mint,
+        token::authority = authority,
+        token::token_program = reward_token_program,
+    )]
+    pub authority_reward_token: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Reward token program
+    pub reward_token_program: Interface<'info, TokenInterface>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn initialize_reward_pool(
+    ctx: Context<InitializeRewardPool>,
+    campaign: u64,
+    emission_rate: u64,
+    end_time: u64,
+) -> Result<()> {
+    let pool_state_info = &ctx.accounts.pool_state;
+    let pool_state = PoolState::try_deserialize(&mut &pool_state_info.data.borrow()[..])?;
+    require_eq!(
+        pool_state.lp_mint,
+        ctx.accounts.lp_mint.key(),
+        ErrorCode::IncorrectLpMint
+    );
+    require_keys_eq!(
+        pool_state.pool_creator,
+        ctx.accounts.authority.key(),
+        ErrorCode::NotPoolCreator
+    );
+    require!(
+        is_supported_mint(&ctx.accounts.reward_mint)?,
+        ErrorCode::NotSupportMint
+    );
+    // A frozen reward vault would stop every claim
+    require!(
+        ctx.accounts.reward_mint.freeze_authority.is_none(),
+        ErrorCode::RewardMintFreezable
+    );
+
+    let block_timestamp = block_timestamp()?;
+    require!(
+        emission_rate > 0 && end_time > block_timestamp,
+        ErrorCode::InvalidRewardSchedule
+    );
+    let total_rewards = emission_rate
+        .checked_mul(end_time - block_timestamp)
+        .ok_or(ErrorCode::Overflow)?;
+
+    create_token_account(
+        &ctx.accounts.lock_vault_authority.to_account_info(),
+        &ctx.accounts.authority.to_account_info(),
+        &ctx.accounts.reward_vault.to_account_info(),
+        &ctx.accounts.reward_mint.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        &ctx.accounts.reward_token_program.to_account_info(),
+        &[&[
+            REWARD_VAULT_SEED.as_bytes(),
+            ctx.accounts.reward_pool.key().as_ref(),
+            &[ctx.bumps.reward_vault][..],
+        ][..]],
+    )?;
+
+    // Fund the vault with the full emission schedule, grossed up by any transfer fee
+    let transfer_fee =
+        get_transfer_inverse_fee(&ctx.accounts.reward_mint.to_account_info(), total_rewards)?;
+    transfer_from_user_to_pool_vault(
+        ctx.accounts.authority.to_account_info(),
+        ctx.accounts.authority_reward_token.to_account_info(),
+        ctx.accounts.reward_vault.to_account_info(),
+        ctx.accounts.reward_mint.to_account_info(),
+        ctx.accounts.reward_token_program.to_account_info(),
+        total_rewards
+            .checked_add(transfer_fee)
+            .ok_or(ErrorCode::Overflow)?,
+        ctx.accounts.reward_mint.decimals,
+    )?;
+
+    let reward_pool = &mut ctx.accounts.reward_pool;
+    reward_pool.bump = ctx.bumps.reward_pool;
+    reward_pool.lp_mint = ctx.accounts.lp_mint.key();
+    reward_pool.campaign = campaign;
+    reward_pool.reward_mint = ctx.accounts.reward_mint.key();
+    reward_pool.reward_vault = ctx.accounts.reward_vault.key();
+    reward_pool.authority = ctx.accounts.authority.key();
+    reward_pool.emission_rate = emission_rate;
+    reward_pool.start_time = block_timestamp;
+    reward_pool.end_time = end_time;
+    reward_pool.last_update_time = block_timestamp;
+    reward_pool.reward_per_weight_stored = 0;
+    reward_pool.total_weight = 0;
+    reward_pool.reward_liability = 0;
+
+    emit!(RewardPoolInitializedEvent {
+        lp_mint: reward_pool.lp_mint,
+        campaign,
+        reward_mint: reward_pool.reward_mint,
+        authority: reward_pool.authority,
+        emission_rate,
+        start_time: block_timestamp,
+        end_time,
+        total_rewards,
+    });
+
+    Ok(())
+}
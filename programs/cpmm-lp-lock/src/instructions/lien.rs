@@ -0,0 +1,195 @@
+use crate::error::ErrorCode;
+use crate::states::*;
+use crate::utils::block_timestamp;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct GrantLien<'info> {
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = user_lp_lock.user == owner.key(),
+    )]
+    pub user_lp_lock: Box<Account<'info, UserLock>>,
+}
+
+#[derive(Accounts)]
+pub struct ReleaseLien<'info> {
+    /// Lienholder, usually a PDA of the lending program signing through CPI
+    pub lienholder: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = user_lp_lock.lienholder == lienholder.key() @ ErrorCode::InvalidLienholder,
+    )]
+    pub user_lp_lock: Box<Account<'info, UserLock>>,
+}
+
+#[derive(Accounts)]
+pub struct Foreclose<'info> {
+    /// Lienholder, usually a PDA of the lending program signing through CPI
+    pub lienholder: Signer<'info>,
+
+    /// Pays rent for the new owner's lock counter and active-lock page if they do not exist yet
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = user_lp_lock.lienholder == lienholder.key() @ ErrorCode::InvalidLienholder,
+    )]
+    pub user_lp_lock: Box<Account<'info, UserLock>>,
+
+    /// Lock counter of the current owner
+    #[account(
+        mut,
+        seeds = [
+            LP_LOCK_COUNTER_SEED.as_bytes(),
+            user_lp_lock.user.as_ref(),
+            user_lp_lock.lp_mint.as_ref()
+        ],
+        bump,
+    )]
+    pub lp_lock_counter: Box<Account<'info, LpLockCounter>>,
+
+    #[account(
+        mut,
+        seeds = [
+            POOL_LOCK_STATS_SEED.as_bytes(),
+            user_lp_lock.lp_mint.as_ref()
+        ],
+        bump = pool_lock_stats.bump,
+    )]
+    pub pool_lock_stats: Box<Account<'info, PoolLockStats>>,
+
+    /// CHECK: wallet or PDA the lock is handed to, chosen by the lienholder.
+    /// Handing the lock to its current owner would alias the two lock counters.
+    #[account(
+        constraint = new_owner.key() != user_lp_lock.user @ ErrorCode::InvalidNewOwner,
+    )]
+    pub new_owner: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        seeds = [
+            LP_LOCK_COUNTER_SEED.as_bytes(),
+            new_owner.key().as_ref(),
+            user_lp_lock.lp_mint.as_ref()
+        ],
+        bump,
+        payer = payer,
+        space = LpLockCounter::LEN,
+    )]
+    pub new_lp_lock_counter: Box<Account<'info, LpLockCounter>>,
+
+    /// Active-lock index page of the current owner tracking the lock
+    #[account(
+        mut,
+        seeds = [
+            ACTIVE_LOCK_PAGE_SEED.as_bytes(),
+            user_lp_lock.user.as_ref(),
+            user_lp_lock.lp_mint.as_ref(),
+            ActiveLockPage::page_of(user_lp_lock.lock_count).to_le_bytes().as_ref(),
+            user_lp_lock.active_lock_page_seed(&user_lp_lock.user)
+        ],
+        bump = active_lock_page.bump,
+    )]
+    pub active_lock_page: Box<Account<'info, ActiveLockPage>>,
+
+    /// Active-lock index page of the new owner the lock moves to
+    #[account(
+        init_if_needed,
+        seeds = [
+            ACTIVE_LOCK_PAGE_SEED.as_bytes(),
+            new_owner.key().as_ref(),
+            user_lp_lock.lp_mint.as_ref(),
+            ActiveLockPage::page_of(user_lp_lock.lock_count).to_le_bytes().as_ref(),
+            user_lp_lock.active_lock_page_seed(&new_owner.key())
+        ],
+        bump,
+        payer = payer,
+        space = ActiveLockPage::LEN,
+    )]
+    pub new_active_lock_page: Box<Account<'info, ActiveLockPage>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Encumber the lock with a lien held by `lienholder`. Until the lienholder releases
+/// it, the lock cannot be unlocked, and the lienholder may foreclose on it.
+pub fn grant_lien(ctx: Context<GrantLien>, lienholder: Pubkey) -> Result<()> {
+    let user_lock = &mut ctx.accounts.user_lp_lock;
+    require_eq!(
+        user_lock.is_unlocked,
+        false,
+        ErrorCode::LockAlreadyUnlocked
+    );
+    user_lock.check_no_lien()?;
+    user_lock.check_not_listed()?;
+    // A foreclosing lienholder would otherwise see fees and principal paid to the owner's picks
+    user_lock.check_transferable()?;
+    require_keys_neq!(lienholder, Pubkey::default(), ErrorCode::InvalidLienholder);
+
+    user_lock.lienholder = lienholder;
+    user_lock.last_updated = block_timestamp()?;
+
+    emit!(LienGrantedEvent {
+        lock_id: user_lock.lock_id,
+        user_lock: user_lock.key(),
+        user: user_lock.user,
+        lienholder,
+    });
+
+    Ok(())
+}
+
+/// Lift the lien, e.g. once the loan it secures is repaid.
+pub fn release_lien(ctx: Context<ReleaseLien>) -> Result<()> {
+    let user_lock = &mut ctx.accounts.user_lp_lock;
+    user_lock.lienholder = Pubkey::default();
+    user_lock.last_updated = block_timestamp()?;
+
+    emit!(LienReleasedEvent {
+        lock_id: user_lock.lock_id,
+        user_lock: user_lock.key(),
+        lienholder: ctx.accounts.lienholder.key(),
+    });
+
+    Ok(())
+}
+
+/// Seize the lock on default: ownership, with the lock's amount and voting power,
+/// moves to `new_owner` and the lien is lifted. The LP itself stays locked.
+pub fn foreclose(ctx: Context<Foreclose>) -> Result<()> {
+    let user_lock = &mut ctx.accounts.user_lp_lock;
+    let new_lp_lock_counter = &mut ctx.accounts.new_lp_lock_counter;
+    new_lp_lock_counter.initialize_if_needed(ctx.accounts.new_owner.key(), user_lock.lp_mint);
+
+    let block_timestamp = block_timestamp()?;
+    let previous_owner = user_lock.user;
+    ctx.accounts.lp_lock_counter.transfer_lock(
+        new_lp_lock_counter,
+        &mut ctx.accounts.pool_lock_stats,
+        user_lock,
+        block_timestamp,
+    )?;
+    ctx.accounts.active_lock_page.move_lock(
+        &mut ctx.accounts.new_active_lock_page,
+        ctx.bumps.new_active_lock_page,
+        user_lock,
+    )?;
+
+    user_lock.lienholder = Pubkey::default();
+    user_lock.last_updated = block_timestamp;
+
+    emit!(LockForeclosedEvent {
+        lock_id: user_lock.lock_id,
+        user_lock: user_lock.key(),
+        previous_owner,
+        new_owner: user_lock.user,
+        lienholder: ctx.accounts.lienholder.key(),
+    });
+
+    Ok(())
+}
@@ -9,13 +9,15 @@ use crate::LP_LOCK_VAULT_SEED;
 use anchor_lang::prelude::*;
 use anchor_spl::token::Token;
 use anchor_spl::token_interface::{Mint, TokenAccount};
-use anchor_lang::{solana_program::clock};
 
 #[derive(Accounts)]
 pub struct LockLp<'info> {
-    #[account(mut)]
     pub owner: Signer<'info>,
 
+    /// Pays rent for the accounts created by the lock, may be the owner
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
     /// CHECK: pool vault and lp mint authority
     #[account(
         address = crate::vault_and_lp_mint_auth::id()
@@ -63,7 +65,7 @@ pub struct LockLp<'info> {
             lp_mint.key().as_ref()
         ],
         bump,
-        payer = owner,
+        payer = payer,
         space = LpLockCounter::LEN,
     )]
     pub lp_lock_counter: Box<Account<'info, LpLockCounter>>,
@@ -77,11 +79,64 @@ pub struct LockLp<'info> {
             lp_lock_counter.total_lock_count.add(1).to_le_bytes().as_ref()
         ],
         bump,
-        payer = owner,
+        payer = payer,
         space = UserLock::LEN,
     )]
     pub user_lp_lock: Box<Account<'info, UserLock>>,
 
+    #[account(
+        init_if_needed,
+        seeds = [
+            POOL_LOCK_STATS_SEED.as_bytes(),
+            lp_mint.key().as_ref()
+        ],
+        bump,
+        payer = payer,
+        space = PoolLockStats::LEN,
+    )]
+    pub pool_lock_stats: Box<Account<'info, PoolLockStats>>,
+
+    /// Assigns the new lock its program-wide ID. Every lock creation writes this one account,
+    /// so lock creations across all pools are serialized on its write lock.
+    #[account(
+        init_if_needed,
+        seeds = [
+            GLOBAL_LOCK_COUNTER_SEED.as_bytes(),
+        ],
+        bump,
+        payer = payer,
+        space = GlobalLockCounter::LEN,
+    )]
+    pub global_lock_counter: Box<Account<'info, GlobalLockCounter>>,
+
+    /// Maps the new lock's program-wide ID to its address
+    #[account(
+        init,
+        seeds = [
+            LOCK_ID_SEED.as_bytes(),
+            global_lock_counter.total_lock_count.add(1).to_le_bytes().as_ref()
+        ],
+        bump,
+        payer = payer,
+        space = LockIdIndex::LEN,
+    )]
+    pub lock_id_index: Box<Account<'info, LockIdIndex>>,
+
+    /// Active-lock index page tracking the new lock
+    #[account(
+        init_if_needed,
+        seeds = [
+            ACTIVE_LOCK_PAGE_SEED.as_bytes(),
+            owner.key().as_ref(),
+            lp_mint.key().as_ref(),
+            ActiveLockPage::page_of(lp_lock_counter.total_lock_count.add(1)).to_le_bytes().as_ref()
+        ],
+        bump,
+        payer = payer,
+        space = ActiveLockPage::LEN,
+    )]
+    pub active_lock_page: Box<Account<'info, ActiveLockPage>>,
+
     /// CHECK The vault that holds the locked LP tokens
     #[account(
         mut,
@@ -101,20 +156,102 @@ pub struct LockLp<'info> {
     /// The address that holds pool tokens for token_1
     pub token_1_vault: Box<InterfaceAccount<'info, TokenAccount>>,
 
+    /// The mint of token_0 vault
+    #[account(
+        address = token_0_vault.mint
+    )]
+    pub vault_0_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The mint of token_1 vault
+    #[account(
+        address = token_1_vault.mint
+    )]
+    pub vault_1_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// CHECK: pool registry settings, uninitialized until the admin configures the registry
+    #[account(
+        seeds = [
+            REGISTRY_CONFIG_SEED.as_bytes(),
+        ],
+        bump,
+    )]
+    pub registry_config: UncheckedAccount<'info>,
+
+    /// CHECK: registry entry of the pool, uninitialized when the pool has no verdict
+    #[account(
+        seeds = [
+            REGISTRY_ENTRY_SEED.as_bytes(),
+            pool_state.key().as_ref()
+        ],
+        bump,
+    )]
+    pub pool_registry_entry: UncheckedAccount<'info>,
+
+    /// CHECK: registry entry of the token_0 mint, uninitialized when the mint has no verdict
+    #[account(
+        seeds = [
+            REGISTRY_ENTRY_SEED.as_bytes(),
+            vault_0_mint.key().as_ref()
+        ],
+        bump,
+    )]
+    pub vault_0_mint_registry_entry: UncheckedAccount<'info>,
+
+    /// CHECK: registry entry of the token_1 mint, uninitialized when the mint has no verdict
+    #[account(
+        seeds = [
+            REGISTRY_ENTRY_SEED.as_bytes(),
+            vault_1_mint.key().as_ref()
+        ],
+        bump,
+    )]
+    pub vault_1_mint_registry_entry: UncheckedAccount<'info>,
+
+    /// Reward pool of the LP mint to enroll the new lock in, if any
+    #[account(
+        mut,
+        seeds = [
+            REWARD_POOL_SEED.as_bytes(),
+            lp_mint.key().as_ref(),
+            reward_pool.campaign.to_le_bytes().as_ref()
+        ],
+        bump = reward_pool.bump,
+    )]
+    pub reward_pool: Option<Box<Account<'info, RewardPool>>>,
+
     pub system_program: Program<'info, System>,
 }
 
+/// How the duration of a lock created by `lock_lp` runs
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LockMode {
+    /// Unlockable once the duration has passed from creation
+    Timed,
+    /// Unlockable once the duration has passed from the pool open time, or creation if later
+    FromOpenTime,
+    /// Renews continually; unlockable a full duration after `stop_rolling`
+    Rolling,
+    /// Never unlockable
+    Permanent,
+}
+
+/// Lock `amount` of the owner's LP. The owner may be a PDA signing through CPI, with
+/// rent paid by a separate `payer`; the new lock is returned as `CreatedLock`.
 pub fn lock_lp(
     ctx: Context<LockLp>,
     amount: u64,
     lock_duration: u64,
-    lock_permanent: bool,
-) -> Result<()> {
+    lock_mode: LockMode,
+    metadata: LockMetadata,
+    beneficiaries: Vec<FeeSplitRecipient>,
+) -> Result<CreatedLock> {
     require!(amount > 100,ErrorCode::InitLpAmountTooLess);
+    metadata.validate()?;
+    validate_beneficiaries(&beneficiaries)?;
 
-    if !lock_permanent {
+    if lock_mode != LockMode::Permanent {
         require!(
-            lock_duration < 15_76_80_000,
+            lock_duration < MAX_LOCK_DURATION,
             ErrorCode::LockDurationTooLong
         );
     }
@@ -123,11 +260,12 @@ pub fn lock_lp(
     let user_lock = &mut ctx.accounts.user_lp_lock;
 
     // Check if lp lock counter is initialized in the same transaction
-    if lp_lock_counter.user == Pubkey::default() {
-        lp_lock_counter.user = ctx.accounts.owner.key();
-        lp_lock_counter.lp_mint = ctx.accounts.lp_mint.key();
-        lp_lock_counter.total_lock_count = 0;
-        lp_lock_counter.total_lock_amount = 0;
+    lp_lock_counter.initialize_if_needed(ctx.accounts.owner.key(), ctx.accounts.lp_mint.key());
+
+    let pool_lock_stats = &mut ctx.accounts.pool_lock_stats;
+    if pool_lock_stats.lp_mint == Pubkey::default() {
+        pool_lock_stats.bump = ctx.bumps.pool_lock_stats;
+        pool_lock_stats.lp_mint = ctx.accounts.lp_mint.key();
     }
 
     let new_lock_count = lp_lock_counter.total_lock_count
@@ -136,7 +274,7 @@ pub fn lock_lp(
 
     create_token_account(
         &ctx.accounts.lock_vault_authority.to_account_info(),
-        &ctx.accounts.owner.to_account_info(),
+        &ctx.accounts.payer.to_account_info(),
         &ctx.accounts.lp_lock_vault.to_account_info(),
         &ctx.accounts.lp_mint.to_account_info(),
         &ctx.accounts.system_program.to_account_info(),
@@ -162,36 +300,38 @@ pub fn lock_lp(
         ctx.accounts.lp_mint.decimals,
     )?;
 
-    let block_timestamp: u64 = match clock::Clock::get() {
-        Ok(clock) => match clock.unix_timestamp.try_into() {
-            Ok(timestamp) => timestamp,
-            Err(_) => {
-                return Err(error!(ErrorCode::InvalidTimestamp));
-            }
-        },
-        Err(_) => {
-            return Err(error!(ErrorCode::ClockUnavailable));
-        }
-    };
-
-    let unlock_time = if lock_permanent {
-        0
-    } else {
-        block_timestamp
-            .checked_add(lock_duration)
-            .ok_or(ErrorCode::Overflow)?
-    };
-
     let pool_state_info = &ctx.accounts.pool_state;
     let pool_state = PoolState::try_deserialize(&mut &pool_state_info.data.borrow()[..])?;
     require_eq!(pool_state.lp_mint,ctx.accounts.lp_mint.key(), ErrorCode::IncorrectLpMint);
-    require_eq!(pool_state.token_0_vault,ctx.accounts.token_0_vault.key());
-    require_eq!(pool_state.token_1_vault,ctx.accounts.token_1_vault.key());
+    require_keys_eq!(
+        pool_state.token_0_vault,
+        ctx.accounts.token_0_vault.key(),
+        ErrorCode::IncorrectPoolAccount
+    );
+    require_keys_eq!(
+        pool_state.token_1_vault,
+        ctx.accounts.token_1_vault.key(),
+        ErrorCode::IncorrectPoolAccount
+    );
+    if lock_mode == LockMode::FromOpenTime {
+        // The lock would never start running in a pool that cannot trade
+        pool_state.require_enabled(PoolStatusBitIndex::Swap)?;
+    }
+
+    check_pool_registry(
+        &ctx.accounts.registry_config,
+        &ctx.accounts.pool_registry_entry,
+        [&ctx.accounts.vault_0_mint, &ctx.accounts.vault_1_mint],
+        [
+            &ctx.accounts.vault_0_mint_registry_entry,
+            &ctx.accounts.vault_1_mint_registry_entry,
+        ],
+    )?;
 
     let (total_token_0_amount, total_token_1_amount) = pool_state.vault_amount_without_fee(
         ctx.accounts.token_0_vault.amount,
         ctx.accounts.token_1_vault.amount,
-    );
+    )?;
 
     let results = CurveCalculator::lp_tokens_to_trading_tokens(
         u128::from(amount),
@@ -202,46 +342,179 @@ pub fn lock_lp(
     )
     .ok_or(ErrorCode::ZeroTradingTokens)?;
 
-    require_gt!(results.token_0_amount, 0);
-    require_gt!(results.token_1_amount, 0);
-
-    let liquidity = U128::from(results.token_0_amount)
-        .checked_mul(results.token_1_amount.into())
-        .unwrap()
-        .integer_sqrt()
-        .as_u64();
+    let principal_token_0 =
+        u64::try_from(results.token_0_amount).map_err(|_| error!(ErrorCode::Overflow))?;
+    let principal_token_1 =
+        u64::try_from(results.token_1_amount).map_err(|_| error!(ErrorCode::Overflow))?;
+    require!(
+        principal_token_0 > 0 && principal_token_1 > 0,
+        ErrorCode::ZeroTradingTokens
+    );
 
     user_lock.bump = ctx.bumps.user_lp_lock;
     user_lock.user = ctx.accounts.owner.key();
+    user_lock.creator = ctx.accounts.owner.key();
+    user_lock.version = UserLock::VERSION;
     user_lock.lp_mint = ctx.accounts.lp_mint.key();
     user_lock.lock_count = new_lock_count;
     user_lock.lock_amount = amount;
+    user_lock.is_locked_permanently = lock_mode == LockMode::Permanent;
+    user_lock.is_rolling = lock_mode == LockMode::Rolling;
+    user_lock.rent_payer = ctx.accounts.payer.key();
+    user_lock.start_time = if lock_mode == LockMode::FromOpenTime {
+        pool_state.open_time
+    } else {
+        0
+    };
+    user_lock.label = metadata.label;
+    user_lock.uri = metadata.uri;
+    user_lock.beneficiaries = beneficiaries;
+    user_lock.is_creator_lock = user_lock.user == pool_state.pool_creator;
+
+    ctx.accounts
+        .active_lock_page
+        .activate(ctx.bumps.active_lock_page, user_lock)?;
+
+    assign_lock_id(
+        &mut ctx.accounts.global_lock_counter,
+        ctx.bumps.global_lock_counter,
+        &mut ctx.accounts.lock_id_index,
+        ctx.bumps.lock_id_index,
+        user_lock,
+    )?;
+
+    open_lock(
+        user_lock,
+        lp_lock_counter,
+        pool_lock_stats,
+        ctx.accounts
+            .reward_pool
+            .as_deref_mut()
+            .map(|reward_pool| (reward_pool.key(), &mut **reward_pool)),
+        principal_token_0,
+        principal_token_1,
+        lock_duration,
+    )?;
+
+    emit!(lp_lock_event(user_lock));
+
+    Ok(CreatedLock {
+        user_lock: user_lock.key(),
+        lock_count: user_lock.lock_count,
+        lock_id: user_lock.lock_id,
+    })
+}
+
+/// Check the pool registry verdicts on a pool and its two mints before locking its LP
+pub(crate) fn check_pool_registry(
+    registry_config: &AccountInfo,
+    pool_registry_entry: &AccountInfo,
+    mints: [&InterfaceAccount<Mint>; 2],
+    mint_registry_entries: [&AccountInfo; 2],
+) -> Result<()> {
+    check_pool_lockable(
+        RegistryConfig::load_allow_list_enabled(registry_config)?,
+        RegistryEntry::load_status(pool_registry_entry)?,
+        [
+            RegistryEntry::load_status(mint_registry_entries[0])?,
+            RegistryEntry::load_status(mint_registry_entries[1])?,
+        ],
+        [is_supported_mint(mints[0])?, is_supported_mint(mints[1])?],
+    )
+}
+
+/// Give a new lock the next program-wide ID and index its address under that ID
+pub(crate) fn assign_lock_id(
+    global_lock_counter: &mut Account<GlobalLockCounter>,
+    global_lock_counter_bump: u8,
+    lock_id_index: &mut Account<LockIdIndex>,
+    lock_id_index_bump: u8,
+    user_lock: &mut Account<UserLock>,
+) -> Result<()> {
+    global_lock_counter.bump = global_lock_counter_bump;
+    user_lock.lock_id = global_lock_counter.next_lock_id()?;
+
+    lock_id_index.bump = lock_id_index_bump;
+    lock_id_index.lock_id = user_lock.lock_id;
+    lock_id_index.user_lock = user_lock.key();
+    Ok(())
+}
+
+/// Record a new lock whose owner, mint, count, amount, mode and metadata are already set,
+/// and whose `start_time` holds the earliest time its duration may run from (0 for now),
+/// backed by the given principal token amounts, and add it to the owner's and pool's
+/// aggregates, enrolling it in the given reward pool, if any.
+pub(crate) fn open_lock(
+    user_lock: &mut UserLock,
+    lp_lock_counter: &mut LpLockCounter,
+    pool_lock_stats: &mut PoolLockStats,
+    reward_pool: Option<(Pubkey, &mut RewardPool)>,
+    principal_token_0: u64,
+    principal_token_1: u64,
+    lock_duration: u64,
+) -> Result<()> {
+    let block_timestamp = block_timestamp()?;
+
+    let start_time = std::cmp::max(block_timestamp, user_lock.start_time);
+    let unlock_time = if user_lock.is_locked_permanently {
+        0
+    } else {
+        let unlock_time = start_time
+            .checked_add(lock_duration)
+            .ok_or(ErrorCode::Overflow)?;
+        require!(
+            unlock_time - block_timestamp < MAX_LOCK_DURATION,
+            ErrorCode::LockDurationTooLong
+        );
+        unlock_time
+    };
+
+    let liquidity = liquidity(principal_token_0, principal_token_1)?;
+
+    user_lock.start_time = start_time;
     user_lock.unlock_time = unlock_time;
-    user_lock.principal_token_0 = results.token_0_amount.try_into().unwrap();
-    user_lock.principal_token_1 = results.token_1_amount.try_into().unwrap();
+    user_lock.lock_duration = if user_lock.is_locked_permanently {
+        0
+    } else {
+        lock_duration
+    };
+    user_lock.principal_token_0 = principal_token_0;
+    user_lock.principal_token_1 = principal_token_1;
     user_lock.principal_liquidity = liquidity;
-    user_lock.is_locked_permanently = lock_permanent;
     user_lock.token_0_fees_collected = 0;
     user_lock.token_1_fees_collected = 0;
     user_lock.is_unlocked = false;
     user_lock.last_updated = block_timestamp;
     user_lock.created_at = block_timestamp;
 
+    lp_lock_counter.voting_power.add_lock(user_lock, block_timestamp)?;
+    pool_lock_stats.add_lock(user_lock, block_timestamp)?;
+
+    if let Some((reward_pool_key, reward_pool)) = reward_pool {
+        reward_pool.enroll(user_lock, reward_pool_key, block_timestamp)?;
+    }
+
     // update lp lock counter
-    lp_lock_counter.total_lock_count = new_lock_count;
+    lp_lock_counter.total_lock_count = user_lock.lock_count;
     lp_lock_counter.total_lock_amount = lp_lock_counter
     .total_lock_amount
-    .checked_add(amount)
+    .checked_add(user_lock.lock_amount)
     .ok_or(ErrorCode::Overflow)?;
 
-    emit!(
-        LpLockEvent{
-            user: user_lock.user,
-            amount: user_lock.lock_amount,
-            lp_mint: user_lock.lp_mint,
-            locked_perm: lock_permanent
-        }
-    );
-
     Ok(())
 }
+
+/// `LpLockEvent` announcing a lock just recorded by `open_lock`, emitted by every
+/// instruction creating a lock so that indexers see all of them the same way
+pub(crate) fn lp_lock_event(user_lock: &UserLock) -> LpLockEvent {
+    LpLockEvent {
+        lock_id: user_lock.lock_id,
+        user: user_lock.user,
+        amount: user_lock.lock_amount,
+        lp_mint: user_lock.lp_mint,
+        locked_perm: user_lock.is_locked_permanently,
+        label: user_lock.label.clone(),
+        uri: user_lock.uri.clone(),
+        is_creator_lock: user_lock.is_creator_lock,
+    }
+}
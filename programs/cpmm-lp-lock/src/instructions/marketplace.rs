@@ -0,0 +1,328 @@
+use crate::error::ErrorCode;
+use crate::states::*;
+use crate::utils::block_timestamp;
+use crate::utils::token::transfer_from_user_to_pool_vault;
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+#[derive(Accounts)]
+pub struct ListLock<'info> {
+    /// Owner of the lock, pays rent for the listing
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = user_lp_lock.user == owner.key(),
+    )]
+    pub user_lp_lock: Box<Account<'info, UserLock>>,
+
+    #[account(
+        init,
+        seeds = [
+            LOCK_LISTING_SEED.as_bytes(),
+            user_lp_lock.key().as_ref()
+        ],
+        bump,
+        payer = owner,
+        space = LockListing::LEN,
+    )]
+    pub lock_listing: Box<Account<'info, LockListing>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateListing<'info> {
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            LOCK_LISTING_SEED.as_bytes(),
+            lock_listing.user_lock.as_ref()
+        ],
+        bump = lock_listing.bump,
+        constraint = lock_listing.seller == owner.key(),
+    )]
+    pub lock_listing: Box<Account<'info, LockListing>>,
+}
+
+#[derive(Accounts)]
+pub struct CancelListing<'info> {
+    /// Owner of the lock, receives the listing rent back
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = user_lp_lock.user == owner.key(),
+    )]
+    pub user_lp_lock: Box<Account<'info, UserLock>>,
+
+    #[account(
+        mut,
+        seeds = [
+            LOCK_LISTING_SEED.as_bytes(),
+            user_lp_lock.key().as_ref()
+        ],
+        bump = lock_listing.bump,
+        close = owner,
+    )]
+    pub lock_listing: Box<Account<'info, LockListing>>,
+}
+
+#[derive(Accounts)]
+pub struct BuyLock<'info> {
+    /// Pays the ask price and rent for its lock counter and active-lock page if they do not exist yet
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    /// CHECK: seller of the lock, receives SOL payments and the listing rent.
+    /// A seller buying back its own listing would alias the two lock counters.
+    #[account(
+        mut,
+        address = lock_listing.seller,
+        constraint = seller.key() != buyer.key() @ ErrorCode::BuyerIsSeller,
+    )]
+    pub seller: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        constraint = user_lp_lock.user == seller.key(),
+    )]
+    pub user_lp_lock: Box<Account<'info, UserLock>>,
+
+    #[account(
+        mut,
+        seeds = [
+            LOCK_LISTING_SEED.as_bytes(),
+            user_lp_lock.key().as_ref()
+        ],
+        bump = lock_listing.bump,
+        close = seller,
+    )]
+    pub lock_listing: Box<Account<'info, LockListing>>,
+
+    /// Lock counter of the seller
+    #[account(
+        mut,
+        seeds = [
+            LP_LOCK_COUNTER_SEED.as_bytes(),
+            seller.key().as_ref(),
+            user_lp_lock.lp_mint.as_ref()
+        ],
+        bump,
+    )]
+    pub seller_lp_lock_counter: Box<Account<'info, LpLockCounter>>,
+
+    #[account(
+        init_if_needed,
+        seeds = [
+            LP_LOCK_COUNTER_SEED.as_bytes(),
+            buyer.key().as_ref(),
+            user_lp_lock.lp_mint.as_ref()
+        ],
+        bump,
+        payer = buyer,
+        space = LpLockCounter::LEN,
+    )]
+    pub buyer_lp_lock_counter: Box<Account<'info, LpLockCounter>>,
+
+    /// Active-lock index page of the seller tracking the lock
+    #[account(
+        mut,
+        seeds = [
+            ACTIVE_LOCK_PAGE_SEED.as_bytes(),
+            seller.key().as_ref(),
+            user_lp_lock.lp_mint.as_ref(),
+            ActiveLockPage::page_of(user_lp_lock.lock_count).to_le_bytes().as_ref(),
+            user_lp_lock.active_lock_page_seed(&seller.key())
+        ],
+        bump = seller_active_lock_page.bump,
+    )]
+    pub seller_active_lock_page: Box<Account<'info, ActiveLockPage>>,
+
+    /// Active-lock index page of the buyer the lock moves to
+    #[account(
+        init_if_needed,
+        seeds = [
+            ACTIVE_LOCK_PAGE_SEED.as_bytes(),
+            buyer.key().as_ref(),
+            user_lp_lock.lp_mint.as_ref(),
+            ActiveLockPage::page_of(user_lp_lock.lock_count).to_le_bytes().as_ref(),
+            user_lp_lock.active_lock_page_seed(&buyer.key())
+        ],
+        bump,
+        payer = buyer,
+        space = ActiveLockPage::LEN,
+    )]
+    pub buyer_active_lock_page: Box<Account<'info, ActiveLockPage>>,
+
+    #[account(
+        mut,
+        seeds = [
+            POOL_LOCK_STATS_SEED.as_bytes(),
+            user_lp_lock.lp_mint.as_ref()
+        ],
+        bump = pool_lock_stats.bump,
+    )]
+    pub pool_lock_stats: Box<Account<'info, PoolLockStats>>,
+
+    /// Mint of the ask price, required if the lock is not priced in SOL
+    pub price_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+
+    /// Buyer's token account paying the ask price, required if the lock is not priced in SOL
+    #[account(mut)]
+    pub buyer_payment_token: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// Seller's token account receiving the ask price, required if the lock is not priced in SOL
+    #[account(mut)]
+    pub seller_payment_token: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// Token program of `price_mint`, required if the lock is not priced in SOL
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Put the lock up for sale at `price`, in SOL if `price_mint` is `Pubkey::default()`.
+/// The lock is escrowed until the listing is cancelled or bought.
+pub fn list_lock(ctx: Context<ListLock>, price_mint: Pubkey, price: u64) -> Result<()> {
+    let user_lock = &mut ctx.accounts.user_lp_lock;
+    require_eq!(user_lock.is_unlocked, false, ErrorCode::LockAlreadyUnlocked);
+    user_lock.check_no_lien()?;
+    // Fees and principal of a sold lock belong to the buyer
+    user_lock.check_transferable()?;
+
+    let block_timestamp = block_timestamp()?;
+    let lock_listing = &mut ctx.accounts.lock_listing;
+    lock_listing.bump = ctx.bumps.lock_listing;
+    lock_listing.user_lock = user_lock.key();
+    lock_listing.seller = user_lock.user;
+    lock_listing.set_price(price_mint, price)?;
+    lock_listing.listed_at = block_timestamp;
+
+    user_lock.is_listed = true;
+    user_lock.last_updated = block_timestamp;
+
+    emit!(LockListedEvent {
+        lock_id: user_lock.lock_id,
+        user_lock: user_lock.key(),
+        seller: lock_listing.seller,
+        price_mint,
+        price,
+    });
+
+    Ok(())
+}
+
+/// Change the ask price of a listed lock.
+pub fn update_listing(ctx: Context<UpdateListing>, price_mint: Pubkey, price: u64) -> Result<()> {
+    let lock_listing = &mut ctx.accounts.lock_listing;
+    lock_listing.set_price(price_mint, price)?;
+
+    emit!(LockListingUpdatedEvent {
+        user_lock: lock_listing.user_lock,
+        price_mint,
+        price,
+    });
+
+    Ok(())
+}
+
+/// Take the lock off the market and release it from escrow.
+pub fn cancel_listing(ctx: Context<CancelListing>) -> Result<()> {
+    let user_lock = &mut ctx.accounts.user_lp_lock;
+    user_lock.is_listed = false;
+    user_lock.last_updated = block_timestamp()?;
+
+    emit!(LockListingCancelledEvent {
+        lock_id: user_lock.lock_id,
+        user_lock: user_lock.key(),
+    });
+
+    Ok(())
+}
+
+/// Pay the seller the ask price and take over the lock, with its amount and voting power,
+/// in one step. `price_mint` and `max_price` are the terms the buyer agreed to.
+pub fn buy_lock(ctx: Context<BuyLock>, price_mint: Pubkey, max_price: u64) -> Result<()> {
+    let lock_listing = &ctx.accounts.lock_listing;
+    lock_listing.check_price(price_mint, max_price)?;
+
+    if lock_listing.price_mint == Pubkey::default() {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.buyer.to_account_info(),
+                    to: ctx.accounts.seller.to_account_info(),
+                },
+            ),
+            lock_listing.price,
+        )?;
+    } else {
+        let (
+            Some(price_mint),
+            Some(buyer_payment_token),
+            Some(seller_payment_token),
+            Some(token_program),
+        ) = (
+            ctx.accounts.price_mint.as_ref(),
+            ctx.accounts.buyer_payment_token.as_ref(),
+            ctx.accounts.seller_payment_token.as_ref(),
+            ctx.accounts.token_program.as_ref(),
+        )
+        else {
+            return err!(ErrorCode::InvalidPaymentAccount);
+        };
+        require!(
+            price_mint.key() == lock_listing.price_mint
+                && seller_payment_token.mint == lock_listing.price_mint
+                && seller_payment_token.owner == lock_listing.seller
+                && *price_mint.to_account_info().owner == token_program.key(),
+            ErrorCode::InvalidPaymentAccount
+        );
+        transfer_from_user_to_pool_vault(
+            ctx.accounts.buyer.to_account_info(),
+            buyer_payment_token.to_account_info(),
+            seller_payment_token.to_account_info(),
+            price_mint.to_account_info(),
+            token_program.to_account_info(),
+            lock_listing.price,
+            price_mint.decimals,
+        )?;
+    }
+
+    let buyer_lp_lock_counter = &mut ctx.accounts.buyer_lp_lock_counter;
+    let user_lock = &mut ctx.accounts.user_lp_lock;
+    buyer_lp_lock_counter.initialize_if_needed(ctx.accounts.buyer.key(), user_lock.lp_mint);
+
+    let block_timestamp = block_timestamp()?;
+    ctx.accounts.seller_lp_lock_counter.transfer_lock(
+        buyer_lp_lock_counter,
+        &mut ctx.accounts.pool_lock_stats,
+        user_lock,
+        block_timestamp,
+    )?;
+    ctx.accounts.seller_active_lock_page.move_lock(
+        &mut ctx.accounts.buyer_active_lock_page,
+        ctx.bumps.buyer_active_lock_page,
+        user_lock,
+    )?;
+    user_lock.is_listed = false;
+    user_lock.last_updated = block_timestamp;
+
+    emit!(LockSoldEvent {
+        lock_id: user_lock.lock_id,
+        user_lock: user_lock.key(),
+        seller: lock_listing.seller,
+        buyer: user_lock.user,
+        price_mint: lock_listing.price_mint,
+        price: lock_listing.price,
+    });
+
+    Ok(())
+}
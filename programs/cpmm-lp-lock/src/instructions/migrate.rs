@@ -0,0 +1,254 @@
+use std::ops::Add;
+use crate::error::ErrorCode;
+use crate::instructions::lock_lp::assign_lock_id;
+use crate::states::*;
+use crate::utils::block_timestamp;
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_lang::Discriminator;
+use anchor_spl::token_interface::Mint;
+
+#[derive(Accounts)]
+#[instruction(lock_count: u64)]
+pub struct MigrateUserLock<'info> {
+    /// Pays the rent for the grown account
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: legacy UserLock, checked against its discriminator and legacy length
+    #[account(
+        mut,
+        owner = crate::id(),
+        seeds = [
+            USER_LOCK_SEED.as_bytes(),
+            owner.key().as_ref(),
+            lp_mint.key().as_ref(),
+            lock_count.to_le_bytes().as_ref()
+        ],
+        bump,
+    )]
+    pub user_lp_lock: UncheckedAccount<'info>,
+
+    /// CHECK: owner of the lock, checked against the migrated lock
+    pub owner: UncheckedAccount<'info>,
+
+    /// Lp token mint of the lock, checked against the migrated lock
+    pub lp_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The owner's counter, which must be migrated first
+    #[account(
+        mut,
+        seeds = [
+            LP_LOCK_COUNTER_SEED.as_bytes(),
+            owner.key().as_ref(),
+            lp_mint.key().as_ref()
+        ],
+        bump,
+    )]
+    pub lp_lock_counter: Box<Account<'info, LpLockCounter>>,
+
+    #[account(
+        init_if_needed,
+        seeds = [
+            POOL_LOCK_STATS_SEED.as_bytes(),
+            lp_mint.key().as_ref()
+        ],
+        bump,
+        payer = payer,
+        space = PoolLockStats::LEN,
+    )]
+    pub pool_lock_stats: Box<Account<'info, PoolLockStats>>,
+
+    /// Active-lock index page the lock is added to
+    #[account(
+        init_if_needed,
+        seeds = [
+            ACTIVE_LOCK_PAGE_SEED.as_bytes(),
+            owner.key().as_ref(),
+            lp_mint.key().as_ref(),
+            ActiveLockPage::page_of(lock_count).to_le_bytes().as_ref()
+        ],
+        bump,
+        payer = payer,
+        space = ActiveLockPage::LEN,
+    )]
+    pub active_lock_page: Box<Account<'info, ActiveLockPage>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateLpLockCounter<'info> {
+    /// Pays the rent for the grown account
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: legacy LpLockCounter, checked against its discriminator and legacy length
+    #[account(
+        mut,
+        owner = crate::id(),
+    )]
+    pub lp_lock_counter: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct BackfillLockId<'info> {
+    /// Pays the rent for the ID index
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// Lock created before program-wide IDs, once migrated to the current layout
+    #[account(
+        mut,
+        constraint = user_lp_lock.lock_id == 0 @ ErrorCode::LockIdAlreadyAssigned,
+        constraint = !user_lp_lock.is_unlocked @ ErrorCode::LockAlreadyUnlocked,
+    )]
+    pub user_lp_lock: Box<Account<'info, UserLock>>,
+
+    #[account(
+        init_if_needed,
+        seeds = [
+            GLOBAL_LOCK_COUNTER_SEED.as_bytes(),
+        ],
+        bump,
+        payer = payer,
+        space = GlobalLockCounter::LEN,
+    )]
+    pub global_lock_counter: Box<Account<'info, GlobalLockCounter>>,
+
+    /// Maps the lock's new program-wide ID to its address
+    #[account(
+        init,
+        seeds = [
+            LOCK_ID_SEED.as_bytes(),
+            global_lock_counter.total_lock_count.add(1).to_le_bytes().as_ref()
+        ],
+        bump,
+        payer = payer,
+        space = LockIdIndex::LEN,
+    )]
+    pub lock_id_index: Box<Account<'info, LockIdIndex>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Bring a UserLock written by the first release up to the current layout and add it to
+/// the voting power of its owner's counter, the pool's lock stats and the owner's
+/// active-lock index, which all predate it.
+/// Anyone can migrate a lock since the result only depends on the lock's own data.
+pub fn migrate_user_lock(ctx: Context<MigrateUserLock>, _lock_count: u64) -> Result<()> {
+    let user_lock = migrate_account::<UserLockV0, UserLock>(
+        &ctx.accounts.user_lp_lock,
+        &ctx.accounts.payer,
+        &ctx.accounts.system_program,
+        UserLockV0::LEN,
+        UserLock::LEN,
+    )?;
+    require_keys_eq!(user_lock.user, ctx.accounts.owner.key(), ErrorCode::IncorrectLockOwner);
+    require_keys_eq!(
+        user_lock.lp_mint,
+        ctx.accounts.lp_mint.key(),
+        ErrorCode::IncorrectLpMint
+    );
+
+    let pool_lock_stats = &mut ctx.accounts.pool_lock_stats;
+    if pool_lock_stats.lp_mint == Pubkey::default() {
+        pool_lock_stats.bump = ctx.bumps.pool_lock_stats;
+        pool_lock_stats.lp_mint = ctx.accounts.lp_mint.key();
+    }
+    let block_timestamp = block_timestamp()?;
+    ctx.accounts
+        .lp_lock_counter
+        .voting_power
+        .add_lock(&user_lock, block_timestamp)?;
+    pool_lock_stats.add_lock(&user_lock, block_timestamp)?;
+    if !user_lock.is_unlocked {
+        ctx.accounts
+            .active_lock_page
+            .activate(ctx.bumps.active_lock_page, &user_lock)?;
+    }
+
+    emit!(AccountMigratedEvent {
+        account: ctx.accounts.user_lp_lock.key(),
+        version: UserLock::VERSION,
+    });
+
+    Ok(())
+}
+
+/// Bring an LpLockCounter written by the first release up to the current layout.
+pub fn migrate_lp_lock_counter(ctx: Context<MigrateLpLockCounter>) -> Result<()> {
+    migrate_account::<LpLockCounterV0, LpLockCounter>(
+        &ctx.accounts.lp_lock_counter,
+        &ctx.accounts.payer,
+        &ctx.accounts.system_program,
+        LpLockCounterV0::LEN,
+        LpLockCounter::LEN,
+    )?;
+
+    emit!(AccountMigratedEvent {
+        account: ctx.accounts.lp_lock_counter.key(),
+        version: LpLockCounter::VERSION,
+    });
+
+    Ok(())
+}
+
+/// Give a lock created before program-wide IDs the next ID and index it, as `lock_lp` does
+/// for new locks. Anyone can backfill a lock since its ID is simply the next one.
+pub fn backfill_lock_id(ctx: Context<BackfillLockId>) -> Result<()> {
+    assign_lock_id(
+        &mut ctx.accounts.global_lock_counter,
+        ctx.bumps.global_lock_counter,
+        &mut ctx.accounts.lock_id_index,
+        ctx.bumps.lock_id_index,
+        &mut ctx.accounts.user_lp_lock,
+    )
+}
+
+/// Decode `account` with its legacy layout, grow it to `current_len`, topping its rent up
+/// from `payer`, and write it back with the current layout, which is returned.
+fn migrate_account<'info, Legacy, Current>(
+    account: &UncheckedAccount<'info>,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+    legacy_len: usize,
+    current_len: usize,
+) -> Result<Current>
+where
+    Legacy: AnchorDeserialize,
+    Current: From<Legacy> + AccountSerialize + Discriminator,
+{
+    let current = {
+        let data = account.try_borrow_data()?;
+        require!(
+            data.starts_with(Current::DISCRIMINATOR),
+            ErrorCode::InvalidLegacyAccount
+        );
+        require_eq!(data.len(), legacy_len, ErrorCode::AccountAlreadyMigrated);
+        Current::from(Legacy::deserialize(&mut &data[Current::DISCRIMINATOR.len()..])?)
+    };
+
+    let rent_due = Rent::get()?
+        .minimum_balance(current_len)
+        .saturating_sub(account.lamports());
+    if rent_due > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.to_account_info(),
+                system_program::Transfer {
+                    from: payer.to_account_info(),
+                    to: account.to_account_info(),
+                },
+            ),
+            rent_due,
+        )?;
+    }
+    account.realloc(current_len, false)?;
+
+    let mut data = account.try_borrow_mut_data()?;
+    current.try_serialize(&mut &mut data[..])?;
+    Ok(current)
+}
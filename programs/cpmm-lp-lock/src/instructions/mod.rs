@@ -1,9 +1,86 @@
+pub mod claim_harvested;
+pub use claim_harvested::*;
+
+pub mod checkpoint_rewards;
+pub use checkpoint_rewards::*;
+
+pub mod claim_rewards;
+pub use claim_rewards::*;
 
 pub mod collect_fees;
 pub use collect_fees::*;
 
+pub mod collect_fees_as_delegate;
+pub use collect_fees_as_delegate::*;
+
+pub mod collect_fees_as_lp;
+pub use collect_fees_as_lp::*;
+
+pub mod collect_fees_single_sided;
+pub use collect_fees_single_sided::*;
+
+pub mod deposit_and_lock;
+pub use deposit_and_lock::*;
+
+pub mod get_creator_lock_share;
+pub use get_creator_lock_share::*;
+
+pub mod get_voting_power;
+pub use get_voting_power::*;
+
+pub mod harvest_fees;
+pub use harvest_fees::*;
+
+pub mod initialize_reward_pool;
+pub use initialize_reward_pool::*;
+
+pub mod lien;
+pub use lien::*;
+
 pub mod lock_lp;
 pub use lock_lp::*;
 
+pub mod marketplace;
+pub use marketplace::*;
+
+pub mod migrate;
+pub use migrate::*;
+
+pub mod notice_period;
+pub use notice_period::*;
+
+pub mod registry_admin;
+pub use registry_admin::*;
+
+pub mod remove_fee_split;
+pub use remove_fee_split::*;
+
+pub mod set_collect_delegate;
+pub use set_collect_delegate::*;
+
+pub mod set_fee_split;
+pub use set_fee_split::*;
+
+pub mod set_registry_config;
+pub use set_registry_config::*;
+
+pub mod set_registry_entry;
+pub use set_registry_entry::*;
+
+pub mod stop_rolling;
+pub use stop_rolling::*;
+
+pub mod sweep_reward_pool;
+pub use sweep_reward_pool::*;
+
+pub mod sync_lock_vault;
+pub use sync_lock_vault::*;
+
+pub mod unlock_and_withdraw;
+pub use unlock_and_withdraw::*;
+
 pub mod unlock_lp;
 pub use unlock_lp::*;
+
+pub mod update_lock_metadata;
+pub use update_lock_metadata::*;
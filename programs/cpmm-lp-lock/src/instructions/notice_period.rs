@@ -0,0 +1,101 @@
+use crate::error::ErrorCode;
+use crate::states::*;
+use crate::utils::block_timestamp;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct ManageUnlockNotice<'info> {
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = user_lp_lock.user == owner.key(),
+    )]
+    pub user_lp_lock: Box<Account<'info, UserLock>>,
+}
+
+/// Require `notice_period` seconds between `request_unlock` and unlocking.
+/// The notice period can only be increased, so holders can rely on it.
+pub fn set_notice_period(ctx: Context<ManageUnlockNotice>, notice_period: u64) -> Result<()> {
+    let user_lock = &mut ctx.accounts.user_lp_lock;
+    check_lock_is_active(user_lock)?;
+    require!(
+        notice_period >= user_lock.notice_period && notice_period <= MAX_NOTICE_PERIOD,
+        ErrorCode::InvalidNoticePeriod
+    );
+
+    user_lock.notice_period = notice_period;
+    user_lock.last_updated = block_timestamp()?;
+
+    emit!(NoticePeriodUpdatedEvent {
+        lock_id: user_lock.lock_id,
+        user_lock: user_lock.key(),
+        notice_period,
+    });
+
+    Ok(())
+}
+
+/// Announce that the lock will be unlocked and start its notice period.
+pub fn request_unlock(ctx: Context<ManageUnlockNotice>) -> Result<()> {
+    let user_lock = &mut ctx.accounts.user_lp_lock;
+    check_lock_is_active(user_lock)?;
+    require!(user_lock.notice_period > 0, ErrorCode::NoNoticePeriod);
+    require_eq!(
+        user_lock.unlock_requested_at,
+        0,
+        ErrorCode::UnlockAlreadyRequested
+    );
+
+    let block_timestamp = block_timestamp()?;
+    user_lock.check_unlock_request(block_timestamp)?;
+    user_lock.unlock_requested_at = block_timestamp;
+    user_lock.last_updated = block_timestamp;
+
+    emit!(UnlockRequestedEvent {
+        lock_id: user_lock.lock_id,
+        user_lock: user_lock.key(),
+        user: user_lock.user,
+        lp_mint: user_lock.lp_mint,
+        lock_amount: user_lock.lock_amount,
+        requested_at: block_timestamp,
+        unlockable_at: user_lock.unlockable_at(block_timestamp),
+    });
+
+    Ok(())
+}
+
+/// Withdraw a pending unlock request, returning the lock to its locked state.
+pub fn cancel_unlock_request(ctx: Context<ManageUnlockNotice>) -> Result<()> {
+    let user_lock = &mut ctx.accounts.user_lp_lock;
+    check_lock_is_active(user_lock)?;
+    require!(
+        user_lock.unlock_requested_at != 0,
+        ErrorCode::UnlockNotRequested
+    );
+
+    user_lock.unlock_requested_at = 0;
+    user_lock.last_updated = block_timestamp()?;
+
+    emit!(UnlockRequestCancelledEvent {
+        lock_id: user_lock.lock_id,
+        user_lock: user_lock.key(),
+        lp_mint: user_lock.lp_mint,
+    });
+
+    Ok(())
+}
+
+fn check_lock_is_active(user_lock: &UserLock) -> Result<()> {
+    require_eq!(
+        user_lock.is_locked_permanently,
+        false,
+        ErrorCode::LockIsPermanent
+    );
+    require_eq!(
+        user_lock.is_unlocked,
+        false,
+        ErrorCode::LockAlreadyUnlocked
+    );
+    Ok(())
+}
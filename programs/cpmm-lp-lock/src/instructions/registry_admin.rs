@@ -0,0 +1,71 @@
+use crate::error::ErrorCode;
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct InitializeRegistry<'info> {
+    /// Upgrade authority of this program
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        constraint = program.programdata_address()? == Some(program_data.key()),
+    )]
+    pub program: Program<'info, crate::program::CpmmLpLock>,
+
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(authority.key())
+            @ ErrorCode::NotUpgradeAuthority,
+    )]
+    pub program_data: Account<'info, ProgramData>,
+
+    #[account(
+        init,
+        seeds = [
+            REGISTRY_CONFIG_SEED.as_bytes(),
+        ],
+        bump,
+        payer = authority,
+        space = RegistryConfig::LEN,
+    )]
+    pub registry_config: Box<Account<'info, RegistryConfig>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetRegistryAdmin<'info> {
+    /// Current registry admin
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            REGISTRY_CONFIG_SEED.as_bytes(),
+        ],
+        bump = registry_config.bump,
+        has_one = admin @ ErrorCode::NotRegistryAdmin,
+    )]
+    pub registry_config: Box<Account<'info, RegistryConfig>>,
+}
+
+/// Create the pool registry and appoint its admin. Only the program's upgrade authority,
+/// which deployed the program, can do this, so the admin key is never baked into the binary.
+pub fn initialize_registry(ctx: Context<InitializeRegistry>, admin: Pubkey) -> Result<()> {
+    let registry_config = &mut ctx.accounts.registry_config;
+    registry_config.bump = ctx.bumps.registry_config;
+    registry_config.admin = admin;
+
+    emit!(RegistryAdminUpdatedEvent { admin });
+
+    Ok(())
+}
+
+/// Hand the pool registry over to a new admin.
+pub fn set_registry_admin(ctx: Context<SetRegistryAdmin>, new_admin: Pubkey) -> Result<()> {
+    ctx.accounts.registry_config.admin = new_admin;
+
+    emit!(RegistryAdminUpdatedEvent { admin: new_admin });
+
+    Ok(())
+}
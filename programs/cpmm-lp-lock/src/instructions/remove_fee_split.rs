@@ -0,0 +1,40 @@
+use crate::states::*;
+use crate::utils::block_timestamp;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct RemoveFeeSplit<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = user_lp_lock.user == owner.key(),
+    )]
+    pub user_lp_lock: Box<Account<'info, UserLock>>,
+
+    #[account(
+        mut,
+        seeds = [
+            FEE_SPLIT_SEED.as_bytes(),
+            user_lp_lock.key().as_ref()
+        ],
+        bump = fee_split.bump,
+        close = owner,
+    )]
+    pub fee_split: Box<Account<'info, FeeSplit>>,
+}
+
+/// Remove the fee-split configuration of a lock, so collected fees go to the owner again.
+pub fn remove_fee_split(ctx: Context<RemoveFeeSplit>) -> Result<()> {
+    let user_lock = &mut ctx.accounts.user_lp_lock;
+    user_lock.has_fee_split = false;
+    user_lock.last_updated = block_timestamp()?;
+
+    emit!(FeeSplitUpdatedEvent {
+        user_lock: user_lock.key(),
+        recipients: Vec::new(),
+    });
+
+    Ok(())
+}
@@ -0,0 +1,42 @@
+use crate::error::ErrorCode;
+use crate::states::*;
+use crate::utils::block_timestamp;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetCollectDelegate<'info> {
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = user_lp_lock.user == owner.key(),
+    )]
+    pub user_lp_lock: Box<Account<'info, UserLock>>,
+}
+
+/// Allow `collect_delegate` to collect the lock's fees for the owner, paying itself
+/// `keeper_tip_bps` of them. `Pubkey::default()` revokes the delegate.
+pub fn set_collect_delegate(
+    ctx: Context<SetCollectDelegate>,
+    collect_delegate: Pubkey,
+    keeper_tip_bps: u16,
+) -> Result<()> {
+    require!(
+        keeper_tip_bps <= MAX_KEEPER_TIP_BPS
+            && (collect_delegate != Pubkey::default() || keeper_tip_bps == 0),
+        ErrorCode::InvalidKeeperTip
+    );
+
+    let user_lock = &mut ctx.accounts.user_lp_lock;
+    user_lock.collect_delegate = collect_delegate;
+    user_lock.keeper_tip_bps = keeper_tip_bps;
+    user_lock.last_updated = block_timestamp()?;
+
+    emit!(CollectDelegateUpdatedEvent {
+        user_lock: user_lock.key(),
+        collect_delegate,
+        keeper_tip_bps,
+    });
+
+    Ok(())
+}
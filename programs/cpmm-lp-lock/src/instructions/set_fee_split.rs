@@ -0,0 +1,54 @@
+use crate::error::ErrorCode;
+use crate::states::*;
+use crate::utils::block_timestamp;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetFeeSplit<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = user_lp_lock.user == owner.key(),
+    )]
+    pub user_lp_lock: Box<Account<'info, UserLock>>,
+
+    #[account(
+        init_if_needed,
+        seeds = [
+            FEE_SPLIT_SEED.as_bytes(),
+            user_lp_lock.key().as_ref()
+        ],
+        bump,
+        payer = owner,
+        space = FeeSplit::LEN,
+    )]
+    pub fee_split: Box<Account<'info, FeeSplit>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Create or replace the fee-split configuration of a lock.
+pub fn set_fee_split(ctx: Context<SetFeeSplit>, recipients: Vec<FeeSplitRecipient>) -> Result<()> {
+    let user_lock = &mut ctx.accounts.user_lp_lock;
+    require_eq!(user_lock.is_unlocked, false, ErrorCode::LockAlreadyUnlocked);
+    user_lock.check_not_listed()?;
+    user_lock.check_no_lien()?;
+    validate_fee_split(&recipients)?;
+
+    let fee_split = &mut ctx.accounts.fee_split;
+    fee_split.bump = ctx.bumps.fee_split;
+    fee_split.user_lock = user_lock.key();
+    fee_split.recipients = recipients;
+
+    user_lock.has_fee_split = true;
+    user_lock.last_updated = block_timestamp()?;
+
+    emit!(FeeSplitUpdatedEvent {
+        user_lock: fee_split.user_lock,
+        recipients: fee_split.recipients.clone(),
+    });
+
+    Ok(())
+}
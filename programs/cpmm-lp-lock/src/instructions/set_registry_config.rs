@@ -0,0 +1,33 @@
+use crate::error::ErrorCode;
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetRegistryConfig<'info> {
+    /// Registry admin
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            REGISTRY_CONFIG_SEED.as_bytes(),
+        ],
+        bump = registry_config.bump,
+        has_one = admin @ ErrorCode::NotRegistryAdmin,
+    )]
+    pub registry_config: Box<Account<'info, RegistryConfig>>,
+}
+
+/// Update the pool registry settings.
+/// With the allow-list enabled, only pools the admin allowed can be locked.
+pub fn set_registry_config(
+    ctx: Context<SetRegistryConfig>,
+    allow_list_enabled: bool,
+) -> Result<()> {
+    let registry_config = &mut ctx.accounts.registry_config;
+    registry_config.allow_list_enabled = allow_list_enabled;
+
+    emit!(RegistryConfigUpdatedEvent { allow_list_enabled });
+
+    Ok(())
+}
@@ -0,0 +1,50 @@
+use crate::error::ErrorCode;
+use crate::states::*;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(key: Pubkey)]
+pub struct SetRegistryEntry<'info> {
+    /// Registry admin
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [
+            REGISTRY_CONFIG_SEED.as_bytes(),
+        ],
+        bump = registry_config.bump,
+        has_one = admin @ ErrorCode::NotRegistryAdmin,
+    )]
+    pub registry_config: Box<Account<'info, RegistryConfig>>,
+
+    #[account(
+        init_if_needed,
+        seeds = [
+            REGISTRY_ENTRY_SEED.as_bytes(),
+            key.as_ref()
+        ],
+        bump,
+        payer = admin,
+        space = RegistryEntry::LEN,
+    )]
+    pub registry_entry: Box<Account<'info, RegistryEntry>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Allow, deny or unlist a pool id or token mint in the pool registry.
+pub fn set_registry_entry(
+    ctx: Context<SetRegistryEntry>,
+    key: Pubkey,
+    status: RegistryStatus,
+) -> Result<()> {
+    let registry_entry = &mut ctx.accounts.registry_entry;
+    registry_entry.bump = ctx.bumps.registry_entry;
+    registry_entry.key = key;
+    registry_entry.status = status;
+
+    emit!(RegistryEntryUpdatedEvent { key, status });
+
+    Ok(())
+}
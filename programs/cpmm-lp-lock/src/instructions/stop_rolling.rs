@@ -0,0 +1,87 @@
+use crate::error::ErrorCode;
+use crate::states::*;
+use crate::utils::block_timestamp;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct StopRolling<'info> {
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = user_lp_lock.user == owner.key(),
+    )]
+    pub user_lp_lock: Box<Account<'info, UserLock>>,
+
+    #[account(
+        mut,
+        seeds = [
+            LP_LOCK_COUNTER_SEED.as_bytes(),
+            owner.key().as_ref(),
+            user_lp_lock.lp_mint.as_ref()
+        ],
+        bump,
+    )]
+    pub lp_lock_counter: Box<Account<'info, LpLockCounter>>,
+
+    #[account(
+        mut,
+        seeds = [
+            POOL_LOCK_STATS_SEED.as_bytes(),
+            user_lp_lock.lp_mint.as_ref()
+        ],
+        bump = pool_lock_stats.bump,
+    )]
+    pub pool_lock_stats: Box<Account<'info, PoolLockStats>>,
+
+    /// Reward pool the lock is enrolled in, required if it has one
+    #[account(
+        mut,
+        address = user_lp_lock.reward_pool @ ErrorCode::IncorrectRewardPool,
+    )]
+    pub reward_pool: Option<Box<Account<'info, RewardPool>>>,
+}
+
+/// Stop a rolling lock from renewing. It becomes a timed lock that unlocks
+/// a full `lock_duration` from now.
+pub fn stop_rolling(ctx: Context<StopRolling>) -> Result<()> {
+    let user_lock = &mut ctx.accounts.user_lp_lock;
+    let lp_lock_counter = &mut ctx.accounts.lp_lock_counter;
+    let pool_lock_stats = &mut ctx.accounts.pool_lock_stats;
+
+    require_eq!(
+        user_lock.is_unlocked,
+        false,
+        ErrorCode::LockAlreadyUnlocked
+    );
+    require!(user_lock.is_rolling, ErrorCode::LockNotRolling);
+
+    let block_timestamp = block_timestamp()?;
+
+    lp_lock_counter.voting_power.remove_lock(user_lock, block_timestamp)?;
+    pool_lock_stats.remove_lock(user_lock, block_timestamp)?;
+
+    user_lock.unlock_time = user_lock.effective_unlock_time(block_timestamp);
+    user_lock.is_rolling = false;
+    user_lock.last_updated = block_timestamp;
+
+    lp_lock_counter.voting_power.add_lock(user_lock, block_timestamp)?;
+    pool_lock_stats.add_lock(user_lock, block_timestamp)?;
+
+    match ctx.accounts.reward_pool.as_mut() {
+        Some(reward_pool) => reward_pool.checkpoint(user_lock, block_timestamp)?,
+        None => require_keys_eq!(
+            user_lock.reward_pool,
+            Pubkey::default(),
+            ErrorCode::RewardPoolRequired
+        ),
+    }
+
+    emit!(StopRollingEvent {
+        lock_id: user_lock.lock_id,
+        user_lock: user_lock.key(),
+        unlock_time: user_lock.unlock_time,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,86 @@
+use crate::error::ErrorCode;
+use crate::states::*;
+use crate::utils::*;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+#[derive(Accounts)]
+pub struct SweepRewardPool<'info> {
+    /// Project that funded the reward pool
+    pub authority: Signer<'info>,
+
+    /// CHECK: lock vault authority
+    #[account(
+        seeds = [
+            crate::AUTH_SEED.as_bytes(),
+        ],
+     bump,
+    )]
+    pub lock_vault_authority: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        has_one = authority @ ErrorCode::NotRewardPoolAuthority,
+        has_one = reward_vault,
+        has_one = reward_mint,
+    )]
+    pub reward_pool: Box<Account<'info, RewardPool>>,
+
+    /// The vault that holds the undistributed rewards
+    #[account(mut)]
+    pub reward_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Reward token mint
+    pub reward_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The authority's token account to receive the undistributed rewards
+    #[account(
+        mut,
+        token::mint = reward_mint,
+        token::token_program = reward_token_program,
+    )]
+    pub authority_reward_token: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Reward token program
+    pub reward_token_program: Interface<'info, TokenInterface>,
+}
+
+/// Return the rewards of an ended campaign that no lock earned to its authority.
+/// Rewards accrued to locks stay in the vault until they are claimed.
+pub fn sweep_reward_pool(ctx: Context<SweepRewardPool>) -> Result<()> {
+    let reward_pool = &mut ctx.accounts.reward_pool;
+    let block_timestamp = block_timestamp()?;
+    require!(
+        block_timestamp >= reward_pool.end_time,
+        ErrorCode::RewardCampaignActive
+    );
+    reward_pool.update(block_timestamp)?;
+    // Emissions nobody was enrolled for are swept too, not paid to the next lock to enroll
+    reward_pool.carried_rewards = 0;
+
+    let amount = reward_pool.undistributed(ctx.accounts.reward_vault.amount);
+    if amount > 0 {
+        transfer_from_pool_vault_to_user(
+            ctx.accounts.lock_vault_authority.to_account_info(),
+            ctx.accounts.reward_vault.to_account_info(),
+            ctx.accounts.authority_reward_token.to_account_info(),
+            ctx.accounts.reward_mint.to_account_info(),
+            ctx.accounts.reward_token_program.to_account_info(),
+            amount,
+            ctx.accounts.reward_mint.decimals,
+            &[&[
+                crate::AUTH_SEED.as_bytes(),
+                &[ctx.bumps.lock_vault_authority],
+            ]],
+        )?;
+    }
+
+    emit!(RewardPoolSweptEvent {
+        lp_mint: reward_pool.lp_mint,
+        campaign: reward_pool.campaign,
+        authority: reward_pool.authority,
+        amount,
+    });
+
+    Ok(())
+}
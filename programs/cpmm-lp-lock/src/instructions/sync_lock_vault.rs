@@ -0,0 +1,233 @@
+use crate::curve::CurveCalculator;
+use crate::curve::RoundDirection;
+use crate::error::ErrorCode;
+use crate::states::*;
+use crate::utils::token::*;
+use crate::utils::{block_timestamp, liquidity};
+use crate::LP_LOCK_VAULT_SEED;
+use anchor_lang::prelude::*;
+use anchor_spl::token::Token;
+use anchor_spl::token_interface::{Mint, TokenAccount};
+
+#[derive(Accounts)]
+pub struct SyncLockVault<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// CHECK: pool vault and lp mint authority
+    #[account(
+        address = crate::vault_and_lp_mint_auth::id()
+    )]
+    pub authority: UncheckedAccount<'info>,
+
+    /// CHECK: lock vault authority
+    #[account(
+        seeds = [
+            crate::AUTH_SEED.as_bytes(),
+        ],
+        bump,
+    )]
+    pub lock_vault_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Raydium pool state account
+    #[account(
+        owner = crate::raydium_cpmm::id()
+    )]
+    pub pool_state: UncheckedAccount<'info>,
+
+    /// Owner lp token account receiving swept surplus
+    #[account(
+        mut,
+        token::mint = lp_mint,
+        token::authority = owner,
+        token::token_program = token_program,
+    )]
+    pub owner_lp_token: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// token Program
+    pub token_program: Program<'info, Token>,
+
+    /// Lp token mint
+    #[account(
+        mint::authority = authority,
+    )]
+    pub lp_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [
+            LP_LOCK_COUNTER_SEED.as_bytes(),
+            owner.key().as_ref(),
+            lp_mint.key().as_ref()
+        ],
+        bump,
+    )]
+    pub lp_lock_counter: Box<Account<'info, LpLockCounter>>,
+
+    #[account(
+        mut,
+        seeds = [
+            POOL_LOCK_STATS_SEED.as_bytes(),
+            lp_mint.key().as_ref()
+        ],
+        bump = pool_lock_stats.bump,
+    )]
+    pub pool_lock_stats: Box<Account<'info, PoolLockStats>>,
+
+    #[account(
+        mut,
+        constraint = user_lp_lock.user == owner.key(),
+        constraint = user_lp_lock.lp_mint == lp_mint.key(),
+    )]
+    pub user_lp_lock: Box<Account<'info, UserLock>>,
+
+    /// The vault that holds the locked LP tokens
+    #[account(
+        mut,
+        token::mint = lp_mint,
+        token::authority = lock_vault_authority,
+        seeds = [
+            LP_LOCK_VAULT_SEED.as_bytes(),
+            user_lp_lock.pda_owner().as_ref(),
+            lp_mint.key().as_ref(),
+            user_lp_lock.lock_count.to_le_bytes().as_ref()
+        ],
+        bump,
+    )]
+    pub lp_lock_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The address that holds pool tokens for token_0
+    pub token_0_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The address that holds pool tokens for token_1
+    pub token_1_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Reward pool the lock is enrolled in, required if it has one
+    #[account(
+        mut,
+        address = user_lp_lock.reward_pool @ ErrorCode::IncorrectRewardPool,
+    )]
+    pub reward_pool: Option<Box<Account<'info, RewardPool>>>,
+}
+
+/// Reconcile a lock vault holding more LP than `lock_amount`, e.g. after a direct transfer.
+/// With `credit` the surplus becomes new principal of the lock, otherwise it is swept back to the owner.
+pub fn sync_lock_vault(ctx: Context<SyncLockVault>, credit: bool) -> Result<()> {
+    let user_lock = &mut ctx.accounts.user_lp_lock;
+    require_eq!(user_lock.is_unlocked, false, ErrorCode::LockAlreadyUnlocked);
+
+    let surplus = ctx
+        .accounts
+        .lp_lock_vault
+        .amount
+        .checked_sub(user_lock.lock_amount)
+        .ok_or(ErrorCode::UnderflowError)?;
+    require_gt!(surplus, 0, ErrorCode::NoLockVaultSurplus);
+
+    if credit {
+        let pool_state =
+            PoolState::try_deserialize(&mut &ctx.accounts.pool_state.data.borrow()[..])?;
+        require_eq!(
+            pool_state.lp_mint,
+            ctx.accounts.lp_mint.key(),
+            ErrorCode::IncorrectLpMint
+        );
+        require_keys_eq!(
+            pool_state.token_0_vault,
+            ctx.accounts.token_0_vault.key(),
+            ErrorCode::IncorrectPoolAccount
+        );
+        require_keys_eq!(
+            pool_state.token_1_vault,
+            ctx.accounts.token_1_vault.key(),
+            ErrorCode::IncorrectPoolAccount
+        );
+
+        let (total_token_0_amount, total_token_1_amount) = pool_state.vault_amount_without_fee(
+            ctx.accounts.token_0_vault.amount,
+            ctx.accounts.token_1_vault.amount,
+        )?;
+        let results = CurveCalculator::lp_tokens_to_trading_tokens(
+            u128::from(surplus),
+            u128::from(pool_state.lp_supply),
+            u128::from(total_token_0_amount),
+            u128::from(total_token_1_amount),
+            RoundDirection::Floor,
+        )
+        .ok_or(ErrorCode::ZeroTradingTokens)?;
+        let token_0_amount =
+            u64::try_from(results.token_0_amount).map_err(|_| error!(ErrorCode::Overflow))?;
+        let token_1_amount =
+            u64::try_from(results.token_1_amount).map_err(|_| error!(ErrorCode::Overflow))?;
+        let liquidity = liquidity(token_0_amount, token_1_amount)?;
+
+        let block_timestamp = block_timestamp()?;
+        let lp_lock_counter = &mut ctx.accounts.lp_lock_counter;
+        let pool_lock_stats = &mut ctx.accounts.pool_lock_stats;
+        lp_lock_counter
+            .voting_power
+            .remove_lock(user_lock, block_timestamp)?;
+        pool_lock_stats.remove_lock(user_lock, block_timestamp)?;
+
+        // The surplus is valued at the current pool price, like a new lock
+        user_lock.lock_amount = user_lock
+            .lock_amount
+            .checked_add(surplus)
+            .ok_or(ErrorCode::Overflow)?;
+        user_lock.principal_token_0 = user_lock
+            .principal_token_0
+            .checked_add(token_0_amount)
+            .ok_or(ErrorCode::Overflow)?;
+        user_lock.principal_token_1 = user_lock
+            .principal_token_1
+            .checked_add(token_1_amount)
+            .ok_or(ErrorCode::Overflow)?;
+        user_lock.principal_liquidity = user_lock
+            .principal_liquidity
+            .checked_add(liquidity)
+            .ok_or(ErrorCode::Overflow)?;
+        user_lock.last_updated = block_timestamp;
+
+        lp_lock_counter
+            .voting_power
+            .add_lock(user_lock, block_timestamp)?;
+        pool_lock_stats.add_lock(user_lock, block_timestamp)?;
+        lp_lock_counter.total_lock_amount = lp_lock_counter
+            .total_lock_amount
+            .checked_add(surplus)
+            .ok_or(ErrorCode::Overflow)?;
+
+        match ctx.accounts.reward_pool.as_mut() {
+            Some(reward_pool) => reward_pool.checkpoint(user_lock, block_timestamp)?,
+            None => require_keys_eq!(
+                user_lock.reward_pool,
+                Pubkey::default(),
+                ErrorCode::RewardPoolRequired
+            ),
+        }
+    } else {
+        transfer_from_pool_vault_to_user(
+            ctx.accounts.lock_vault_authority.to_account_info(),
+            ctx.accounts.lp_lock_vault.to_account_info(),
+            ctx.accounts.owner_lp_token.to_account_info(),
+            ctx.accounts.lp_mint.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            surplus,
+            ctx.accounts.lp_mint.decimals,
+            &[&[
+                crate::AUTH_SEED.as_bytes(),
+                &[ctx.bumps.lock_vault_authority],
+            ]],
+        )?;
+    }
+
+    emit!(SyncLockVaultEvent {
+        user: user_lock.user,
+        lp_mint: user_lock.lp_mint,
+        lock_count: user_lock.lock_count,
+        surplus,
+        credited: credit,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,337 @@
+use crate::error::ErrorCode;
+use crate::instructions::unlock_lp::settle_unlock;
+use crate::states::*;
+use crate::utils::raydium;
+use crate::LP_LOCK_VAULT_SEED;
+use anchor_lang::prelude::*;
+use anchor_spl::memo::spl_memo;
+use anchor_spl::token::{CloseAccount, Token};
+use anchor_spl::token_2022::Token2022;
+use anchor_spl::token_interface::{Mint, TokenAccount};
+
+#[derive(Accounts)]
+pub struct UnlockAndWithdraw<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// CHECK: pool vault and lp mint authority
+    #[account(
+        address = crate::vault_and_lp_mint_auth::id()
+    )]
+    pub authority: UncheckedAccount<'info>,
+
+    /// CHECK: lock vault authority
+    #[account(
+        seeds = [
+            crate::AUTH_SEED.as_bytes(),
+        ],
+        bump,
+    )]
+    pub lock_vault_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Raydium pool state account
+    #[account(
+        mut,
+        owner = crate::raydium_cpmm::id()
+    )]
+    pub pool_state: UncheckedAccount<'info>,
+
+    /// Lp token mint
+    #[account(
+        mut,
+        mint::authority = authority,
+    )]
+    pub lp_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [
+            LP_LOCK_COUNTER_SEED.as_bytes(),
+            owner.key().as_ref(),
+            lp_mint.key().as_ref()
+        ],
+        bump,
+    )]
+    pub lp_lock_counter: Box<Account<'info, LpLockCounter>>,
+
+    #[account(
+        mut,
+        seeds = [
+            POOL_LOCK_STATS_SEED.as_bytes(),
+            lp_mint.key().as_ref()
+        ],
+        bump = pool_lock_stats.bump,
+    )]
+    pub pool_lock_stats: Box<Account<'info, PoolLockStats>>,
+
+    /// The lock, closed with its rent going to `rent_payer`
+    #[account(
+        mut,
+        constraint = user_lp_lock.user == owner.key(),
+        constraint = user_lp_lock.lp_mint == lp_mint.key(),
+        close = rent_payer,
+    )]
+    pub user_lp_lock: Box<Account<'info, UserLock>>,
+
+    /// Index entry of the lock's ID, closed with it. Only locks without an ID may omit it.
+    #[account(
+        mut,
+        seeds = [
+            LOCK_ID_SEED.as_bytes(),
+            user_lp_lock.lock_id.to_le_bytes().as_ref()
+        ],
+        bump = lock_id_index.bump,
+        close = rent_payer,
+    )]
+    pub lock_id_index: Option<Box<Account<'info, LockIdIndex>>>,
+
+    /// Active-lock index page tracking the lock
+    #[account(
+        mut,
+        seeds = [
+            ACTIVE_LOCK_PAGE_SEED.as_bytes(),
+            user_lp_lock.user.as_ref(),
+            lp_mint.key().as_ref(),
+            ActiveLockPage::page_of(user_lp_lock.lock_count).to_le_bytes().as_ref(),
+            user_lp_lock.active_lock_page_seed(&user_lp_lock.user)
+        ],
+        bump = active_lock_page.bump,
+    )]
+    pub active_lock_page: Box<Account<'info, ActiveLockPage>>,
+
+    /// The vault that holds the locked LP tokens
+    #[account(
+        mut,
+        token::mint = lp_mint,
+        token::authority = lock_vault_authority,
+        seeds = [
+            LP_LOCK_VAULT_SEED.as_bytes(),
+            user_lp_lock.pda_owner().as_ref(),
+            lp_mint.key().as_ref(),
+            user_lp_lock.lock_count.to_le_bytes().as_ref()
+        ],
+        bump,
+    )]
+    pub lp_lock_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: receives the rent of the lock and its vault, checked against the lock
+    #[account(
+        mut,
+        address = *user_lp_lock.rent_recipient(),
+    )]
+    pub rent_payer: UncheckedAccount<'info>,
+
+    /// The owner's token account for receive token_0
+    #[account(
+        mut,
+        token::mint = token_0_vault.mint,
+        token::authority = owner
+    )]
+    pub token_0_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The owner's token account for receive token_1
+    #[account(
+        mut,
+        token::mint = token_1_vault.mint,
+        token::authority = owner
+    )]
+    pub token_1_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The address that holds pool tokens for token_0
+    #[account(mut)]
+    pub token_0_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The address that holds pool tokens for token_1
+    #[account(mut)]
+    pub token_1_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// token Program
+    pub token_program: Program<'info, Token>,
+
+    /// Token program 2022
+    pub token_program_2022: Program<'info, Token2022>,
+
+    /// The mint of token_0 vault
+    #[account(
+        address = token_0_vault.mint
+    )]
+    pub vault_0_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The mint of token_1 vault
+    #[account(
+        address = token_1_vault.mint
+    )]
+    pub vault_1_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// memo program
+    /// CHECK:
+    #[account(
+        address = spl_memo::id()
+    )]
+    pub memo_program: UncheckedAccount<'info>,
+
+    /// CHECK: This account is owned by another program
+    #[account(
+        address = crate::raydium_cpmm::id()
+    )]
+    pub raydium_cpmm_program: AccountInfo<'info>,
+
+    /// Reward pool the lock is enrolled in, required if it has one
+    #[account(
+        mut,
+        address = user_lp_lock.reward_pool @ ErrorCode::IncorrectRewardPool,
+    )]
+    pub reward_pool: Option<Box<Account<'info, RewardPool>>>,
+
+    /// Receives the lock's unclaimed rewards, required with `reward_pool`
+    #[account(
+        init,
+        seeds = [
+            REWARD_RECEIPT_SEED.as_bytes(),
+            user_lp_lock.key().as_ref()
+        ],
+        bump,
+        payer = owner,
+        space = RewardReceipt::LEN,
+    )]
+    pub reward_receipt: Option<Box<Account<'info, RewardReceipt>>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Unlock a matured lock and withdraw its LP from Raydium in the same step,
+/// paying token_0 and token_1 straight to the owner and closing the lock vault.
+pub fn unlock_and_withdraw(
+    ctx: Context<UnlockAndWithdraw>,
+    minimum_token_0_amount: u64,
+    minimum_token_1_amount: u64,
+) -> Result<()> {
+    let user_lock = &mut ctx.accounts.user_lp_lock;
+
+    let pool_state = PoolState::try_deserialize(&mut &ctx.accounts.pool_state.data.borrow()[..])?;
+    require_eq!(
+        pool_state.lp_mint,
+        ctx.accounts.lp_mint.key(),
+        ErrorCode::IncorrectLpMint
+    );
+    require_keys_eq!(
+        pool_state.token_0_vault,
+        ctx.accounts.token_0_vault.key(),
+        ErrorCode::IncorrectPoolAccount
+    );
+    require_keys_eq!(
+        pool_state.token_1_vault,
+        ctx.accounts.token_1_vault.key(),
+        ErrorCode::IncorrectPoolAccount
+    );
+    pool_state.require_enabled(PoolStatusBitIndex::Withdraw)?;
+
+    require!(
+        user_lock.beneficiaries.is_empty(),
+        ErrorCode::LockHasBeneficiaries
+    );
+
+    require!(
+        user_lock.lock_id == 0 || ctx.accounts.lock_id_index.is_some(),
+        ErrorCode::LockIdIndexRequired
+    );
+
+    settle_unlock(
+        user_lock,
+        &mut ctx.accounts.lp_lock_counter,
+        &mut ctx.accounts.pool_lock_stats,
+        &mut ctx.accounts.active_lock_page,
+        ctx.accounts
+            .reward_pool
+            .as_deref_mut()
+            .map(|reward_pool| &mut **reward_pool),
+    )?;
+    match ctx.accounts.reward_receipt.as_deref_mut() {
+        Some(reward_receipt) => reward_receipt.issue(
+            ctx.bumps.reward_receipt.ok_or(ErrorCode::RewardReceiptRequired)?,
+            user_lock.key(),
+            user_lock,
+        ),
+        None => require!(
+            ctx.accounts.reward_pool.is_none() && user_lock.rewards_owed == 0,
+            ErrorCode::RewardReceiptRequired
+        ),
+    }
+
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        crate::AUTH_SEED.as_bytes(),
+        &[ctx.bumps.lock_vault_authority],
+    ]];
+
+    let token_0_balance_before = ctx.accounts.token_0_account.amount;
+    let token_1_balance_before = ctx.accounts.token_1_account.amount;
+
+    // The lock vault authority burns the vault's LP directly, including any LP sent straight
+    // to the vault, so that the vault can be closed
+    let lp_amount = ctx.accounts.lp_lock_vault.amount;
+    if lp_amount > 0 {
+        raydium::withdraw(
+            raydium::WithdrawAccounts {
+                owner: ctx.accounts.lock_vault_authority.to_account_info(),
+                authority: ctx.accounts.authority.to_account_info(),
+                pool_state: ctx.accounts.pool_state.to_account_info(),
+                owner_lp_token: ctx.accounts.lp_lock_vault.to_account_info(),
+                token_0_account: ctx.accounts.token_0_account.to_account_info(),
+                token_1_account: ctx.accounts.token_1_account.to_account_info(),
+                token_0_vault: ctx.accounts.token_0_vault.to_account_info(),
+                token_1_vault: ctx.accounts.token_1_vault.to_account_info(),
+                token_program: ctx.accounts.token_program.to_account_info(),
+                token_program_2022: ctx.accounts.token_program_2022.to_account_info(),
+                vault_0_mint: ctx.accounts.vault_0_mint.to_account_info(),
+                vault_1_mint: ctx.accounts.vault_1_mint.to_account_info(),
+                lp_mint: ctx.accounts.lp_mint.to_account_info(),
+                memo_program: ctx.accounts.memo_program.to_account_info(),
+            },
+            lp_amount,
+            minimum_token_0_amount,
+            minimum_token_1_amount,
+            signer_seeds,
+        )?;
+    }
+
+    anchor_spl::token::close_account(CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        CloseAccount {
+            account: ctx.accounts.lp_lock_vault.to_account_info(),
+            destination: ctx.accounts.rent_payer.to_account_info(),
+            authority: ctx.accounts.lock_vault_authority.to_account_info(),
+        },
+        signer_seeds,
+    ))?;
+
+    ctx.accounts.token_0_account.reload()?;
+    ctx.accounts.token_1_account.reload()?;
+    let token_0_amount = ctx
+        .accounts
+        .token_0_account
+        .amount
+        .checked_sub(token_0_balance_before)
+        .ok_or(ErrorCode::UnderflowError)?;
+    let token_1_amount = ctx
+        .accounts
+        .token_1_account
+        .amount
+        .checked_sub(token_1_balance_before)
+        .ok_or(ErrorCode::UnderflowError)?;
+
+    emit!(LpUnlockEvent {
+        lock_id: user_lock.lock_id,
+        user: user_lock.user,
+        amount: user_lock.lock_amount,
+        lp_mint: user_lock.lp_mint
+    });
+    emit!(UnlockAndWithdrawEvent {
+        user: user_lock.user,
+        lp_mint: user_lock.lp_mint,
+        lp_amount: user_lock.lock_amount,
+        token_0_amount,
+        token_1_amount,
+    });
+
+    Ok(())
+}
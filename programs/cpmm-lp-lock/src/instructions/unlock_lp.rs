@@ -1,11 +1,12 @@
 use crate::error::ErrorCode;
+use crate::instructions::collect_fees::check_recipient_token_account;
 use crate::states::*;
 use crate::utils::token::*;
+use crate::utils::block_timestamp;
 use crate::LP_LOCK_VAULT_SEED;
 use anchor_lang::prelude::*;
 use anchor_spl::token::Token;
 use anchor_spl::token_interface::{Mint, TokenAccount};
-use anchor_lang::{solana_program::clock};
 use anchor_spl::token::CloseAccount;
 
 
@@ -64,13 +65,51 @@ pub struct UnlockLp<'info> {
     )]
     pub lp_lock_counter: Box<Account<'info, LpLockCounter>>,
 
+    #[account(
+        mut,
+        seeds = [
+            POOL_LOCK_STATS_SEED.as_bytes(),
+            lp_mint.key().as_ref()
+        ],
+        bump = pool_lock_stats.bump,
+    )]
+    pub pool_lock_stats: Box<Account<'info, PoolLockStats>>,
+
+    /// The lock, closed with its rent going to `rent_payer`
     #[account(
         mut,
         constraint = user_lp_lock.user == owner.key(),
         constraint = user_lp_lock.lp_mint == lp_mint.key(),
+        close = rent_payer,
     )]
     pub user_lp_lock: Box<Account<'info, UserLock>>,
 
+    /// Index entry of the lock's ID, closed with it. Only locks without an ID may omit it.
+    #[account(
+        mut,
+        seeds = [
+            LOCK_ID_SEED.as_bytes(),
+            user_lp_lock.lock_id.to_le_bytes().as_ref()
+        ],
+        bump = lock_id_index.bump,
+        close = rent_payer,
+    )]
+    pub lock_id_index: Option<Box<Account<'info, LockIdIndex>>>,
+
+    /// Active-lock index page tracking the lock
+    #[account(
+        mut,
+        seeds = [
+            ACTIVE_LOCK_PAGE_SEED.as_bytes(),
+            user_lp_lock.user.as_ref(),
+            lp_mint.key().as_ref(),
+            ActiveLockPage::page_of(user_lp_lock.lock_count).to_le_bytes().as_ref(),
+            user_lp_lock.active_lock_page_seed(&user_lp_lock.user)
+        ],
+        bump = active_lock_page.bump,
+    )]
+    pub active_lock_page: Box<Account<'info, ActiveLockPage>>,
+
     /// CHECK The vault that holds the locked LP tokens
     #[account(
         mut , 
@@ -78,7 +117,7 @@ pub struct UnlockLp<'info> {
         token::authority = lock_vault_authority ,
         seeds = [
             LP_LOCK_VAULT_SEED.as_bytes(),
-            owner.key().as_ref(),
+            user_lp_lock.pda_owner().as_ref(),
             lp_mint.key().as_ref(),
             user_lp_lock.lock_count.to_le_bytes().as_ref()
         ],
@@ -86,65 +125,130 @@ pub struct UnlockLp<'info> {
     )]
     pub lp_lock_vault: Box<InterfaceAccount<'info, TokenAccount>>,
 
+    /// CHECK: receives the rent of the lock and its vault, checked against the lock
+    #[account(
+        mut,
+        address = *user_lp_lock.rent_recipient(),
+    )]
+    pub rent_payer: UncheckedAccount<'info>,
+
+    /// Reward pool the lock is enrolled in, required if it has one
+    #[account(
+        mut,
+        address = user_lp_lock.reward_pool @ ErrorCode::IncorrectRewardPool,
+    )]
+    pub reward_pool: Option<Box<Account<'info, RewardPool>>>,
+
+    /// Receives the lock's unclaimed rewards, required with `reward_pool`
+    #[account(
+        init,
+        seeds = [
+            REWARD_RECEIPT_SEED.as_bytes(),
+            user_lp_lock.key().as_ref()
+        ],
+        bump,
+        payer = owner,
+        space = RewardReceipt::LEN,
+    )]
+    pub reward_receipt: Option<Box<Account<'info, RewardReceipt>>>,
+
     pub system_program: Program<'info, System>,
 }
 
-pub fn unlock_lp(ctx: Context<UnlockLp>) -> Result<()> {
+/// Unlock an expired lock. If the lock has beneficiaries, each one's LP token account
+/// follows in `remaining_accounts`, in beneficiary order, and receives its share of the LP.
+pub fn unlock_lp<'info>(ctx: Context<'_, '_, '_, 'info, UnlockLp<'info>>) -> Result<()> {
     let user_lock = &mut ctx.accounts.user_lp_lock;
-    let lp_lock_counter = &mut ctx.accounts.lp_lock_counter;
 
-    require_eq!(
-        user_lock.is_locked_permanently,
-        false,
-        ErrorCode::LockIsPermanent
-    );
+    let pool_state_info = &ctx.accounts.pool_state;
+    let pool_state = PoolState::try_deserialize(&mut &pool_state_info.data.borrow()[..])?;
+    require_eq!(pool_state.lp_mint,ctx.accounts.lp_mint.key(), ErrorCode::IncorrectLpMint);
 
-    require_eq!(
-        user_lock.is_unlocked,
-        false,
-        ErrorCode::LockAlreadyUnlocked
+    require!(
+        user_lock.lock_id == 0 || ctx.accounts.lock_id_index.is_some(),
+        ErrorCode::LockIdIndexRequired
     );
 
-    let block_timestamp: u64 = match clock::Clock::get() {
-        Ok(clock) => match clock.unix_timestamp.try_into() {
-            Ok(timestamp) => timestamp,
-            Err(_) => {
-                return Err(error!(ErrorCode::InvalidTimestamp));
-            }
-        },
-        Err(_) => {
-            return Err(error!(ErrorCode::ClockUnavailable));
+    settle_unlock(
+        user_lock,
+        &mut ctx.accounts.lp_lock_counter,
+        &mut ctx.accounts.pool_lock_stats,
+        &mut ctx.accounts.active_lock_page,
+        ctx.accounts
+            .reward_pool
+            .as_deref_mut()
+            .map(|reward_pool| &mut **reward_pool),
+    )?;
+    match ctx.accounts.reward_receipt.as_deref_mut() {
+        Some(reward_receipt) => reward_receipt.issue(
+            ctx.bumps.reward_receipt.ok_or(ErrorCode::RewardReceiptRequired)?,
+            user_lock.key(),
+            user_lock,
+        ),
+        None => require!(
+            ctx.accounts.reward_pool.is_none() && user_lock.rewards_owed == 0,
+            ErrorCode::RewardReceiptRequired
+        ),
+    }
+
+    let mut destinations = vec![ctx.accounts.owner_lp_token.to_account_info()];
+    let mut amounts = vec![user_lock.lock_amount];
+    if !user_lock.beneficiaries.is_empty() {
+        require_eq!(
+            ctx.remaining_accounts.len(),
+            user_lock.beneficiaries.len(),
+            ErrorCode::InvalidBeneficiaryAccount
+        );
+        for (beneficiary, beneficiary_lp_token) in
+            user_lock.beneficiaries.iter().zip(ctx.remaining_accounts.iter())
+        {
+            check_recipient_token_account(
+                beneficiary_lp_token,
+                beneficiary,
+                &ctx.accounts.lp_mint,
+                ErrorCode::InvalidBeneficiaryAccount,
+            )?;
         }
-    };
+        destinations = ctx.remaining_accounts.to_vec();
+        amounts = user_lock.unlock_payouts()?;
 
-    if block_timestamp < user_lock.unlock_time {
-        return Err(error!(ErrorCode::UnlockTimeNotReached));
+        emit!(UnlockDistributionEvent {
+            lock_id: user_lock.lock_id,
+            lp_mint: user_lock.lp_mint,
+            beneficiaries: user_lock.beneficiaries.iter().map(|b| b.recipient).collect(),
+            amounts: amounts.clone(),
+        });
     }
 
-    let pool_state_info = &ctx.accounts.pool_state;
-    let pool_state = PoolState::try_deserialize(&mut &pool_state_info.data.borrow()[..])?;
-    require_eq!(pool_state.lp_mint,ctx.accounts.lp_mint.key(), ErrorCode::IncorrectLpMint);
-
-    // update user lock
-    user_lock.is_unlocked = true;
-    user_lock.last_updated = block_timestamp;
+    for (destination, amount) in destinations.into_iter().zip(amounts) {
+        transfer_from_pool_vault_to_user(
+            ctx.accounts.lock_vault_authority.to_account_info(),
+            ctx.accounts.lp_lock_vault.to_account_info(),
+            destination,
+            ctx.accounts.lp_mint.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            amount,
+            ctx.accounts.lp_mint.decimals,
+            &[&[crate::AUTH_SEED.as_bytes(), &[ctx.bumps.lock_vault_authority]]],
+        )?;
+    }
 
-    // update lp lock counter
-   lp_lock_counter.total_lock_amount = lp_lock_counter
-    .total_lock_amount
-    .checked_sub(user_lock.lock_amount)
-    .ok_or(ErrorCode::UnderflowError)?;
-
-    transfer_from_pool_vault_to_user(
-        ctx.accounts.lock_vault_authority.to_account_info(),
-        ctx.accounts.lp_lock_vault.to_account_info(),
-        ctx.accounts.owner_lp_token.to_account_info(),
-        ctx.accounts.lp_mint.to_account_info(),
-        ctx.accounts.token_program.to_account_info(),
-        user_lock.lock_amount,
-        ctx.accounts.lp_mint.decimals,
-        &[&[crate::AUTH_SEED.as_bytes(), &[ctx.bumps.lock_vault_authority]]],
-    )?;
+    // LP sent straight to the vault is not part of the lock; sweep it to the owner so the
+    // vault can be closed
+    ctx.accounts.lp_lock_vault.reload()?;
+    let surplus = ctx.accounts.lp_lock_vault.amount;
+    if surplus > 0 {
+        transfer_from_pool_vault_to_user(
+            ctx.accounts.lock_vault_authority.to_account_info(),
+            ctx.accounts.lp_lock_vault.to_account_info(),
+            ctx.accounts.owner_lp_token.to_account_info(),
+            ctx.accounts.lp_mint.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            surplus,
+            ctx.accounts.lp_mint.decimals,
+            &[&[crate::AUTH_SEED.as_bytes(), &[ctx.bumps.lock_vault_authority]]],
+        )?;
+    }
 
     let signer_seeds: &[u8] = &[ctx.bumps.lock_vault_authority];
     let signer_seeds_arr: &[&[u8]] = &[crate::AUTH_SEED.as_bytes(), signer_seeds];
@@ -154,7 +258,7 @@ pub fn unlock_lp(ctx: Context<UnlockLp>) -> Result<()> {
     ctx.accounts.token_program.to_account_info(),
     CloseAccount {
         account: ctx.accounts.lp_lock_vault.to_account_info(),
-        destination: ctx.accounts.owner.to_account_info(),
+        destination: ctx.accounts.rent_payer.to_account_info(),
         authority: ctx.accounts.lock_vault_authority.to_account_info(),
     },
     signer_seeds_refs,);
@@ -163,6 +267,7 @@ pub fn unlock_lp(ctx: Context<UnlockLp>) -> Result<()> {
 
     emit!(
         LpUnlockEvent {
+            lock_id: user_lock.lock_id,
             user: user_lock.user,
             amount: user_lock.lock_amount,
             lp_mint: user_lock.lp_mint
@@ -171,3 +276,63 @@ pub fn unlock_lp(ctx: Context<UnlockLp>) -> Result<()> {
 
     Ok(())
 }
+
+/// Check that `user_lock` can be unlocked now, mark it unlocked and remove it
+/// from the owner's and pool's aggregates and from the reward pool, leaving its settled
+/// rewards in `rewards_owed` for a `RewardReceipt`. Fails if harvested fees are left to
+/// claim, or a fee split to remove, since the lock is closed.
+pub(crate) fn settle_unlock(
+    user_lock: &mut UserLock,
+    lp_lock_counter: &mut LpLockCounter,
+    pool_lock_stats: &mut PoolLockStats,
+    active_lock_page: &mut ActiveLockPage,
+    reward_pool: Option<&mut RewardPool>,
+) -> Result<()> {
+    require_eq!(
+        user_lock.is_locked_permanently,
+        false,
+        ErrorCode::LockIsPermanent
+    );
+
+    require_eq!(
+        user_lock.is_unlocked,
+        false,
+        ErrorCode::LockAlreadyUnlocked
+    );
+
+    let block_timestamp = block_timestamp()?;
+
+    if block_timestamp < user_lock.effective_unlock_time(block_timestamp) {
+        return Err(error!(ErrorCode::UnlockTimeNotReached));
+    }
+    user_lock.check_notice_period(block_timestamp)?;
+    user_lock.check_no_lien()?;
+    user_lock.check_not_listed()?;
+
+    lp_lock_counter.voting_power.remove_lock(user_lock, block_timestamp)?;
+    pool_lock_stats.remove_lock(user_lock, block_timestamp)?;
+
+    // update user lock
+    user_lock.is_unlocked = true;
+    user_lock.last_updated = block_timestamp;
+    active_lock_page.deactivate(user_lock)?;
+
+    match reward_pool {
+        Some(reward_pool) => reward_pool.checkpoint(user_lock, block_timestamp)?,
+        None => require_keys_eq!(
+            user_lock.reward_pool,
+            Pubkey::default(),
+            ErrorCode::RewardPoolRequired
+        ),
+    }
+    // The lock is closed once unlocked
+    user_lock.check_closable()?;
+
+    // update lp lock counter
+    lp_lock_counter.total_lock_amount = lp_lock_counter
+        .total_lock_amount
+        .checked_sub(user_lock.lock_amount)
+        .ok_or(ErrorCode::UnderflowError)?;
+
+    Ok(())
+}
@@ -0,0 +1,35 @@
+use crate::states::*;
+use crate::utils::block_timestamp;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct UpdateLockMetadata<'info> {
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = user_lp_lock.user == owner.key(),
+    )]
+    pub user_lp_lock: Box<Account<'info, UserLock>>,
+}
+
+/// Replace the label and URI of a lock. Empty strings clear them.
+pub fn update_lock_metadata(
+    ctx: Context<UpdateLockMetadata>,
+    metadata: LockMetadata,
+) -> Result<()> {
+    metadata.validate()?;
+
+    let user_lock = &mut ctx.accounts.user_lp_lock;
+    user_lock.label = metadata.label;
+    user_lock.uri = metadata.uri;
+    user_lock.last_updated = block_timestamp()?;
+
+    emit!(LockMetadataUpdatedEvent {
+        user_lock: user_lock.key(),
+        label: user_lock.label.clone(),
+        uri: user_lock.uri.clone(),
+    });
+
+    Ok(())
+}
@@ -32,6 +32,7 @@ pub mod vault_and_lp_mint_auth {
 
 pub const AUTH_SEED: &str = "lock_lp_auth_seed";
 pub const LP_LOCK_VAULT_SEED: &str = "lock_lp_vault";
+pub const LOCK_FEE_VAULT_SEED: &str = "lock_fee_vault";
 
 #[cfg(not(feature = "no-entrypoint"))]
 solana_security_txt::security_txt! {
@@ -51,29 +52,302 @@ pub mod states;
 pub mod utils;
 
 use instructions::*;
+use states::{CreatedLock, CreatorLockShare, FeeSplitRecipient, LockMetadata, RegistryStatus};
 
 #[program]
 pub mod cpmm_lp_lock {
     use super::*;
 
-    pub fn lock_lp(ctx: Context<LockLp>, amount: u64, lock_duration: u64) -> Result<()> {
+    pub fn lock_lp(ctx: Context<LockLp>, amount: u64, lock_duration: u64) -> Result<CreatedLock> {
         instructions::lock_lp(
             ctx,
             amount,
             if lock_duration == 0 { 1 } else { lock_duration },
-            false,
+            LockMode::Timed,
+            LockMetadata::default(),
+            Vec::new(),
         )
     }
 
-    pub fn lock_lp_permanent(ctx: Context<LockLp>, amount: u64) -> Result<()> {
-        instructions::lock_lp(ctx, amount, 0, true)
+    pub fn lock_lp_with_options(
+        ctx: Context<LockLp>,
+        amount: u64,
+        lock_duration: u64,
+        lock_permanent: bool,
+        metadata: LockMetadata,
+        beneficiaries: Vec<FeeSplitRecipient>,
+    ) -> Result<CreatedLock> {
+        if lock_permanent {
+            instructions::lock_lp(ctx, amount, 0, LockMode::Permanent, metadata, beneficiaries)
+        } else {
+            instructions::lock_lp(
+                ctx,
+                amount,
+                if lock_duration == 0 { 1 } else { lock_duration },
+                LockMode::Timed,
+                metadata,
+                beneficiaries,
+            )
+        }
     }
 
-    pub fn unlock_lp(ctx: Context<UnlockLp>) -> Result<()> {
+    pub fn lock_lp_from_open_time(
+        ctx: Context<LockLp>,
+        amount: u64,
+        lock_duration: u64,
+        metadata: LockMetadata,
+        beneficiaries: Vec<FeeSplitRecipient>,
+    ) -> Result<CreatedLock> {
+        instructions::lock_lp(
+            ctx,
+            amount,
+            if lock_duration == 0 { 1 } else { lock_duration },
+            LockMode::FromOpenTime,
+            metadata,
+            beneficiaries,
+        )
+    }
+
+    pub fn lock_lp_rolling(
+        ctx: Context<LockLp>,
+        amount: u64,
+        lock_duration: u64,
+        metadata: LockMetadata,
+        beneficiaries: Vec<FeeSplitRecipient>,
+    ) -> Result<CreatedLock> {
+        instructions::lock_lp(
+            ctx,
+            amount,
+            if lock_duration == 0 { 1 } else { lock_duration },
+            LockMode::Rolling,
+            metadata,
+            beneficiaries,
+        )
+    }
+
+    pub fn stop_rolling(ctx: Context<StopRolling>) -> Result<()> {
+        instructions::stop_rolling(ctx)
+    }
+
+    pub fn set_notice_period(ctx: Context<ManageUnlockNotice>, notice_period: u64) -> Result<()> {
+        instructions::set_notice_period(ctx, notice_period)
+    }
+
+    pub fn request_unlock(ctx: Context<ManageUnlockNotice>) -> Result<()> {
+        instructions::request_unlock(ctx)
+    }
+
+    pub fn cancel_unlock_request(ctx: Context<ManageUnlockNotice>) -> Result<()> {
+        instructions::cancel_unlock_request(ctx)
+    }
+
+    pub fn grant_lien(ctx: Context<GrantLien>, lienholder: Pubkey) -> Result<()> {
+        instructions::grant_lien(ctx, lienholder)
+    }
+
+    pub fn release_lien(ctx: Context<ReleaseLien>) -> Result<()> {
+        instructions::release_lien(ctx)
+    }
+
+    pub fn foreclose(ctx: Context<Foreclose>) -> Result<()> {
+        instructions::foreclose(ctx)
+    }
+
+    pub fn list_lock(ctx: Context<ListLock>, price_mint: Pubkey, price: u64) -> Result<()> {
+        instructions::list_lock(ctx, price_mint, price)
+    }
+
+    pub fn update_listing(
+        ctx: Context<UpdateListing>,
+        price_mint: Pubkey,
+        price: u64,
+    ) -> Result<()> {
+        instructions::update_listing(ctx, price_mint, price)
+    }
+
+    pub fn cancel_listing(ctx: Context<CancelListing>) -> Result<()> {
+        instructions::cancel_listing(ctx)
+    }
+
+    pub fn buy_lock(ctx: Context<BuyLock>, price_mint: Pubkey, max_price: u64) -> Result<()> {
+        instructions::buy_lock(ctx, price_mint, max_price)
+    }
+
+    pub fn lock_lp_permanent(ctx: Context<LockLp>, amount: u64) -> Result<CreatedLock> {
+        instructions::lock_lp(
+            ctx,
+            amount,
+            0,
+            LockMode::Permanent,
+            LockMetadata::default(),
+            Vec::new(),
+        )
+    }
+
+    pub fn deposit_and_lock(
+        ctx: Context<DepositAndLock>,
+        lp_token_amount: u64,
+        maximum_token_0_amount: u64,
+        maximum_token_1_amount: u64,
+        lock_duration: u64,
+        lock_permanent: bool,
+        metadata: LockMetadata,
+    ) -> Result<CreatedLock> {
+        instructions::deposit_and_lock(
+            ctx,
+            lp_token_amount,
+            maximum_token_0_amount,
+            maximum_token_1_amount,
+            if lock_duration == 0 { 1 } else { lock_duration },
+            lock_permanent,
+            metadata,
+        )
+    }
+
+    pub fn update_lock_metadata(
+        ctx: Context<UpdateLockMetadata>,
+        metadata: LockMetadata,
+    ) -> Result<()> {
+        instructions::update_lock_metadata(ctx, metadata)
+    }
+
+    pub fn unlock_lp<'info>(ctx: Context<'_, '_, '_, 'info, UnlockLp<'info>>) -> Result<()> {
         instructions::unlock_lp(ctx)
     }
 
-    pub fn collect_fees(ctx: Context<CollectFees>) -> Result<()> {
+    pub fn unlock_and_withdraw(
+        ctx: Context<UnlockAndWithdraw>,
+        minimum_token_0_amount: u64,
+        minimum_token_1_amount: u64,
+    ) -> Result<()> {
+        instructions::unlock_and_withdraw(ctx, minimum_token_0_amount, minimum_token_1_amount)
+    }
+
+    pub fn collect_fees<'info>(
+        ctx: Context<'_, '_, '_, 'info, CollectFees<'info>>,
+    ) -> Result<()> {
         instructions::collect_fees(ctx)
     }
+
+    pub fn collect_fees_as_delegate<'info>(
+        ctx: Context<'_, '_, '_, 'info, CollectFeesAsDelegate<'info>>,
+    ) -> Result<()> {
+        instructions::collect_fees_as_delegate(ctx)
+    }
+
+    pub fn collect_fees_as_lp(ctx: Context<CollectFeesAsLp>) -> Result<()> {
+        instructions::collect_fees_as_lp(ctx)
+    }
+
+    pub fn set_collect_delegate(
+        ctx: Context<SetCollectDelegate>,
+        collect_delegate: Pubkey,
+        keeper_tip_bps: u16,
+    ) -> Result<()> {
+        instructions::set_collect_delegate(ctx, collect_delegate, keeper_tip_bps)
+    }
+
+    pub fn harvest_fees(ctx: Context<HarvestFees>) -> Result<()> {
+        instructions::harvest_fees(ctx)
+    }
+
+    pub fn claim_harvested(ctx: Context<ClaimHarvested>) -> Result<()> {
+        instructions::claim_harvested(ctx)
+    }
+
+    pub fn collect_fees_single_sided<'info>(
+        ctx: Context<'_, '_, '_, 'info, CollectFeesSingleSided<'info>>,
+        output_mint: Pubkey,
+        minimum_amount_out: u64,
+    ) -> Result<()> {
+        instructions::collect_fees_single_sided(ctx, output_mint, minimum_amount_out)
+    }
+
+    pub fn sync_lock_vault(ctx: Context<SyncLockVault>, credit: bool) -> Result<()> {
+        instructions::sync_lock_vault(ctx, credit)
+    }
+
+    pub fn set_fee_split(
+        ctx: Context<SetFeeSplit>,
+        recipients: Vec<FeeSplitRecipient>,
+    ) -> Result<()> {
+        instructions::set_fee_split(ctx, recipients)
+    }
+
+    pub fn remove_fee_split(ctx: Context<RemoveFeeSplit>) -> Result<()> {
+        instructions::remove_fee_split(ctx)
+    }
+
+    pub fn initialize_registry(ctx: Context<InitializeRegistry>, admin: Pubkey) -> Result<()> {
+        instructions::initialize_registry(ctx, admin)
+    }
+
+    pub fn set_registry_admin(ctx: Context<SetRegistryAdmin>, new_admin: Pubkey) -> Result<()> {
+        instructions::set_registry_admin(ctx, new_admin)
+    }
+
+    pub fn set_registry_config(
+        ctx: Context<SetRegistryConfig>,
+        allow_list_enabled: bool,
+    ) -> Result<()> {
+        instructions::set_registry_config(ctx, allow_list_enabled)
+    }
+
+    pub fn set_registry_entry(
+        ctx: Context<SetRegistryEntry>,
+        key: Pubkey,
+        status: RegistryStatus,
+    ) -> Result<()> {
+        instructions::set_registry_entry(ctx, key, status)
+    }
+
+    pub fn initialize_reward_pool(
+        ctx: Context<InitializeRewardPool>,
+        campaign: u64,
+        emission_rate: u64,
+        end_time: u64,
+    ) -> Result<()> {
+        instructions::initialize_reward_pool(ctx, campaign, emission_rate, end_time)
+    }
+
+    pub fn sweep_reward_pool(ctx: Context<SweepRewardPool>) -> Result<()> {
+        instructions::sweep_reward_pool(ctx)
+    }
+
+    pub fn claim_rewards(ctx: Context<ClaimRewards>) -> Result<()> {
+        instructions::claim_rewards(ctx)
+    }
+
+    pub fn claim_reward_receipt(ctx: Context<ClaimRewardReceipt>) -> Result<()> {
+        instructions::claim_reward_receipt(ctx)
+    }
+
+    pub fn checkpoint_rewards(ctx: Context<CheckpointRewards>) -> Result<()> {
+        instructions::checkpoint_rewards(ctx)
+    }
+
+    pub fn get_voting_power(
+        ctx: Context<GetVotingPower>,
+        user: Pubkey,
+        lp_mint: Pubkey,
+        timestamp: u64,
+    ) -> Result<u64> {
+        instructions::get_voting_power(ctx, user, lp_mint, timestamp)
+    }
+
+    pub fn migrate_user_lock(ctx: Context<MigrateUserLock>, lock_count: u64) -> Result<()> {
+        instructions::migrate_user_lock(ctx, lock_count)
+    }
+
+    pub fn migrate_lp_lock_counter(ctx: Context<MigrateLpLockCounter>) -> Result<()> {
+        instructions::migrate_lp_lock_counter(ctx)
+    }
+
+    pub fn backfill_lock_id(ctx: Context<BackfillLockId>) -> Result<()> {
+        instructions::backfill_lock_id(ctx)
+    }
+
+    pub fn get_creator_lock_share(ctx: Context<GetCreatorLockShare>) -> Result<CreatorLockShare> {
+        instructions::get_creator_lock_share(ctx)
+    }
 }
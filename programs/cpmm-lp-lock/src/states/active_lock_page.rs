@@ -0,0 +1,167 @@
+use crate::error::ErrorCode;
+use crate::states::UserLock;
+use anchor_lang::prelude::*;
+
+pub const ACTIVE_LOCK_PAGE_SEED: &str = "active_lock_page";
+
+/// Number of locks tracked by one ActiveLockPage
+pub const LOCKS_PER_ACTIVE_LOCK_PAGE: u64 = 1024;
+
+/// Bitmap of which locks of a user for a specific LP mint are still active.
+/// Bit `i` of page `p` tracks lock_count `p * LOCKS_PER_ACTIVE_LOCK_PAGE + i + 1`, so
+/// wallets can find all active locks from `LpLockCounter.total_lock_count` and a page read.
+/// Locks the user received from another wallet are tracked in separate pages per `creator`,
+/// since their lock counts are the creator's.
+/// This account is uniquely identified by `(user, lp_mint, page)`, plus `creator` for received locks.
+#[account]
+#[derive(Default, Debug)]
+pub struct ActiveLockPage {
+    pub bump: u8,        // PDA bump for address derivation
+    pub user: Pubkey,    // Wallet that owns the tracked locks
+    pub lp_mint: Pubkey, // Mint address of the LP token being tracked
+    pub page: u64,       // Index of this page
+    pub active: [u64; (LOCKS_PER_ACTIVE_LOCK_PAGE / 64) as usize], // Bitmap of active locks
+    pub creator: Pubkey, // Wallet that created the tracked locks if not `user` (default for the user's own locks)
+}
+
+impl ActiveLockPage {
+    /// Total space required for the ActiveLockPage account (in bytes)
+    pub const LEN: usize = 8 +   // discriminator
+        1 +   // bump
+        32 +  // user
+        32 +  // lp_mint
+        8 +   // page
+        (LOCKS_PER_ACTIVE_LOCK_PAGE / 8) as usize + // active
+        32; // creator
+
+    /// Index of the page tracking `lock_count`
+    pub fn page_of(lock_count: u64) -> u64 {
+        lock_count.saturating_sub(1) / LOCKS_PER_ACTIVE_LOCK_PAGE
+    }
+
+    /// Mark a lock as active in its owner's page, initializing the page on its first use
+    pub fn activate(&mut self, bump: u8, user_lock: &UserLock) -> Result<()> {
+        if self.user == Pubkey::default() {
+            self.bump = bump;
+            self.user = user_lock.user;
+            self.lp_mint = user_lock.lp_mint;
+            self.page = Self::page_of(user_lock.lock_count);
+            if user_lock.pda_owner() != &user_lock.user {
+                self.creator = *user_lock.pda_owner();
+            }
+        }
+        let (word, bit) = self.position(user_lock.lock_count)?;
+        self.active[word] |= bit;
+        Ok(())
+    }
+
+    /// Mark a lock as no longer active
+    pub fn deactivate(&mut self, user_lock: &UserLock) -> Result<()> {
+        let (word, bit) = self.position(user_lock.lock_count)?;
+        self.active[word] &= !bit;
+        Ok(())
+    }
+
+    /// Move a lock that just changed hands from this page of its previous owner to `to`,
+    /// the page of its new owner, initializing `to` on its first use
+    pub fn move_lock(
+        &mut self,
+        to: &mut ActiveLockPage,
+        to_bump: u8,
+        user_lock: &UserLock,
+    ) -> Result<()> {
+        self.deactivate(user_lock)?;
+        to.activate(to_bump, user_lock)
+    }
+
+    /// Lock counts of the active locks tracked by this page, in ascending order
+    pub fn active_lock_counts(&self) -> Vec<u64> {
+        let first_lock_count = self.page * LOCKS_PER_ACTIVE_LOCK_PAGE + 1;
+        (0..LOCKS_PER_ACTIVE_LOCK_PAGE)
+            .filter(|i| self.active[(i / 64) as usize] & (1 << (i % 64)) != 0)
+            .map(|i| first_lock_count + i)
+            .collect()
+    }
+
+    fn position(&self, lock_count: u64) -> Result<(usize, u64)> {
+        require!(
+            lock_count > 0 && Self::page_of(lock_count) == self.page,
+            ErrorCode::IncorrectActiveLockPage
+        );
+        let i = (lock_count - 1) % LOCKS_PER_ACTIVE_LOCK_PAGE;
+        Ok(((i / 64) as usize, 1 << (i % 64)))
+    }
+}
+
+#[cfg(test)]
+pub mod active_lock_page_test {
+    use super::*;
+
+    fn new_user_lock(lock_count: u64) -> UserLock {
+        UserLock {
+            user: Pubkey::new_from_array([1; 32]),
+            creator: Pubkey::new_from_array([1; 32]),
+            lp_mint: Pubkey::new_from_array([2; 32]),
+            lock_count,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn activate_and_deactivate_track_active_locks() {
+        let mut page = ActiveLockPage::default();
+        for lock_count in [1, 2, 64, 65, 1024] {
+            page.activate(255, &new_user_lock(lock_count)).unwrap();
+        }
+        assert_eq!(page.bump, 255);
+        assert_eq!(page.user, Pubkey::new_from_array([1; 32]));
+        assert_eq!(page.page, 0);
+        assert_eq!(page.active_lock_counts(), vec![1, 2, 64, 65, 1024]);
+
+        page.deactivate(&new_user_lock(2)).unwrap();
+        page.deactivate(&new_user_lock(1024)).unwrap();
+        assert_eq!(page.active_lock_counts(), vec![1, 64, 65]);
+    }
+
+    #[test]
+    fn move_lock_tracks_received_locks_by_creator() {
+        let (creator, buyer) = (Pubkey::new_from_array([1; 32]), Pubkey::new_unique());
+        let mut user_lock = new_user_lock(5);
+        let mut creator_page = ActiveLockPage::default();
+        creator_page.activate(255, &user_lock).unwrap();
+
+        user_lock.user = buyer;
+        let mut buyer_page = ActiveLockPage::default();
+        creator_page.move_lock(&mut buyer_page, 254, &user_lock).unwrap();
+        assert!(creator_page.active_lock_counts().is_empty());
+        assert_eq!((buyer_page.user, buyer_page.creator), (buyer, creator));
+        assert_eq!(buyer_page.active_lock_counts(), vec![5]);
+
+        // back with its creator, the lock returns to the creator's own page
+        user_lock.user = creator;
+        buyer_page.move_lock(&mut creator_page, 255, &user_lock).unwrap();
+        assert!(buyer_page.active_lock_counts().is_empty());
+        assert_eq!(creator_page.creator, Pubkey::default());
+        assert_eq!(creator_page.active_lock_counts(), vec![5]);
+    }
+
+    #[test]
+    fn pages_only_track_their_own_range() {
+        assert_eq!(ActiveLockPage::page_of(1), 0);
+        assert_eq!(ActiveLockPage::page_of(1024), 0);
+        assert_eq!(ActiveLockPage::page_of(1025), 1);
+
+        let mut page = ActiveLockPage::default();
+        page.activate(255, &new_user_lock(1025)).unwrap();
+        page.activate(255, &new_user_lock(2048)).unwrap();
+        assert_eq!(page.page, 1);
+        assert_eq!(page.active_lock_counts(), vec![1025, 2048]);
+
+        for lock_count in [1024, 2049] {
+            assert_eq!(
+                page.activate(255, &new_user_lock(lock_count)).unwrap_err(),
+                ErrorCode::IncorrectActiveLockPage.into()
+            );
+        }
+    }
+}
@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+
+/// Mirror of the Raydium CPMM `AmmConfig` account, holding the fee rates of the pools
+/// created under it. Only deserialized from accounts owned by the Raydium CPMM program.
+#[account]
+#[derive(Default, Debug)]
+pub struct AmmConfig {
+    /// Bump to identify PDA
+    pub bump: u8,
+    /// Status to control if new pool can be create
+    pub disable_create_pool: bool,
+    /// Config index
+    pub index: u16,
+    /// The trade fee, denominated in hundredths of a bip (10^-6)
+    pub trade_fee_rate: u64,
+    /// The protocol fee
+    pub protocol_fee_rate: u64,
+    /// The fund fee, denominated in hundredths of a bip (10^-6)
+    pub fund_fee_rate: u64,
+    /// Fee for create a new pool
+    pub create_pool_fee: u64,
+    /// Address of the protocol fee owner
+    pub protocol_owner: Pubkey,
+    /// Address of the fund fee owner
+    pub fund_owner: Pubkey,
+    /// padding
+    pub padding: [u64; 16],
+}
@@ -1,17 +1,23 @@
+use crate::states::{FeeSplitRecipient, RegistryStatus};
 use anchor_lang::prelude::*;
 
 #[event]
 #[cfg_attr(feature = "client", derive(Debug))]
 pub struct LpLockEvent {
+    pub lock_id: u64,
     pub user: Pubkey,
     pub amount: u64,
     pub lp_mint: Pubkey,
     pub locked_perm: bool,
+    pub label: String,
+    pub uri: String,
+    pub is_creator_lock: bool,
 }
 
 #[event]
 #[cfg_attr(feature = "client", derive(Debug))]
 pub struct LpUnlockEvent {
+    pub lock_id: u64,
     pub user: Pubkey,
     pub amount: u64,
     pub lp_mint: Pubkey,
@@ -20,8 +26,301 @@ pub struct LpUnlockEvent {
 #[event]
 #[cfg_attr(feature = "client", derive(Debug))]
 pub struct CollectFeesEvent {
+    pub lock_id: u64,
     pub user: Pubkey,
     pub lp_mint: Pubkey,
     pub token_0_amount: u64,
     pub token_1_amount: u64,
 }
+
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct CollectFeesAsLpEvent {
+    pub lock_id: u64,
+    pub user: Pubkey,
+    pub lp_mint: Pubkey,
+    pub lp_amount: u64,
+}
+
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct RewardPoolInitializedEvent {
+    pub lp_mint: Pubkey,
+    pub campaign: u64,
+    pub reward_mint: Pubkey,
+    pub authority: Pubkey,
+    pub emission_rate: u64,
+    pub start_time: u64,
+    pub end_time: u64,
+    pub total_rewards: u64,
+}
+
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct RewardPoolSweptEvent {
+    pub lp_mint: Pubkey,
+    pub campaign: u64,
+    pub authority: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct ClaimRewardsEvent {
+    pub user: Pubkey,
+    pub lp_mint: Pubkey,
+    pub reward_mint: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct RewardCheckpointEvent {
+    pub lock_id: u64,
+    pub lp_mint: Pubkey,
+    pub reward_weight: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct FeeSplitPayout {
+    pub recipient: Pubkey,
+    pub token_0_amount: u64,
+    pub token_1_amount: u64,
+}
+
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct FeeSplitPayoutEvent {
+    pub user_lock: Pubkey,
+    pub lp_mint: Pubkey,
+    pub token_0_mint: Pubkey,
+    pub token_1_mint: Pubkey,
+    pub payouts: Vec<FeeSplitPayout>,
+}
+
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct FeeSplitUpdatedEvent {
+    pub user_lock: Pubkey,
+    pub recipients: Vec<FeeSplitRecipient>,
+}
+
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct CollectFeesSingleSidedEvent {
+    pub user: Pubkey,
+    pub lp_mint: Pubkey,
+    pub input_mint: Pubkey,
+    pub output_mint: Pubkey,
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub total_amount_out: u64,
+}
+
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct UnlockAndWithdrawEvent {
+    pub user: Pubkey,
+    pub lp_mint: Pubkey,
+    pub lp_amount: u64,
+    pub token_0_amount: u64,
+    pub token_1_amount: u64,
+}
+
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct DepositAndLockEvent {
+    pub user: Pubkey,
+    pub lp_mint: Pubkey,
+    pub lp_amount: u64,
+    pub token_0_amount: u64,
+    pub token_1_amount: u64,
+}
+
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct RegistryConfigUpdatedEvent {
+    pub allow_list_enabled: bool,
+}
+
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct RegistryAdminUpdatedEvent {
+    pub admin: Pubkey,
+}
+
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct RegistryEntryUpdatedEvent {
+    pub key: Pubkey,
+    pub status: RegistryStatus,
+}
+
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct SyncLockVaultEvent {
+    pub user: Pubkey,
+    pub lp_mint: Pubkey,
+    pub lock_count: u64,
+    pub surplus: u64,
+    pub credited: bool,
+}
+
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct LockMetadataUpdatedEvent {
+    pub user_lock: Pubkey,
+    pub label: String,
+    pub uri: String,
+}
+
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct StopRollingEvent {
+    pub lock_id: u64,
+    pub user_lock: Pubkey,
+    pub unlock_time: u64,
+}
+
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct CollectDelegateUpdatedEvent {
+    pub user_lock: Pubkey,
+    pub collect_delegate: Pubkey,
+    pub keeper_tip_bps: u16,
+}
+
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct KeeperTipEvent {
+    pub lock_id: u64,
+    pub keeper: Pubkey,
+    pub lp_amount: u64,
+}
+
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct HarvestFeesEvent {
+    pub lock_id: u64,
+    pub harvester: Pubkey,
+    pub lp_mint: Pubkey,
+    pub token_0_amount: u64,
+    pub token_1_amount: u64,
+}
+
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct ClaimHarvestedEvent {
+    pub lock_id: u64,
+    pub user: Pubkey,
+    pub token_0_amount: u64,
+    pub token_1_amount: u64,
+}
+
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct NoticePeriodUpdatedEvent {
+    pub lock_id: u64,
+    pub user_lock: Pubkey,
+    pub notice_period: u64,
+}
+
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct UnlockRequestedEvent {
+    pub lock_id: u64,
+    pub user_lock: Pubkey,
+    pub user: Pubkey,
+    pub lp_mint: Pubkey,
+    pub lock_amount: u64,
+    pub requested_at: u64,
+    pub unlockable_at: u64,
+}
+
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct UnlockRequestCancelledEvent {
+    pub lock_id: u64,
+    pub user_lock: Pubkey,
+    pub lp_mint: Pubkey,
+}
+
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct LienGrantedEvent {
+    pub lock_id: u64,
+    pub user_lock: Pubkey,
+    pub user: Pubkey,
+    pub lienholder: Pubkey,
+}
+
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct LienReleasedEvent {
+    pub lock_id: u64,
+    pub user_lock: Pubkey,
+    pub lienholder: Pubkey,
+}
+
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct LockForeclosedEvent {
+    pub lock_id: u64,
+    pub user_lock: Pubkey,
+    pub previous_owner: Pubkey,
+    pub new_owner: Pubkey,
+    pub lienholder: Pubkey,
+}
+
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct LockListedEvent {
+    pub lock_id: u64,
+    pub user_lock: Pubkey,
+    pub seller: Pubkey,
+    pub price_mint: Pubkey,
+    pub price: u64,
+}
+
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct LockListingUpdatedEvent {
+    pub user_lock: Pubkey,
+    pub price_mint: Pubkey,
+    pub price: u64,
+}
+
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct LockListingCancelledEvent {
+    pub lock_id: u64,
+    pub user_lock: Pubkey,
+}
+
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct LockSoldEvent {
+    pub lock_id: u64,
+    pub user_lock: Pubkey,
+    pub seller: Pubkey,
+    pub buyer: Pubkey,
+    pub price_mint: Pubkey,
+    pub price: u64,
+}
+
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct UnlockDistributionEvent {
+    pub lock_id: u64,
+    pub lp_mint: Pubkey,
+    pub beneficiaries: Vec<Pubkey>,
+    pub amounts: Vec<u64>,
+}
+
+#[event]
+#[cfg_attr(feature = "client", derive(Debug))]
+pub struct AccountMigratedEvent {
+    pub account: Pubkey,
+    pub version: u8,
+}
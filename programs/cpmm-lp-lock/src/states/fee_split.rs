@@ -0,0 +1,145 @@
+use crate::error::ErrorCode;
+use crate::utils::BASIS_POINTS_DENOMINATOR;
+use anchor_lang::prelude::*;
+
+pub const FEE_SPLIT_SEED: &str = "fee_split";
+
+/// Maximum number of recipients a lock's collected fees can be split between
+pub const MAX_FEE_SPLIT_RECIPIENTS: usize = 5;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, Debug, PartialEq)]
+pub struct FeeSplitRecipient {
+    pub recipient: Pubkey, // Wallet receiving this share of the fees
+    pub bps: u16,          // Share of the fees in basis points
+}
+
+/// Fee-split configuration of a specific lock.
+/// Fees collected from the lock are paid to the recipients by their bps shares,
+/// which sum to `BASIS_POINTS_DENOMINATOR`.
+/// This account is uniquely identified by `user_lock`.
+#[account]
+#[derive(Default, Debug)]
+pub struct FeeSplit {
+    pub bump: u8,                           // PDA bump for address derivation
+    pub user_lock: Pubkey,                  // Lock whose fees are split
+    pub recipients: Vec<FeeSplitRecipient>, // Recipients and their bps shares
+}
+
+impl FeeSplit {
+    /// Total space required for the FeeSplit account (in bytes)
+    pub const LEN: usize = 8 +   // discriminator
+        1 +   // bump
+        32 +  // user_lock
+        4 + (32 + 2) * MAX_FEE_SPLIT_RECIPIENTS; // recipients
+
+    /// Split `amount` between the recipients by their bps shares.
+    /// Rounding dust goes to the last recipient so the payouts always sum to `amount`.
+    pub fn split_amount(&self, amount: u64) -> Result<Vec<u64>> {
+        split_by_shares(&self.recipients, amount)
+    }
+}
+
+/// Split `amount` between `recipients` by their bps shares, with the rounding dust
+/// going to the last recipient.
+pub fn split_by_shares(recipients: &[FeeSplitRecipient], amount: u64) -> Result<Vec<u64>> {
+    let mut payouts = Vec::with_capacity(recipients.len());
+    let mut remaining = amount;
+    for (i, recipient) in recipients.iter().enumerate() {
+        let payout = if i + 1 == recipients.len() {
+            remaining
+        } else {
+            (u128::from(amount) * u128::from(recipient.bps)
+                / u128::from(BASIS_POINTS_DENOMINATOR)) as u64
+        };
+        remaining = remaining
+            .checked_sub(payout)
+            .ok_or(ErrorCode::UnderflowError)?;
+        payouts.push(payout);
+    }
+    Ok(payouts)
+}
+
+/// Validate a fee-split configuration: 1 to `MAX_FEE_SPLIT_RECIPIENTS` distinct recipients
+/// with non-zero shares summing to `BASIS_POINTS_DENOMINATOR`.
+pub fn validate_fee_split(recipients: &[FeeSplitRecipient]) -> Result<()> {
+    validate_shares(recipients, MAX_FEE_SPLIT_RECIPIENTS, ErrorCode::InvalidFeeSplit)
+}
+
+/// Validate 1 to `max_recipients` distinct recipients with non-zero shares summing to
+/// `BASIS_POINTS_DENOMINATOR`, failing with `error` otherwise.
+pub fn validate_shares(
+    recipients: &[FeeSplitRecipient],
+    max_recipients: usize,
+    error: ErrorCode,
+) -> Result<()> {
+    let total_bps: u64 = recipients.iter().map(|r| u64::from(r.bps)).sum();
+    let valid = !recipients.is_empty()
+        && recipients.len() <= max_recipients
+        && total_bps == BASIS_POINTS_DENOMINATOR
+        && recipients.iter().enumerate().all(|(i, recipient)| {
+            recipient.bps > 0
+                && !recipients[..i]
+                    .iter()
+                    .any(|other| other.recipient == recipient.recipient)
+        });
+    if !valid {
+        return Err(error.into());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+pub mod fee_split_test {
+    use super::*;
+
+    fn new_recipients(shares: &[u16]) -> Vec<FeeSplitRecipient> {
+        shares
+            .iter()
+            .map(|&bps| FeeSplitRecipient {
+                recipient: Pubkey::new_unique(),
+                bps,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn split_amount_assigns_dust_to_last_recipient() {
+        let fee_split = FeeSplit {
+            recipients: new_recipients(&[3_333, 3_333, 3_334]),
+            ..Default::default()
+        };
+        assert_eq!(fee_split.split_amount(100).unwrap(), vec![33, 33, 34]);
+        assert_eq!(fee_split.split_amount(10).unwrap(), vec![3, 3, 4]);
+        assert_eq!(fee_split.split_amount(0).unwrap(), vec![0, 0, 0]);
+        let payouts = fee_split.split_amount(u64::MAX).unwrap();
+        assert_eq!(
+            payouts.iter().map(|&p| u128::from(p)).sum::<u128>(),
+            u128::from(u64::MAX)
+        );
+    }
+
+    #[test]
+    fn validate_fee_split_rejects_bad_configs() {
+        assert!(validate_fee_split(&new_recipients(&[5_000, 3_000, 2_000])).is_ok());
+        assert!(validate_fee_split(&new_recipients(&[10_000])).is_ok());
+
+        for shares in [
+            &[][..],
+            &[5_000, 4_999][..],
+            &[10_000, 0][..],
+            &[2_000; 6][..],
+        ] {
+            assert_eq!(
+                validate_fee_split(&new_recipients(shares)).unwrap_err(),
+                ErrorCode::InvalidFeeSplit.into()
+            );
+        }
+
+        let mut duplicated = new_recipients(&[5_000, 5_000]);
+        duplicated[1].recipient = duplicated[0].recipient;
+        assert_eq!(
+            validate_fee_split(&duplicated).unwrap_err(),
+            ErrorCode::InvalidFeeSplit.into()
+        );
+    }
+}
@@ -0,0 +1,153 @@
+use crate::states::{LpLockCounter, UserLock};
+use anchor_lang::prelude::*;
+
+/// Layout of `UserLock` as written by the first release, before the account carried a version.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default, Debug)]
+pub struct UserLockV0 {
+    pub bump: u8,
+    pub user: Pubkey,
+    pub lp_mint: Pubkey,
+    pub lock_count: u64,
+    pub lock_amount: u64,
+    pub unlock_time: u64,
+    pub principal_token_0: u64,
+    pub principal_token_1: u64,
+    pub principal_liquidity: u64,
+    pub is_locked_permanently: bool,
+    pub token_0_fees_collected: u64,
+    pub token_1_fees_collected: u64,
+    pub is_unlocked: bool,
+    pub last_updated: u64,
+    pub created_at: u64,
+}
+
+impl UserLockV0 {
+    /// Space taken by the legacy UserLock account (in bytes)
+    pub const LEN: usize = 8 + 1 + 32 + 32 + 8 * 6 + 1 + 8 + 8 + 1 + 8 + 8;
+}
+
+impl From<UserLockV0> for UserLock {
+    fn from(legacy: UserLockV0) -> Self {
+        let lock_duration = if legacy.is_locked_permanently {
+            0
+        } else {
+            legacy.unlock_time.saturating_sub(legacy.created_at)
+        };
+        UserLock {
+            bump: legacy.bump,
+            user: legacy.user,
+            lp_mint: legacy.lp_mint,
+            lock_count: legacy.lock_count,
+            lock_amount: legacy.lock_amount,
+            unlock_time: legacy.unlock_time,
+            principal_token_0: legacy.principal_token_0,
+            principal_token_1: legacy.principal_token_1,
+            principal_liquidity: legacy.principal_liquidity,
+            is_locked_permanently: legacy.is_locked_permanently,
+            token_0_fees_collected: legacy.token_0_fees_collected,
+            token_1_fees_collected: legacy.token_1_fees_collected,
+            is_unlocked: legacy.is_unlocked,
+            last_updated: legacy.last_updated,
+            created_at: legacy.created_at,
+            // Legacy locks were always created, and paid for, by their owner
+            rent_payer: legacy.user,
+            creator: legacy.user,
+            start_time: legacy.created_at,
+            lock_duration,
+            version: UserLock::VERSION,
+            ..Default::default()
+        }
+    }
+}
+
+/// Layout of `LpLockCounter` as written by the first release, before the account carried a version.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default, Debug)]
+pub struct LpLockCounterV0 {
+    pub user: Pubkey,
+    pub lp_mint: Pubkey,
+    pub total_lock_count: u64,
+    pub total_lock_amount: u64,
+}
+
+impl LpLockCounterV0 {
+    /// Space taken by the legacy LpLockCounter account (in bytes)
+    pub const LEN: usize = 8 + 32 + 32 + 8 + 8;
+}
+
+impl From<LpLockCounterV0> for LpLockCounter {
+    fn from(legacy: LpLockCounterV0) -> Self {
+        LpLockCounter {
+            user: legacy.user,
+            lp_mint: legacy.lp_mint,
+            total_lock_count: legacy.total_lock_count,
+            total_lock_amount: legacy.total_lock_amount,
+            version: LpLockCounter::VERSION,
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod legacy_test {
+    use super::*;
+
+    #[test]
+    fn user_lock_v0_round_trips_into_current_layout() {
+        let legacy = UserLockV0 {
+            bump: 254,
+            user: Pubkey::new_unique(),
+            lp_mint: Pubkey::new_unique(),
+            lock_count: 2,
+            lock_amount: 1_000,
+            unlock_time: 5_000,
+            principal_liquidity: 900,
+            last_updated: 1_500,
+            created_at: 1_000,
+            ..Default::default()
+        };
+        let mut data = UserLock::DISCRIMINATOR.to_vec();
+        legacy.serialize(&mut data).unwrap();
+        assert_eq!(data.len(), UserLockV0::LEN);
+
+        let legacy = UserLockV0::deserialize(&mut &data[8..]).unwrap();
+        let user_lock = UserLock::from(legacy.clone());
+        assert_eq!(user_lock.lock_amount, 1_000);
+        assert_eq!((user_lock.start_time, user_lock.lock_duration), (1_000, 4_000));
+        assert_eq!(user_lock.creator, legacy.user);
+        assert_eq!(user_lock.rent_payer, legacy.user);
+        assert_eq!(user_lock.version, UserLock::VERSION);
+
+        let mut migrated = Vec::new();
+        user_lock.try_serialize(&mut migrated).unwrap();
+        assert!(migrated.len() <= UserLock::LEN);
+        assert_eq!(migrated[..UserLockV0::LEN], data[..]);
+    }
+
+    #[test]
+    fn permanent_user_lock_v0_has_no_duration() {
+        let user_lock = UserLock::from(UserLockV0 {
+            is_locked_permanently: true,
+            created_at: 1_000,
+            ..Default::default()
+        });
+        assert_eq!(user_lock.lock_duration, 0);
+    }
+
+    #[test]
+    fn lp_lock_counter_v0_keeps_totals() {
+        let legacy = LpLockCounterV0 {
+            user: Pubkey::new_unique(),
+            lp_mint: Pubkey::new_unique(),
+            total_lock_count: 3,
+            total_lock_amount: 7_000,
+        };
+        let mut data = LpLockCounter::DISCRIMINATOR.to_vec();
+        legacy.serialize(&mut data).unwrap();
+        assert_eq!(data.len(), LpLockCounterV0::LEN);
+
+        let counter = LpLockCounter::from(LpLockCounterV0::deserialize(&mut &data[8..]).unwrap());
+        assert_eq!((counter.total_lock_count, counter.total_lock_amount), (3, 7_000));
+        assert_eq!(counter.version, LpLockCounter::VERSION);
+        assert_eq!(counter.voting_power.voting_power_at(0, 0).unwrap(), 0);
+    }
+}
@@ -1,9 +1,46 @@
+use crate::error::ErrorCode;
+use crate::states::{split_by_shares, validate_shares, FeeSplitRecipient};
+use crate::utils::U128;
 use anchor_lang::prelude::*;
 
 pub const USER_LOCK_SEED: &str = "user_lock";
 
+/// Longest duration (in seconds) a non-permanent lock can be created for
+pub const MAX_LOCK_DURATION: u64 = 157_680_000;
+
+/// Largest share (in basis points) of collected fees a collect delegate can take as a tip
+pub const MAX_KEEPER_TIP_BPS: u16 = 500;
+
+/// Maximum number of beneficiaries a lock's principal can be split between on unlock
+pub const MAX_UNLOCK_BENEFICIARIES: usize = 5;
+
+/// Longest notice period (in seconds) a lock can require between `request_unlock` and unlocking
+pub const MAX_NOTICE_PERIOD: u64 = 31_536_000;
+
+/// Longest label (in UTF-8 bytes) a lock can carry
+pub const MAX_LOCK_LABEL_LEN: usize = 64;
+
+/// Longest metadata URI (in bytes) a lock can carry
+pub const MAX_LOCK_URI_LEN: usize = 200;
+
+/// Human-readable label and metadata URI given to a lock by its owner
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default, Debug, PartialEq)]
+pub struct LockMetadata {
+    pub label: String, // Name shown for the lock, e.g. "Team allocation - 24 month lock"
+    pub uri: String,   // Link to off-chain metadata of the lock
+}
+
+/// Lock created by `lock_lp`, `deposit_and_lock` and their variants, set as return data
+/// so that programs locking through CPI learn where their lock lives
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, Debug, PartialEq)]
+pub struct CreatedLock {
+    pub user_lock: Pubkey, // Address of the new lock
+    pub lock_count: u64,   // Index of the lock among the owner's locks of the LP mint
+    pub lock_id: u64,      // Program-wide sequential ID of the lock
+}
+
 /// Stores information about a specific LP token lock created by a user.
-/// Each lock is uniquely identified by `(user, lp_mint, lock_count)`.
+/// Each lock is uniquely identified by `(creator, lp_mint, lock_count)`.
 #[account]
 #[derive(Default, Debug)]
 pub struct UserLock {
@@ -22,9 +59,40 @@ pub struct UserLock {
     pub is_unlocked: bool, // Flag indicating whether this lock has already been unlocked
     pub last_updated: u64, // Last update timestamp (useful for syncing/indexing)
     pub created_at: u64, // Timestamp when the lock was created
+    pub reward_weight: u64, // Weight of this lock in the LP mint's reward pool at its last checkpoint
+    pub reward_per_weight_paid: u128, // Reward pool accumulator value at the last checkpoint
+    pub rewards_owed: u64, // Settled rewards not yet claimed
+    pub has_fee_split: bool, // True if collected fees are split according to the lock's FeeSplit
+    pub lock_id: u64,        // Program-wide sequential ID of the lock
+    pub rent_payer: Pubkey,  // Account that paid rent for the lock vault and receives it back on close
+    pub start_time: u64, // Timestamp the lock duration runs from: creation, or the pool open time if later
+    pub lock_duration: u64, // Duration the lock was created with (0 if permanent)
+    pub is_rolling: bool, // True while the lock renews continually and stays `lock_duration` from unlocking
+    pub collect_delegate: Pubkey, // Keeper allowed to collect fees on the owner's behalf (default if none)
+    pub keeper_tip_bps: u16, // Share of collected fees, in LP, paid to the collect delegate
+    pub is_creator_lock: bool, // True if the owner was the pool creator when the lock was created
+    pub notice_period: u64, // Seconds between `request_unlock` and unlocking (0 if no notice is required)
+    pub unlock_requested_at: u64, // Timestamp of the pending unlock request (0 if none)
+    pub creator: Pubkey, // Wallet that created the lock; seeds its PDAs even if `user` changes
+    pub lienholder: Pubkey, // Program or PDA holding a lien on the lock (default if none)
+    pub is_listed: bool, // True while the lock is escrowed in a sale listing
+    pub harvest_pending: bool, // True while harvested fees wait in the lock's fee vaults for `claim_harvested`
+    pub reward_pool: Pubkey, // Reward pool the lock is enrolled in by its owner (default if none)
+    pub reward_checkpoint_time: u64, // Timestamp of the lock's last reward checkpoint
+    pub reward_decays_until: u64, // Unlock time the checkpointed reward weight decays towards (0 if it does not decay)
+    pub harvester: Pubkey, // Account that paid rent for the lock's fee vaults and receives it back when they close (default if none)
+    pub version: u8, // Layout version of the account, see `UserLock::VERSION`
+    // Variable-length fields come last so that every field above sits at a fixed offset
+    pub label: String, // Optional human-readable name of the lock (empty if unset)
+    pub uri: String,   // Optional link to off-chain metadata of the lock (empty if unset)
+    pub beneficiaries: Vec<FeeSplitRecipient>, // Shares of the principal paid out on unlock (empty: all to the owner)
 }
 
 impl UserLock {
+    /// Current layout version. Locks written by the first release carry no version and are
+    /// brought up to date with `migrate_user_lock`.
+    pub const VERSION: u8 = 1;
+
     /// Total space required for the UserLock account (in bytes)
     pub const LEN: usize = 8 +   // discriminator
         1 +   // bump
@@ -41,5 +109,444 @@ impl UserLock {
         8 +   // token_1_fees_collected
         1 +   // is_unlocked
         8 +   // last_updated
-        8; // created_at
+        8 +   // created_at
+        8 +   // reward_weight
+        16 +  // reward_per_weight_paid
+        8 +   // rewards_owed
+        1 +   // has_fee_split
+        8 +   // lock_id
+        32 +  // rent_payer
+        8 +   // start_time
+        8 +   // lock_duration
+        1 +   // is_rolling
+        32 +  // collect_delegate
+        2 +   // keeper_tip_bps
+        1 +   // is_creator_lock
+        8 +   // notice_period
+        8 +   // unlock_requested_at
+        32 +  // creator
+        32 +  // lienholder
+        1 +   // is_listed
+        1 +   // harvest_pending
+        32 +  // reward_pool
+        8 +   // reward_checkpoint_time
+        8 +   // reward_decays_until
+        32 +  // harvester
+        1 +   // version
+        4 + MAX_LOCK_LABEL_LEN + // label
+        4 + MAX_LOCK_URI_LEN + // uri
+        4 + (32 + 2) * MAX_UNLOCK_BENEFICIARIES; // beneficiaries
+}
+
+impl UserLock {
+    /// Unix timestamp after which the lock can be unlocked, as seen at `now`.
+    /// A rolling lock is always a full `lock_duration` from unlocking.
+    pub fn effective_unlock_time(&self, now: u64) -> u64 {
+        if self.is_rolling {
+            now.saturating_add(self.lock_duration)
+        } else {
+            self.unlock_time
+        }
+    }
+
+    /// Unix timestamp after which an unlock requested at `requested_at` can go through.
+    pub fn unlockable_at(&self, requested_at: u64) -> u64 {
+        self.effective_unlock_time(requested_at)
+            .max(requested_at.saturating_add(self.notice_period))
+    }
+
+    /// Wallet whose key seeds the lock's PDAs: its `creator`, or `user` for locks
+    /// created before `creator` was recorded, which cannot have changed hands since.
+    pub fn pda_owner(&self) -> &Pubkey {
+        if self.creator == Pubkey::default() {
+            &self.user
+        } else {
+            &self.creator
+        }
+    }
+
+    /// Last seed of the active-lock page tracking the lock while `holder` owns it: empty for
+    /// locks `holder` created, whose pages keep their original address, else the creator's key.
+    pub fn active_lock_page_seed(&self, holder: &Pubkey) -> &[u8] {
+        let pda_owner = self.pda_owner();
+        if pda_owner == holder {
+            &[]
+        } else {
+            pda_owner.as_ref()
+        }
+    }
+
+    /// Account refunded the rent of the lock's accounts: its `rent_payer`, or `user` for
+    /// locks created before `rent_payer` was recorded, which were always paid for by their owner.
+    pub fn rent_recipient(&self) -> &Pubkey {
+        if self.rent_payer == Pubkey::default() {
+            &self.user
+        } else {
+            &self.rent_payer
+        }
+    }
+
+    /// Add the fees a collection actually paid out, net of any keeper tip and transfer fee,
+    /// to the lock's running totals.
+    pub fn record_fees_collected(&mut self, token_0_amount: u64, token_1_amount: u64) -> Result<()> {
+        self.token_0_fees_collected = self
+            .token_0_fees_collected
+            .checked_add(token_0_amount)
+            .ok_or(ErrorCode::Overflow)?;
+        self.token_1_fees_collected = self
+            .token_1_fees_collected
+            .checked_add(token_1_amount)
+            .ok_or(ErrorCode::Overflow)?;
+        Ok(())
+    }
+
+    /// Check that the notice period, if any, has run out on a pending unlock request.
+    pub fn check_notice_period(&self, now: u64) -> Result<()> {
+        if self.notice_period == 0 {
+            return Ok(());
+        }
+        require!(self.unlock_requested_at != 0, ErrorCode::UnlockNotRequested);
+        require!(
+            now >= self.unlock_requested_at.saturating_add(self.notice_period),
+            ErrorCode::NoticePeriodNotElapsed
+        );
+        Ok(())
+    }
+
+    /// Check that an unlock requested at `now` falls within the notice period preceding
+    /// the unlock time, so the request cannot be parked early to skip the notice.
+    pub fn check_unlock_request(&self, now: u64) -> Result<()> {
+        require!(
+            now >= self
+                .effective_unlock_time(now)
+                .saturating_sub(self.notice_period),
+            ErrorCode::UnlockRequestTooEarly
+        );
+        Ok(())
+    }
+
+    /// Check that no lien encumbers the lock, so its principal can leave the owner.
+    pub fn check_no_lien(&self) -> Result<()> {
+        require_keys_eq!(self.lienholder, Pubkey::default(), ErrorCode::LockHasLien);
+        Ok(())
+    }
+
+    /// Check that the lock is not escrowed in a sale listing.
+    pub fn check_not_listed(&self) -> Result<()> {
+        require_eq!(self.is_listed, false, ErrorCode::LockIsListed);
+        Ok(())
+    }
+
+    /// Check that no fee split or unlock beneficiaries, set by the current owner and paying
+    /// out to parties of its choosing, would follow the lock to a new owner.
+    pub fn check_transferable(&self) -> Result<()> {
+        require_eq!(self.has_fee_split, false, ErrorCode::LockHasFeeSplit);
+        require!(self.beneficiaries.is_empty(), ErrorCode::LockHasBeneficiaries);
+        Ok(())
+    }
+
+    /// Check that nothing tied to the lock is left for the owner to claim, so that the lock
+    /// can be closed once unlocked. Owed rewards move to a `RewardReceipt` instead.
+    pub fn check_closable(&self) -> Result<()> {
+        require_eq!(self.harvest_pending, false, ErrorCode::UnclaimedHarvestedFees);
+        require_eq!(self.has_fee_split, false, ErrorCode::LockHasFeeSplit);
+        Ok(())
+    }
+
+    /// Split the locked LP, whose underlying tokens are now worth `liquidity`, into the LP
+    /// still backing the principal and the LP that represents accrued fees.
+    pub fn split_fee_lp_tokens(&self, liquidity: u64) -> Result<(u64, u64)> {
+        require_gt!(liquidity, 0, ErrorCode::ZeroLiquidity);
+        let principal_lp_tokens = U128::from(self.principal_liquidity)
+            .checked_mul(self.lock_amount.into())
+            .ok_or(ErrorCode::Overflow)?
+            / U128::from(liquidity);
+        // The pool lost value since the lock was opened, so there are no fees to take
+        let principal_lp_tokens = u64::try_from(principal_lp_tokens)
+            .map_err(|_| error!(ErrorCode::PrincipalExceedsValue))?;
+        let fee_lp_tokens = self
+            .lock_amount
+            .checked_sub(principal_lp_tokens)
+            .ok_or(ErrorCode::PrincipalExceedsValue)?;
+        require_gt!(fee_lp_tokens, 0, ErrorCode::ZeroLpTokensToBurn);
+        Ok((principal_lp_tokens, fee_lp_tokens))
+    }
+
+    /// LP amounts paid to each beneficiary on unlock, in beneficiary order.
+    pub fn unlock_payouts(&self) -> Result<Vec<u64>> {
+        split_by_shares(&self.beneficiaries, self.lock_amount)
+    }
+}
+
+/// Validate an unlock distribution table given at lock time. Empty means all to the owner.
+pub fn validate_beneficiaries(beneficiaries: &[FeeSplitRecipient]) -> Result<()> {
+    if beneficiaries.is_empty() {
+        return Ok(());
+    }
+    validate_shares(
+        beneficiaries,
+        MAX_UNLOCK_BENEFICIARIES,
+        ErrorCode::InvalidBeneficiaries,
+    )
+}
+
+impl LockMetadata {
+    /// Validate the label and URI: a label of at most `MAX_LOCK_LABEL_LEN` bytes without
+    /// control characters or surrounding whitespace, and a URI of at most `MAX_LOCK_URI_LEN`
+    /// printable ASCII characters. Both may be empty.
+    pub fn validate(&self) -> Result<()> {
+        let (label, uri) = (&self.label, &self.uri);
+        require!(
+            label.len() <= MAX_LOCK_LABEL_LEN
+                && !label.chars().any(char::is_control)
+                && label.trim() == label,
+            ErrorCode::InvalidLockMetadata
+        );
+        require!(
+            uri.len() <= MAX_LOCK_URI_LEN && uri.bytes().all(|b| b.is_ascii_graphic()),
+            ErrorCode::InvalidLockMetadata
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub mod lock_test {
+    use super::*;
+
+    #[test]
+    fn unlock_payouts_follow_beneficiary_shares() {
+        let beneficiaries: Vec<FeeSplitRecipient> = [6_000, 4_000]
+            .iter()
+            .map(|&bps| FeeSplitRecipient {
+                recipient: Pubkey::new_unique(),
+                bps,
+            })
+            .collect();
+        assert!(validate_beneficiaries(&beneficiaries).is_ok());
+        assert!(validate_beneficiaries(&[]).is_ok());
+        assert_eq!(
+            validate_beneficiaries(&beneficiaries[..1]).unwrap_err(),
+            ErrorCode::InvalidBeneficiaries.into()
+        );
+
+        let user_lock = UserLock {
+            lock_amount: 1_001,
+            beneficiaries,
+            ..Default::default()
+        };
+        assert_eq!(user_lock.unlock_payouts().unwrap(), vec![600, 401]);
+    }
+
+    #[test]
+    fn transferable_only_without_fee_split_or_beneficiaries() {
+        let mut user_lock = UserLock::default();
+        assert!(user_lock.check_transferable().is_ok());
+
+        user_lock.has_fee_split = true;
+        assert_eq!(
+            user_lock.check_transferable().unwrap_err(),
+            ErrorCode::LockHasFeeSplit.into()
+        );
+
+        user_lock.has_fee_split = false;
+        user_lock.beneficiaries = vec![FeeSplitRecipient {
+            recipient: Pubkey::new_unique(),
+            bps: 10_000,
+        }];
+        assert_eq!(
+            user_lock.check_transferable().unwrap_err(),
+            ErrorCode::LockHasBeneficiaries.into()
+        );
+    }
+
+    #[test]
+    fn split_fee_lp_tokens_rejects_lost_value() {
+        let user_lock = UserLock {
+            lock_amount: 1_000,
+            principal_liquidity: 1_000,
+            ..Default::default()
+        };
+        assert_eq!(user_lock.split_fee_lp_tokens(1_250).unwrap(), (800, 200));
+        assert_eq!(
+            user_lock.split_fee_lp_tokens(0).unwrap_err(),
+            ErrorCode::ZeroLiquidity.into()
+        );
+        assert_eq!(
+            user_lock.split_fee_lp_tokens(1_000).unwrap_err(),
+            ErrorCode::ZeroLpTokensToBurn.into()
+        );
+        assert_eq!(
+            user_lock.split_fee_lp_tokens(999).unwrap_err(),
+            ErrorCode::PrincipalExceedsValue.into()
+        );
+        // Too little value left for the principal to even fit in a u64
+        let user_lock = UserLock {
+            lock_amount: u64::MAX,
+            principal_liquidity: u64::MAX,
+            ..Default::default()
+        };
+        assert_eq!(
+            user_lock.split_fee_lp_tokens(1).unwrap_err(),
+            ErrorCode::PrincipalExceedsValue.into()
+        );
+    }
+
+    #[test]
+    fn closable_only_without_unclaimed_balances() {
+        let mut user_lock = UserLock::default();
+        user_lock.check_closable().unwrap();
+
+        // owed rewards are paid out through a receipt and never hold the lock
+        user_lock.rewards_owed = 1;
+        user_lock.check_closable().unwrap();
+        user_lock.harvest_pending = true;
+        assert_eq!(
+            user_lock.check_closable().unwrap_err(),
+            ErrorCode::UnclaimedHarvestedFees.into()
+        );
+        user_lock.harvest_pending = false;
+        user_lock.has_fee_split = true;
+        assert_eq!(
+            user_lock.check_closable().unwrap_err(),
+            ErrorCode::LockHasFeeSplit.into()
+        );
+    }
+
+    #[test]
+    fn rent_goes_back_to_owner_without_recorded_payer() {
+        let user = Pubkey::new_unique();
+        let mut user_lock = UserLock {
+            user,
+            ..Default::default()
+        };
+        assert_eq!(*user_lock.rent_recipient(), user);
+
+        let rent_payer = Pubkey::new_unique();
+        user_lock.rent_payer = rent_payer;
+        assert_eq!(*user_lock.rent_recipient(), rent_payer);
+    }
+
+    #[test]
+    fn fees_collected_accumulate_and_reject_overflow() {
+        let mut user_lock = UserLock::default();
+        user_lock.record_fees_collected(100, 7).unwrap();
+        user_lock.record_fees_collected(50, 0).unwrap();
+        assert_eq!(
+            (user_lock.token_0_fees_collected, user_lock.token_1_fees_collected),
+            (150, 7)
+        );
+
+        assert_eq!(
+            user_lock.record_fees_collected(0, u64::MAX).unwrap_err(),
+            ErrorCode::Overflow.into()
+        );
+    }
+
+    #[test]
+    fn effective_unlock_time_renews_while_rolling() {
+        let mut user_lock = UserLock {
+            unlock_time: 1_000 + 86_400,
+            lock_duration: 86_400,
+            is_rolling: true,
+            ..Default::default()
+        };
+        assert_eq!(user_lock.effective_unlock_time(1_000), 1_000 + 86_400);
+        assert_eq!(user_lock.effective_unlock_time(50_000), 50_000 + 86_400);
+
+        user_lock.is_rolling = false;
+        user_lock.unlock_time = 50_000 + 86_400;
+        assert_eq!(user_lock.effective_unlock_time(100_000), 50_000 + 86_400);
+    }
+
+    #[test]
+    fn unlock_waits_for_notice_period() {
+        let mut user_lock = UserLock {
+            unlock_time: 10_000,
+            ..Default::default()
+        };
+        assert!(user_lock.check_notice_period(0).is_ok());
+
+        user_lock.notice_period = 3_600;
+        assert_eq!(
+            user_lock.check_notice_period(20_000).unwrap_err(),
+            ErrorCode::UnlockNotRequested.into()
+        );
+
+        user_lock.unlock_requested_at = 20_000;
+        assert_eq!(user_lock.unlockable_at(20_000), 23_600);
+        assert_eq!(
+            user_lock.check_notice_period(23_599).unwrap_err(),
+            ErrorCode::NoticePeriodNotElapsed.into()
+        );
+        assert!(user_lock.check_notice_period(23_600).is_ok());
+
+        // A request made early still has to wait for the unlock time
+        assert_eq!(user_lock.unlockable_at(1_000), 10_000);
+    }
+
+    #[test]
+    fn unlock_request_opens_one_notice_period_before_unlock_time() {
+        let mut user_lock = UserLock {
+            unlock_time: 10_000,
+            notice_period: 3_600,
+            ..Default::default()
+        };
+        assert_eq!(
+            user_lock.check_unlock_request(6_399).unwrap_err(),
+            ErrorCode::UnlockRequestTooEarly.into()
+        );
+        assert!(user_lock.check_unlock_request(6_400).is_ok());
+        assert!(user_lock.check_unlock_request(20_000).is_ok());
+
+        // A rolling lock is never within reach of its unlock time
+        user_lock.is_rolling = true;
+        user_lock.lock_duration = 86_400;
+        assert_eq!(
+            user_lock.check_unlock_request(20_000).unwrap_err(),
+            ErrorCode::UnlockRequestTooEarly.into()
+        );
+    }
+
+    fn new_metadata(label: &str, uri: &str) -> LockMetadata {
+        LockMetadata {
+            label: label.to_string(),
+            uri: uri.to_string(),
+        }
+    }
+
+    #[test]
+    fn validate_accepts_bounded_metadata() {
+        for (label, uri) in [
+            (String::new(), String::new()),
+            (
+                "Team allocation \u{2013} 24 month lock".to_string(),
+                "https://example.com/locks/1.json".to_string(),
+            ),
+            ("\u{e9}".repeat(MAX_LOCK_LABEL_LEN / 2), String::new()),
+            (String::new(), "a".repeat(MAX_LOCK_URI_LEN)),
+        ] {
+            assert!(new_metadata(&label, &uri).validate().is_ok());
+        }
+    }
+
+    #[test]
+    fn validate_rejects_bad_metadata() {
+        for (label, uri) in [
+            ("a".repeat(MAX_LOCK_LABEL_LEN + 1), String::new()),
+            // Length is bounded in bytes, not characters
+            ("\u{e9}".repeat(MAX_LOCK_LABEL_LEN / 2 + 1), String::new()),
+            ("Team\nallocation".to_string(), String::new()),
+            ("Team\u{0}".to_string(), String::new()),
+            (" Team".to_string(), String::new()),
+            (String::new(), "a".repeat(MAX_LOCK_URI_LEN + 1)),
+            (String::new(), "https://example.com/a b".to_string()),
+            (String::new(), "https://example.com/\u{e9}".to_string()),
+        ] {
+            assert_eq!(
+                new_metadata(&label, &uri).validate().unwrap_err(),
+                ErrorCode::InvalidLockMetadata.into()
+            );
+        }
+    }
 }
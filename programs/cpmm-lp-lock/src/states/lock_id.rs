@@ -0,0 +1,67 @@
+use crate::error::ErrorCode;
+use anchor_lang::prelude::*;
+
+pub const GLOBAL_LOCK_COUNTER_SEED: &str = "global_lock_counter";
+pub const LOCK_ID_SEED: &str = "lock_id";
+
+/// Assigns every new lock a program-wide ID, starting at 1.
+/// This account is a singleton PDA.
+#[account]
+#[derive(Default, Debug)]
+pub struct GlobalLockCounter {
+    pub bump: u8,              // PDA bump for address derivation
+    pub total_lock_count: u64, // Number of locks created across the program, i.e. the last assigned ID
+}
+
+impl GlobalLockCounter {
+    /// Total space required for the GlobalLockCounter account (in bytes)
+    pub const LEN: usize = 8 +   // discriminator
+        1 +   // bump
+        8; // total_lock_count
+
+    /// Assign the next lock ID
+    pub fn next_lock_id(&mut self) -> Result<u64> {
+        self.total_lock_count = self
+            .total_lock_count
+            .checked_add(1)
+            .ok_or(ErrorCode::Overflow)?;
+        Ok(self.total_lock_count)
+    }
+}
+
+/// Maps a program-wide lock ID to the address of its UserLock.
+/// This account is uniquely identified by `lock_id`.
+#[account]
+#[derive(Default, Debug)]
+pub struct LockIdIndex {
+    pub bump: u8,          // PDA bump for address derivation
+    pub lock_id: u64,      // Program-wide ID of the lock
+    pub user_lock: Pubkey, // Address of the UserLock with this ID
+}
+
+impl LockIdIndex {
+    /// Total space required for the LockIdIndex account (in bytes)
+    pub const LEN: usize = 8 +   // discriminator
+        1 +   // bump
+        8 +   // lock_id
+        32; // user_lock
+}
+
+#[cfg(test)]
+pub mod lock_id_test {
+    use super::*;
+
+    #[test]
+    fn next_lock_id_is_sequential_from_one() {
+        let mut global_lock_counter = GlobalLockCounter::default();
+        assert_eq!(global_lock_counter.next_lock_id().unwrap(), 1);
+        assert_eq!(global_lock_counter.next_lock_id().unwrap(), 2);
+        assert_eq!(global_lock_counter.total_lock_count, 2);
+
+        global_lock_counter.total_lock_count = u64::MAX;
+        assert_eq!(
+            global_lock_counter.next_lock_id().unwrap_err(),
+            ErrorCode::Overflow.into()
+        );
+    }
+}
@@ -0,0 +1,72 @@
+use crate::error::ErrorCode;
+use anchor_lang::prelude::*;
+
+pub const LOCK_LISTING_SEED: &str = "lock_listing";
+
+/// Sale listing of a lock. The lock is escrowed while listed: it cannot be unlocked,
+/// encumbered or have its fee split changed until the listing is cancelled or bought.
+/// This account is uniquely identified by `user_lock`.
+#[account]
+#[derive(Default, Debug)]
+pub struct LockListing {
+    pub bump: u8,           // PDA bump for address derivation
+    pub user_lock: Pubkey,  // Lock for sale
+    pub seller: Pubkey,     // Owner of the lock when it was listed, paid on sale
+    pub price_mint: Pubkey, // Mint the ask price is paid in (default for SOL)
+    pub price: u64,         // Ask price, in lamports or base units of `price_mint`
+    pub listed_at: u64,     // Timestamp the lock was listed
+}
+
+impl LockListing {
+    /// Total space required for the LockListing account (in bytes)
+    pub const LEN: usize = 8 +   // discriminator
+        1 +   // bump
+        32 +  // user_lock
+        32 +  // seller
+        32 +  // price_mint
+        8 +   // price
+        8; // listed_at
+
+    /// Set the ask price, in SOL if `price_mint` is `Pubkey::default()`
+    pub fn set_price(&mut self, price_mint: Pubkey, price: u64) -> Result<()> {
+        require_gt!(price, 0, ErrorCode::InvalidListingPrice);
+        self.price_mint = price_mint;
+        self.price = price;
+        Ok(())
+    }
+
+    /// Check the ask against the price the buyer agreed to, so a listing updated
+    /// after the buyer saw it is not bought at the new price
+    pub fn check_price(&self, price_mint: Pubkey, max_price: u64) -> Result<()> {
+        require!(
+            self.price_mint == price_mint && self.price <= max_price,
+            ErrorCode::ListingPriceChanged
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub mod lock_listing_test {
+    use super::*;
+
+    #[test]
+    fn buyer_pays_at_most_the_agreed_price() {
+        let usdc = Pubkey::new_unique();
+        let mut lock_listing = LockListing::default();
+        assert_eq!(
+            lock_listing.set_price(usdc, 0).unwrap_err(),
+            ErrorCode::InvalidListingPrice.into()
+        );
+        lock_listing.set_price(usdc, 1_000).unwrap();
+
+        assert!(lock_listing.check_price(usdc, 1_000).is_ok());
+        assert!(lock_listing.check_price(usdc, 2_000).is_ok());
+        for (price_mint, max_price) in [(usdc, 999), (Pubkey::default(), 1_000)] {
+            assert_eq!(
+                lock_listing.check_price(price_mint, max_price).unwrap_err(),
+                ErrorCode::ListingPriceChanged.into()
+            );
+        }
+    }
+}
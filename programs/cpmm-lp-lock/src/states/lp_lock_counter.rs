@@ -1,3 +1,5 @@
+use crate::error::ErrorCode;
+use crate::states::{PoolLockStats, UserLock, VotingPowerCheckpoint};
 use anchor_lang::prelude::*;
 
 pub const LP_LOCK_COUNTER_SEED: &str = "lp_lock_counter";
@@ -11,13 +13,186 @@ pub struct LpLockCounter {
     pub lp_mint: Pubkey,        // Mint address of the LP token being tracked
     pub total_lock_count: u64, // Total number of lock positions created by this user for the given LP
     pub total_lock_amount: u64, // Cumulative LP tokens locked by this user for the given LP
+    pub voting_power: VotingPowerCheckpoint, // Aggregate voting power of this user's active locks
+    pub version: u8, // Layout version of the account, see `LpLockCounter::VERSION`
 }
 
 impl LpLockCounter {
+    /// Current layout version. Counters written by the first release carry no version and
+    /// are brought up to date with `migrate_lp_lock_counter`.
+    pub const VERSION: u8 = 1;
+
     /// Total space required for the LpLockCounter account (in bytes)
     pub const LEN: usize = 8 +   // discriminator
         32 +  // user
         32 +  // lp_mint
         8 +   // total_lock_count
-        8; // total_lock_amount
+        8 +   // total_lock_amount
+        VotingPowerCheckpoint::LEN + // voting_power
+        1; // version
+
+    /// Set up a counter created in the same transaction, leaving an existing one untouched
+    pub fn initialize_if_needed(&mut self, user: Pubkey, lp_mint: Pubkey) {
+        if self.user == Pubkey::default() {
+            self.user = user;
+            self.lp_mint = lp_mint;
+            self.version = Self::VERSION;
+        }
+    }
+
+    /// Hand an active lock over from this counter's user to `to`'s user, moving its
+    /// amount and voting power with it and clearing the previous owner's delegate and
+    /// unlock request. The lock no longer counts as locked by the pool creator in
+    /// `pool_lock_stats`. `total_lock_count` is left alone on both sides
+    /// since the lock keeps the PDAs of its creator.
+    pub fn transfer_lock(
+        &mut self,
+        to: &mut LpLockCounter,
+        pool_lock_stats: &mut PoolLockStats,
+        user_lock: &mut UserLock,
+        timestamp: u64,
+    ) -> Result<()> {
+        require_eq!(user_lock.is_unlocked, false, ErrorCode::LockAlreadyUnlocked);
+
+        let stats_voting_power = pool_lock_stats.voting_power;
+        self.voting_power.remove_lock(user_lock, timestamp)?;
+        pool_lock_stats.remove_lock(user_lock, timestamp)?;
+        self.total_lock_amount = self
+            .total_lock_amount
+            .checked_sub(user_lock.lock_amount)
+            .ok_or(ErrorCode::UnderflowError)?;
+
+        // The lock keeps deriving its PDAs from the key it was created under
+        if user_lock.creator == Pubkey::default() {
+            user_lock.creator = user_lock.user;
+        }
+        // Settings made by the previous owner do not carry over
+        user_lock.user = to.user;
+        user_lock.collect_delegate = Pubkey::default();
+        user_lock.keeper_tip_bps = 0;
+        user_lock.unlock_requested_at = 0;
+        user_lock.is_creator_lock = false;
+
+        to.voting_power.add_lock(user_lock, timestamp)?;
+        pool_lock_stats.add_lock(user_lock, timestamp)?;
+        // The pool's voting power does not depend on who owns the lock
+        pool_lock_stats
+            .voting_power
+            .keep_timestamp_if_unchanged(&stats_voting_power);
+        to.total_lock_amount = to
+            .total_lock_amount
+            .checked_add(user_lock.lock_amount)
+            .ok_or(ErrorCode::Overflow)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub mod lp_lock_counter_test {
+    use super::*;
+
+    #[test]
+    fn transfer_lock_moves_amount_and_voting_power() {
+        let (from_user, to_user) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mut user_lock = UserLock {
+            user: from_user,
+            creator: from_user,
+            lock_amount: 1_000,
+            is_locked_permanently: true,
+            collect_delegate: Pubkey::new_unique(),
+            keeper_tip_bps: 100,
+            ..Default::default()
+        };
+        let mut from = LpLockCounter {
+            user: from_user,
+            total_lock_count: 3,
+            ..Default::default()
+        };
+        from.voting_power.add_lock(&user_lock, 100).unwrap();
+        from.total_lock_amount = 1_500;
+        let mut to = LpLockCounter {
+            user: to_user,
+            ..Default::default()
+        };
+
+        let mut pool_lock_stats = PoolLockStats::default();
+        pool_lock_stats.add_lock(&user_lock, 100).unwrap();
+        from.transfer_lock(&mut to, &mut pool_lock_stats, &mut user_lock, 200).unwrap();
+
+        assert_eq!(user_lock.user, to_user);
+        assert_eq!(user_lock.creator, from_user);
+        assert_eq!(user_lock.collect_delegate, Pubkey::default());
+        assert_eq!(user_lock.keeper_tip_bps, 0);
+        assert_eq!((from.total_lock_amount, to.total_lock_amount), (500, 1_000));
+        assert_eq!((from.total_lock_count, to.total_lock_count), (3, 0));
+        assert_eq!(from.voting_power.voting_power_at(200, 0).unwrap(), 0);
+        assert_eq!(to.voting_power.voting_power_at(200, 0).unwrap(), 1_000);
+        assert_eq!(
+            pool_lock_stats.voting_power.voting_power_at(200, 0).unwrap(),
+            1_000
+        );
+    }
+
+    #[test]
+    fn transfer_lock_records_creator_of_older_locks() {
+        let (from_user, to_user) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mut user_lock = UserLock {
+            user: from_user,
+            lock_amount: 1_000,
+            ..Default::default()
+        };
+        assert_eq!(*user_lock.pda_owner(), from_user);
+
+        let mut from = LpLockCounter {
+            user: from_user,
+            total_lock_amount: 1_000,
+            ..Default::default()
+        };
+        from.voting_power.add_lock(&user_lock, 0).unwrap();
+        let mut to = LpLockCounter {
+            user: to_user,
+            ..Default::default()
+        };
+        let mut pool_lock_stats = PoolLockStats::default();
+        pool_lock_stats.add_lock(&user_lock, 0).unwrap();
+        from.transfer_lock(&mut to, &mut pool_lock_stats, &mut user_lock, 0).unwrap();
+
+        assert_eq!(user_lock.user, to_user);
+        assert_eq!(*user_lock.pda_owner(), from_user);
+    }
+
+    #[test]
+    fn transfer_lock_drops_creator_lock() {
+        let (creator, buyer) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mut user_lock = UserLock {
+            user: creator,
+            creator,
+            lock_amount: 1_000,
+            is_locked_permanently: true,
+            is_creator_lock: true,
+            ..Default::default()
+        };
+        let mut from = LpLockCounter {
+            user: creator,
+            total_lock_amount: 1_000,
+            ..Default::default()
+        };
+        from.voting_power.add_lock(&user_lock, 0).unwrap();
+        let mut pool_lock_stats = PoolLockStats::default();
+        pool_lock_stats.add_lock(&user_lock, 0).unwrap();
+        assert_eq!(pool_lock_stats.creator_locked_amount, 1_000);
+
+        let mut to = LpLockCounter {
+            user: buyer,
+            ..Default::default()
+        };
+        from.transfer_lock(&mut to, &mut pool_lock_stats, &mut user_lock, 0).unwrap();
+
+        assert!(!user_lock.is_creator_lock);
+        assert_eq!(pool_lock_stats.creator_locked_amount, 0);
+        assert_eq!(
+            pool_lock_stats.voting_power.voting_power_at(0, 0).unwrap(),
+            1_000
+        );
+    }
 }
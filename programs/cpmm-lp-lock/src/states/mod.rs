@@ -9,3 +9,33 @@ pub use lp_lock_counter::*;
 
 pub mod lock;
 pub use lock::*;
+
+pub mod reward_pool;
+pub use reward_pool::*;
+
+pub mod voting_power;
+pub use voting_power::*;
+
+pub mod pool_lock_stats;
+pub use pool_lock_stats::*;
+
+pub mod fee_split;
+pub use fee_split::*;
+
+pub mod amm_config;
+pub use amm_config::*;
+
+pub mod registry;
+pub use registry::*;
+
+pub mod lock_id;
+pub use lock_id::*;
+
+pub mod active_lock_page;
+pub use active_lock_page::*;
+
+pub mod lock_listing;
+pub use lock_listing::*;
+
+pub mod legacy;
+pub use legacy::*;
@@ -1,3 +1,4 @@
+use crate::error::ErrorCode;
 use anchor_lang::prelude::*;
 use anchor_spl::token_interface::Mint;
 use std::ops::{BitAnd, BitOr, BitXor};
@@ -94,7 +95,7 @@ impl PoolState {
         token_1_mint: &InterfaceAccount<Mint>,
         lp_mint: &InterfaceAccount<Mint>,
         observation_key: Pubkey,
-    ) {
+    ) -> Result<()> {
         self.amm_config = amm_config.key();
         self.pool_creator = pool_creator.key();
         self.token_0_vault = token_0_vault;
@@ -115,8 +116,11 @@ impl PoolState {
         self.fund_fees_token_0 = 0;
         self.fund_fees_token_1 = 0;
         self.open_time = open_time;
-        self.recent_epoch = Clock::get().unwrap().epoch;
+        self.recent_epoch = Clock::get()
+            .map_err(|_| error!(ErrorCode::ClockUnavailable))?
+            .epoch;
         self.padding = [0u64; 31];
+        Ok(())
     }
 
     pub fn set_status(&mut self, status: u8) {
@@ -139,23 +143,49 @@ impl PoolState {
         self.status.bitand(status) == 0
     }
 
-    pub fn vault_amount_without_fee(&self, vault_0: u64, vault_1: u64) -> (u64, u64) {
-        (
+    /// Check that `bit` is in its normal status, failing with the error dedicated to it,
+    /// so that callers stop before Raydium rejects the CPI with an unrelated one.
+    pub fn require_enabled(&self, bit: PoolStatusBitIndex) -> Result<()> {
+        let error = match bit {
+            PoolStatusBitIndex::Deposit => ErrorCode::PoolDepositDisabled,
+            PoolStatusBitIndex::Withdraw => ErrorCode::PoolWithdrawDisabled,
+            PoolStatusBitIndex::Swap => ErrorCode::PoolSwapDisabled,
+        };
+        if self.get_status_by_bit(bit) {
+            Ok(())
+        } else {
+            Err(error.into())
+        }
+    }
+
+    /// Vault balances net of the protocol and fund fees the pool has yet to claim.
+    pub fn vault_amount_without_fee(&self, vault_0: u64, vault_1: u64) -> Result<(u64, u64)> {
+        let fees_token_0 = self
+            .protocol_fees_token_0
+            .checked_add(self.fund_fees_token_0)
+            .ok_or(ErrorCode::Overflow)?;
+        let fees_token_1 = self
+            .protocol_fees_token_1
+            .checked_add(self.fund_fees_token_1)
+            .ok_or(ErrorCode::Overflow)?;
+        Ok((
             vault_0
-                .checked_sub(self.protocol_fees_token_0 + self.fund_fees_token_0)
-                .unwrap(),
+                .checked_sub(fees_token_0)
+                .ok_or(ErrorCode::PoolFeesExceedVault)?,
             vault_1
-                .checked_sub(self.protocol_fees_token_1 + self.fund_fees_token_1)
-                .unwrap(),
-        )
+                .checked_sub(fees_token_1)
+                .ok_or(ErrorCode::PoolFeesExceedVault)?,
+        ))
     }
 
-    pub fn token_price_x32(&self, vault_0: u64, vault_1: u64) -> (u128, u128) {
-        let (token_0_amount, token_1_amount) = self.vault_amount_without_fee(vault_0, vault_1);
-        (
+    pub fn token_price_x32(&self, vault_0: u64, vault_1: u64) -> Result<(u128, u128)> {
+        let (token_0_amount, token_1_amount) = self.vault_amount_without_fee(vault_0, vault_1)?;
+        require_gt!(token_0_amount, 0, ErrorCode::ZeroLiquidity);
+        require_gt!(token_1_amount, 0, ErrorCode::ZeroLiquidity);
+        Ok((
             token_1_amount as u128 * Q32 as u128 / token_0_amount as u128,
             token_0_amount as u128 * Q32 as u128 / token_1_amount as u128,
-        )
+        ))
     }
 }
 
@@ -244,4 +274,59 @@ pub mod pool_test {
             );
         }
     }
+
+    #[test]
+    fn require_enabled_fails_with_the_error_of_each_bit() {
+        let mut pool_state = PoolState::default();
+        assert!(pool_state.require_enabled(PoolStatusBitIndex::Deposit).is_ok());
+        assert!(pool_state.require_enabled(PoolStatusBitIndex::Withdraw).is_ok());
+        assert!(pool_state.require_enabled(PoolStatusBitIndex::Swap).is_ok());
+
+        pool_state.set_status(1); // deposit disabled
+        assert_eq!(
+            pool_state.require_enabled(PoolStatusBitIndex::Deposit).unwrap_err(),
+            ErrorCode::PoolDepositDisabled.into()
+        );
+        assert!(pool_state.require_enabled(PoolStatusBitIndex::Withdraw).is_ok());
+
+        pool_state.set_status(2); // withdraw disabled
+        assert_eq!(
+            pool_state.require_enabled(PoolStatusBitIndex::Withdraw).unwrap_err(),
+            ErrorCode::PoolWithdrawDisabled.into()
+        );
+        assert!(pool_state.require_enabled(PoolStatusBitIndex::Swap).is_ok());
+
+        pool_state.set_status(4); // swap disabled
+        assert_eq!(
+            pool_state.require_enabled(PoolStatusBitIndex::Swap).unwrap_err(),
+            ErrorCode::PoolSwapDisabled.into()
+        );
+        assert!(pool_state.require_enabled(PoolStatusBitIndex::Deposit).is_ok());
+    }
+
+    #[test]
+    fn vault_amount_without_fee_rejects_fees_above_vault() {
+        let pool_state = PoolState {
+            protocol_fees_token_0: 60,
+            fund_fees_token_0: 40,
+            protocol_fees_token_1: 5,
+            ..Default::default()
+        };
+        assert_eq!(
+            pool_state.vault_amount_without_fee(1_100, 1_005).unwrap(),
+            (1_000, 1_000)
+        );
+        assert_eq!(
+            pool_state.vault_amount_without_fee(99, 1_005).unwrap_err(),
+            ErrorCode::PoolFeesExceedVault.into()
+        );
+        assert_eq!(
+            pool_state.vault_amount_without_fee(1_100, 4).unwrap_err(),
+            ErrorCode::PoolFeesExceedVault.into()
+        );
+        assert_eq!(
+            pool_state.token_price_x32(100, 1_005).unwrap_err(),
+            ErrorCode::ZeroLiquidity.into()
+        );
+    }
 }
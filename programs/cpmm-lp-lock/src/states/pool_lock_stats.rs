@@ -0,0 +1,119 @@
+use crate::error::ErrorCode;
+use crate::states::{UserLock, VotingPowerCheckpoint};
+use crate::utils::BASIS_POINTS_DENOMINATOR;
+use anchor_lang::prelude::*;
+
+pub const POOL_LOCK_STATS_SEED: &str = "pool_lock_stats";
+
+/// Aggregates the locks of all users for a specific LP mint.
+/// This account is uniquely identified by `lp_mint`.
+#[account]
+#[derive(Default, Debug)]
+pub struct PoolLockStats {
+    pub bump: u8,                            // PDA bump for address derivation
+    pub lp_mint: Pubkey,                     // Mint address of the LP token being tracked
+    pub voting_power: VotingPowerCheckpoint, // Aggregate voting power of all active locks
+    pub creator_locked_amount: u64,          // LP locked in active locks of the pool creator
+}
+
+impl PoolLockStats {
+    /// Total space required for the PoolLockStats account (in bytes)
+    pub const LEN: usize = 8 +   // discriminator
+        1 +   // bump
+        32 +  // lp_mint
+        VotingPowerCheckpoint::LEN + // voting_power
+        8; // creator_locked_amount
+
+    /// Add the contribution of `user_lock` to the aggregates.
+    pub fn add_lock(&mut self, user_lock: &UserLock, timestamp: u64) -> Result<()> {
+        self.voting_power.add_lock(user_lock, timestamp)?;
+        if user_lock.is_creator_lock && !user_lock.is_unlocked {
+            self.creator_locked_amount = self
+                .creator_locked_amount
+                .checked_add(user_lock.lock_amount)
+                .ok_or(ErrorCode::Overflow)?;
+        }
+        Ok(())
+    }
+
+    /// Remove the contribution of `user_lock` from the aggregates.
+    /// Must be called with the lock's state as it was last added.
+    pub fn remove_lock(&mut self, user_lock: &UserLock, timestamp: u64) -> Result<()> {
+        self.voting_power.remove_lock(user_lock, timestamp)?;
+        if user_lock.is_creator_lock && !user_lock.is_unlocked {
+            self.creator_locked_amount = self
+                .creator_locked_amount
+                .checked_sub(user_lock.lock_amount)
+                .ok_or(ErrorCode::UnderflowError)?;
+        }
+        Ok(())
+    }
+}
+
+/// LP locked by the pool creator, returned by `get_creator_lock_share`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, Debug, PartialEq)]
+pub struct CreatorLockShare {
+    pub pool_creator: Pubkey,       // Creator of the pool
+    pub creator_locked_amount: u64, // LP locked in the creator's active locks
+    pub lp_supply: u64,             // LP supply of the pool
+    pub share_bps: u16,             // Creator-locked share of the LP supply, in basis points
+}
+
+impl CreatorLockShare {
+    pub fn new(pool_creator: Pubkey, creator_locked_amount: u64, lp_supply: u64) -> Self {
+        let share_bps = if lp_supply == 0 {
+            0
+        } else {
+            (u128::from(creator_locked_amount) * u128::from(BASIS_POINTS_DENOMINATOR)
+                / u128::from(lp_supply))
+            .min(u128::from(BASIS_POINTS_DENOMINATOR)) as u16
+        };
+        Self {
+            pool_creator,
+            creator_locked_amount,
+            lp_supply,
+            share_bps,
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod pool_lock_stats_test {
+    use super::*;
+
+    #[test]
+    fn creator_locked_amount_tracks_creator_locks_only() {
+        let mut stats = PoolLockStats::default();
+        let mut creator_lock = UserLock {
+            lock_amount: 1_000,
+            is_locked_permanently: true,
+            is_creator_lock: true,
+            ..Default::default()
+        };
+        let other_lock = UserLock {
+            lock_amount: 500,
+            is_locked_permanently: true,
+            ..Default::default()
+        };
+        stats.add_lock(&creator_lock, 0).unwrap();
+        stats.add_lock(&other_lock, 0).unwrap();
+        assert_eq!(stats.creator_locked_amount, 1_000);
+
+        stats.remove_lock(&creator_lock, 0).unwrap();
+        creator_lock.lock_amount = 900;
+        stats.add_lock(&creator_lock, 0).unwrap();
+        assert_eq!(stats.creator_locked_amount, 900);
+
+        stats.remove_lock(&creator_lock, 0).unwrap();
+        assert_eq!(stats.creator_locked_amount, 0);
+    }
+
+    #[test]
+    fn creator_lock_share_is_bounded_bps() {
+        let creator = Pubkey::new_unique();
+        assert_eq!(CreatorLockShare::new(creator, 250, 1_000).share_bps, 2_500);
+        assert_eq!(CreatorLockShare::new(creator, 1, 3).share_bps, 3_333);
+        assert_eq!(CreatorLockShare::new(creator, 0, 0).share_bps, 0);
+        assert_eq!(CreatorLockShare::new(creator, 2_000, 1_000).share_bps, 10_000);
+    }
+}
@@ -0,0 +1,151 @@
+use crate::error::ErrorCode;
+use anchor_lang::prelude::*;
+
+pub const REGISTRY_CONFIG_SEED: &str = "registry_config";
+pub const REGISTRY_ENTRY_SEED: &str = "registry_entry";
+
+/// Admin verdict on a pool id or token mint
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub enum RegistryStatus {
+    #[default]
+    Unlisted, // No verdict, subject to the default rules
+    Allowed, // Vetted by the admin
+    Denied,  // Blocked by the admin
+}
+
+/// Global settings of the admin-managed pool registry.
+/// This account is a singleton PDA.
+#[account]
+#[derive(Default, Debug)]
+pub struct RegistryConfig {
+    pub bump: u8,                 // PDA bump for address derivation
+    pub allow_list_enabled: bool, // Whether only explicitly allowed pools can be locked
+    pub admin: Pubkey,            // Key allowed to manage the registry, appointed by the upgrade authority
+}
+
+impl RegistryConfig {
+    /// Total space required for the RegistryConfig account (in bytes)
+    pub const LEN: usize = 8 +   // discriminator
+        1 +   // bump
+        1 +   // allow_list_enabled
+        32; // admin
+
+    /// Read whether the allow-list is enabled from the registry config address; before the
+    /// admin first configures the registry, the address is uninitialized and every pool is allowed
+    pub fn load_allow_list_enabled(config_info: &AccountInfo) -> Result<bool> {
+        if config_info.data_is_empty() {
+            return Ok(false);
+        }
+        let config = RegistryConfig::try_deserialize(&mut &config_info.data.borrow()[..])?;
+        Ok(config.allow_list_enabled)
+    }
+}
+
+/// Admin verdict on a specific pool id or token mint.
+/// This account is uniquely identified by `key`.
+#[account]
+#[derive(Default, Debug)]
+pub struct RegistryEntry {
+    pub bump: u8,               // PDA bump for address derivation
+    pub key: Pubkey,            // Pool id or token mint the verdict applies to
+    pub status: RegistryStatus, // Verdict on the key
+}
+
+impl RegistryEntry {
+    /// Total space required for the RegistryEntry account (in bytes)
+    pub const LEN: usize = 8 +   // discriminator
+        1 +   // bump
+        32 +  // key
+        1; // status
+
+    /// Read the verdict stored at a registry entry address; an address that was never
+    /// initialized holds no verdict
+    pub fn load_status(entry_info: &AccountInfo) -> Result<RegistryStatus> {
+        if entry_info.data_is_empty() {
+            return Ok(RegistryStatus::Unlisted);
+        }
+        let entry = RegistryEntry::try_deserialize(&mut &entry_info.data.borrow()[..])?;
+        Ok(entry.status)
+    }
+}
+
+/// Check that a pool may be locked given the registry verdicts on the pool and its two mints.
+/// `mints_supported` holds the Token-2022 extension check result of each mint.
+pub fn check_pool_lockable(
+    allow_list_enabled: bool,
+    pool_status: RegistryStatus,
+    mint_statuses: [RegistryStatus; 2],
+    mints_supported: [bool; 2],
+) -> Result<()> {
+    require!(pool_status != RegistryStatus::Denied, ErrorCode::PoolDenied);
+    for (mint_status, mint_supported) in mint_statuses.into_iter().zip(mints_supported) {
+        require!(mint_status != RegistryStatus::Denied, ErrorCode::MintDenied);
+        require!(
+            mint_supported || mint_status == RegistryStatus::Allowed,
+            ErrorCode::NotSupportMint
+        );
+    }
+    if allow_list_enabled {
+        require!(
+            pool_status == RegistryStatus::Allowed,
+            ErrorCode::PoolNotAllowed
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+pub mod registry_test {
+    use super::*;
+    use RegistryStatus::*;
+
+    #[test]
+    fn check_pool_lockable_applies_verdicts() {
+        assert!(check_pool_lockable(false, Unlisted, [Unlisted; 2], [true; 2]).is_ok());
+        assert!(check_pool_lockable(true, Allowed, [Unlisted; 2], [true; 2]).is_ok());
+        // An allowed mint overrides the extension check
+        assert!(check_pool_lockable(false, Unlisted, [Allowed, Unlisted], [false, true]).is_ok());
+
+        for (allow_list_enabled, pool_status, mint_statuses, mints_supported, error) in [
+            (
+                false,
+                Denied,
+                [Allowed; 2],
+                [true; 2],
+                ErrorCode::PoolDenied,
+            ),
+            (
+                false,
+                Allowed,
+                [Unlisted, Denied],
+                [true; 2],
+                ErrorCode::MintDenied,
+            ),
+            (
+                false,
+                Unlisted,
+                [Unlisted; 2],
+                [true, false],
+                ErrorCode::NotSupportMint,
+            ),
+            (
+                true,
+                Unlisted,
+                [Allowed; 2],
+                [true; 2],
+                ErrorCode::PoolNotAllowed,
+            ),
+        ] {
+            assert_eq!(
+                check_pool_lockable(
+                    allow_list_enabled,
+                    pool_status,
+                    mint_statuses,
+                    mints_supported
+                )
+                .unwrap_err(),
+                error.into()
+            );
+        }
+    }
+}
@@ -0,0 +1,454 @@
+use crate::error::ErrorCode;
+use crate::states::{UserLock, MAX_LOCK_DURATION};
+use crate::utils::BASIS_POINTS_DENOMINATOR;
+use anchor_lang::prelude::*;
+
+pub const REWARD_POOL_SEED: &str = "reward_pool";
+pub const REWARD_VAULT_SEED: &str = "reward_vault";
+pub const REWARD_RECEIPT_SEED: &str = "reward_receipt";
+
+/// Fixed-point scale of `RewardPool::reward_per_weight_stored`.
+pub const REWARD_PRECISION: u128 = 1_000_000_000_000;
+
+/// Weight multiplier (bps) of a lock with no remaining lock duration.
+pub const BASE_REWARD_MULTIPLIER_BPS: u64 = 10_000;
+
+/// Weight multiplier (bps) of a permanent lock, or a lock with `MAX_LOCK_DURATION` remaining.
+pub const MAX_REWARD_MULTIPLIER_BPS: u64 = 30_000;
+
+/// Liquidity-mining campaign for the lockers of a specific LP mint.
+/// Emissions are shared pro-rata to each lock's weight, which is `lock_amount`
+/// scaled by its remaining lock duration, averaged over the time since the lock's last checkpoint.
+/// This account is uniquely identified by `(lp_mint, campaign)`.
+#[account]
+#[derive(Default, Debug)]
+pub struct RewardPool {
+    pub bump: u8,                       // PDA bump for address derivation
+    pub lp_mint: Pubkey,                // LP mint whose lockers earn rewards
+    pub campaign: u64,                  // Index distinguishing successive campaigns of the same LP mint
+    pub reward_mint: Pubkey,            // Mint of the reward token
+    pub reward_vault: Pubkey,           // Program-owned vault holding undistributed rewards
+    pub authority: Pubkey,              // Project that funded the pool
+    pub emission_rate: u64,             // Reward tokens emitted per second
+    pub start_time: u64,                // Unix timestamp emissions started
+    pub end_time: u64,                  // Unix timestamp emissions stop
+    pub last_update_time: u64,          // Timestamp `reward_per_weight_stored` was last accrued to
+    pub reward_per_weight_stored: u128, // Accumulated rewards per unit of weight, scaled by REWARD_PRECISION
+    pub total_weight: u128, // Sum of the checkpointed weights of all participating locks
+    pub carried_rewards: u64, // Emissions from periods with no participating weight, paid out with the next accrual
+    pub reward_liability: u64, // Rewards accrued to locks and not yet claimed; the rest of the vault can be swept
+}
+
+impl RewardPool {
+    /// Total space required for the RewardPool account (in bytes)
+    pub const LEN: usize = 8 +   // discriminator
+        1 +   // bump
+        32 +  // lp_mint
+        8 +   // campaign
+        32 +  // reward_mint
+        32 +  // reward_vault
+        32 +  // authority
+        8 +   // emission_rate
+        8 +   // start_time
+        8 +   // end_time
+        8 +   // last_update_time
+        16 +  // reward_per_weight_stored
+        16 +  // total_weight
+        8 +   // carried_rewards
+        8; // reward_liability
+
+    /// Accrue emissions between `last_update_time` and `now` (capped at `end_time`).
+    /// Emissions for periods with no participating weight are carried forward and shared
+    /// among the weight present at the next accrual.
+    pub fn update(&mut self, now: u64) -> Result<()> {
+        let accrue_until = now.min(self.end_time);
+        let mut pending = u128::from(self.carried_rewards);
+        if accrue_until > self.last_update_time {
+            let elapsed = accrue_until - self.last_update_time;
+            pending = u128::from(self.emission_rate)
+                .checked_mul(u128::from(elapsed))
+                .and_then(|emitted| emitted.checked_add(pending))
+                .ok_or(ErrorCode::Overflow)?;
+            self.last_update_time = accrue_until;
+        }
+        if pending == 0 {
+            return Ok(());
+        }
+        if self.total_weight == 0 {
+            self.carried_rewards =
+                u64::try_from(pending).map_err(|_| error!(ErrorCode::Overflow))?;
+            return Ok(());
+        }
+        let increment = pending
+            .checked_mul(REWARD_PRECISION)
+            .ok_or(ErrorCode::Overflow)?
+            .checked_div(self.total_weight)
+            .ok_or(ErrorCode::Overflow)?;
+        self.reward_per_weight_stored = self
+            .reward_per_weight_stored
+            .checked_add(increment)
+            .ok_or(ErrorCode::Overflow)?;
+        self.reward_liability = u64::try_from(pending)
+            .ok()
+            .and_then(|pending| self.reward_liability.checked_add(pending))
+            .ok_or(ErrorCode::Overflow)?;
+        self.carried_rewards = 0;
+        Ok(())
+    }
+
+    /// Release `amount` of the liability once it is paid out to, or forfeited by, a lock.
+    pub fn release(&mut self, amount: u64) -> Result<()> {
+        self.reward_liability = self
+            .reward_liability
+            .checked_sub(amount)
+            .ok_or(ErrorCode::UnderflowError)?;
+        Ok(())
+    }
+
+    /// Reward tokens in the vault, holding `vault_amount`, that are not owed to any lock.
+    /// Only sweepable after `end_time`, once nothing more is emitted.
+    pub fn undistributed(&self, vault_amount: u64) -> u64 {
+        vault_amount.saturating_sub(self.reward_liability)
+    }
+
+    /// Enroll `user_lock` in this pool, whose address is `reward_pool`, and weight it from `now`.
+    /// Only the lock's owner may enroll it.
+    pub fn enroll(&mut self, user_lock: &mut UserLock, reward_pool: Pubkey, now: u64) -> Result<()> {
+        user_lock.reward_pool = reward_pool;
+        self.checkpoint(user_lock, now)
+    }
+
+    /// Withdraw `user_lock`, whose rewards were just claimed, from this ended campaign so that
+    /// its owner can enroll it in another one.
+    pub fn unenroll(&mut self, user_lock: &mut UserLock) -> Result<()> {
+        self.total_weight = self
+            .total_weight
+            .checked_sub(u128::from(user_lock.reward_weight))
+            .ok_or(ErrorCode::UnderflowError)?;
+        user_lock.reward_weight = 0;
+        user_lock.reward_pool = Pubkey::default();
+        Ok(())
+    }
+
+    /// Settle the rewards earned by `user_lock` since its last checkpoint and
+    /// re-weight it according to its current amount and remaining duration.
+    /// Must be called after every change to the lock's amount or status.
+    ///
+    /// The weight of a timed lock decays as it approaches `unlock_time`, so the lock earns on its
+    /// checkpointed weight averaged over the accrual interval rather than on the weight it had at
+    /// the checkpoint. What the checkpointed weight would have earned on top is forfeited.
+    pub fn checkpoint(&mut self, user_lock: &mut UserLock, now: u64) -> Result<()> {
+        self.update(now)?;
+
+        let reward_per_weight = self
+            .reward_per_weight_stored
+            .checked_sub(user_lock.reward_per_weight_paid)
+            .ok_or(ErrorCode::UnderflowError)?;
+        let accrued_weight = u128::from(user_lock.reward_weight);
+        let earned_weight = if user_lock.reward_decays_until == 0 {
+            accrued_weight
+        } else {
+            let from = user_lock.reward_checkpoint_time;
+            let to = now.min(self.end_time).max(from);
+            accrued_weight
+                .checked_mul(u128::from(average_reward_multiplier_bps(
+                    user_lock.reward_decays_until,
+                    from,
+                    to,
+                )))
+                .ok_or(ErrorCode::Overflow)?
+                / u128::from(reward_multiplier_bps(
+                    user_lock.reward_decays_until.saturating_sub(from),
+                ))
+        }
+        .min(accrued_weight);
+        let accrued = accrued_weight
+            .checked_mul(reward_per_weight)
+            .ok_or(ErrorCode::Overflow)?
+            / REWARD_PRECISION;
+        let earned = earned_weight
+            .checked_mul(reward_per_weight)
+            .ok_or(ErrorCode::Overflow)?
+            / REWARD_PRECISION;
+        self.release(u64::try_from(accrued - earned).map_err(|_| error!(ErrorCode::Overflow))?)?;
+        user_lock.rewards_owed = user_lock
+            .rewards_owed
+            .checked_add(u64::try_from(earned).map_err(|_| error!(ErrorCode::Overflow))?)
+            .ok_or(ErrorCode::Overflow)?;
+        user_lock.reward_per_weight_paid = self.reward_per_weight_stored;
+
+        let new_weight = if user_lock.is_unlocked {
+            0
+        } else {
+            reward_weight(
+                user_lock.lock_amount,
+                user_lock.effective_unlock_time(now),
+                user_lock.is_locked_permanently,
+                now,
+            )?
+        };
+        self.total_weight = self
+            .total_weight
+            .checked_sub(u128::from(user_lock.reward_weight))
+            .ok_or(ErrorCode::UnderflowError)?
+            .checked_add(u128::from(new_weight))
+            .ok_or(ErrorCode::Overflow)?;
+        user_lock.reward_weight = new_weight;
+        user_lock.reward_checkpoint_time = now;
+        user_lock.reward_decays_until =
+            if user_lock.is_unlocked || user_lock.is_locked_permanently || user_lock.is_rolling {
+                0
+            } else {
+                user_lock.unlock_time
+            };
+        Ok(())
+    }
+}
+
+/// Rewards a lock had settled but not claimed when it was unlocked and closed, claimable by
+/// its owner with `claim_reward_receipt`, which closes the receipt.
+/// This account is uniquely identified by `user_lock`.
+#[account]
+#[derive(Default, Debug)]
+pub struct RewardReceipt {
+    pub bump: u8,            // PDA bump for address derivation
+    pub owner: Pubkey,       // Owner of the lock when it was unlocked
+    pub user_lock: Pubkey,   // Address of the closed lock
+    pub reward_pool: Pubkey, // Reward pool the rewards are paid from
+    pub amount: u64,         // Rewards owed to the owner
+}
+
+impl RewardReceipt {
+    /// Total space required for the RewardReceipt account (in bytes)
+    pub const LEN: usize = 8 +   // discriminator
+        1 +   // bump
+        32 +  // owner
+        32 +  // user_lock
+        32 +  // reward_pool
+        8; // amount
+
+    /// Move the rewards owed to the unlocked `user_lock`, at `user_lock_key`, into this receipt.
+    pub fn issue(&mut self, bump: u8, user_lock_key: Pubkey, user_lock: &mut UserLock) {
+        self.bump = bump;
+        self.owner = user_lock.user;
+        self.user_lock = user_lock_key;
+        self.reward_pool = user_lock.reward_pool;
+        self.amount = std::mem::take(&mut user_lock.rewards_owed);
+    }
+}
+
+/// Reward weight of a lock: `lock_amount` scaled linearly from the base to the max
+/// multiplier by the remaining lock duration. Permanent locks get the max multiplier.
+pub fn reward_weight(
+    lock_amount: u64,
+    unlock_time: u64,
+    is_locked_permanently: bool,
+    now: u64,
+) -> Result<u64> {
+    let multiplier_bps = if is_locked_permanently {
+        MAX_REWARD_MULTIPLIER_BPS
+    } else {
+        reward_multiplier_bps(unlock_time.saturating_sub(now))
+    };
+    let weight =
+        u128::from(lock_amount) * u128::from(multiplier_bps) / u128::from(BASIS_POINTS_DENOMINATOR);
+    u64::try_from(weight).map_err(|_| error!(ErrorCode::Overflow))
+}
+
+/// Weight multiplier (bps) of a lock with `remaining` seconds left until it unlocks.
+pub fn reward_multiplier_bps(remaining: u64) -> u64 {
+    let remaining = remaining.min(MAX_LOCK_DURATION);
+    BASE_REWARD_MULTIPLIER_BPS
+        + (MAX_REWARD_MULTIPLIER_BPS - BASE_REWARD_MULTIPLIER_BPS) * remaining / MAX_LOCK_DURATION
+}
+
+/// Average weight multiplier (bps) between `from` and `to` of a lock unlocking at `unlock_time`.
+pub fn average_reward_multiplier_bps(unlock_time: u64, from: u64, to: u64) -> u64 {
+    if to <= from {
+        return reward_multiplier_bps(unlock_time.saturating_sub(from));
+    }
+    let (from, to, unlock_time) = (u128::from(from), u128::from(to), u128::from(unlock_time));
+    let max_duration = u128::from(MAX_LOCK_DURATION);
+    // The remaining duration is capped at MAX_LOCK_DURATION until `decay_from`,
+    // then falls linearly to zero at `decay_until`
+    let decay_from = unlock_time.saturating_sub(max_duration).clamp(from, to);
+    let decay_until = unlock_time.clamp(from, to);
+    let remaining_integral = max_duration * (decay_from - from)
+        + ((unlock_time - decay_from).pow(2) - (unlock_time - decay_until).pow(2)) / 2;
+    let average_remaining = remaining_integral / (to - from);
+    reward_multiplier_bps(average_remaining as u64)
+}
+
+#[cfg(test)]
+pub mod reward_pool_test {
+    use super::*;
+
+    fn new_lock(lock_amount: u64, unlock_time: u64, is_locked_permanently: bool) -> UserLock {
+        UserLock {
+            lock_amount,
+            unlock_time,
+            is_locked_permanently,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn reward_weight_scales_with_remaining_duration() {
+        let now = 1_000;
+        assert_eq!(reward_weight(1_000, now, false, now).unwrap(), 1_000);
+        assert_eq!(
+            reward_weight(1_000, now + MAX_LOCK_DURATION / 2, false, now).unwrap(),
+            2_000
+        );
+        assert_eq!(
+            reward_weight(1_000, now + MAX_LOCK_DURATION, false, now).unwrap(),
+            3_000
+        );
+        assert_eq!(reward_weight(1_000, 0, true, now).unwrap(), 3_000);
+    }
+
+    #[test]
+    fn rewards_are_shared_pro_rata_to_weight() {
+        let mut pool = RewardPool {
+            emission_rate: 100,
+            start_time: 0,
+            end_time: 1_000,
+            ..Default::default()
+        };
+        let mut permanent = new_lock(1_000, 0, true);
+        let mut expired = new_lock(1_000, 0, false);
+        pool.checkpoint(&mut permanent, 0).unwrap();
+        pool.checkpoint(&mut expired, 0).unwrap();
+        assert_eq!(pool.total_weight, 4_000);
+
+        // emissions stop at end_time
+        pool.checkpoint(&mut permanent, 2_000).unwrap();
+        pool.checkpoint(&mut expired, 2_000).unwrap();
+        assert_eq!(permanent.rewards_owed, 75_000);
+        assert_eq!(expired.rewards_owed, 25_000);
+    }
+
+    #[test]
+    fn emissions_without_weight_carry_forward() {
+        let mut pool = RewardPool {
+            emission_rate: 30,
+            end_time: 1_000,
+            ..Default::default()
+        };
+        pool.update(100).unwrap();
+        assert_eq!(pool.carried_rewards, 3_000);
+
+        let mut user_lock = new_lock(1_000, 0, true);
+        pool.checkpoint(&mut user_lock, 100).unwrap();
+        pool.checkpoint(&mut user_lock, 200).unwrap();
+        assert_eq!(pool.carried_rewards, 0);
+        assert_eq!(user_lock.rewards_owed, 6_000);
+    }
+
+    #[test]
+    fn checkpoint_drops_multiplier_of_expired_lock() {
+        let mut pool = RewardPool {
+            emission_rate: 10,
+            end_time: 1_000,
+            ..Default::default()
+        };
+        let mut user_lock = new_lock(1_000, MAX_LOCK_DURATION, false);
+        pool.checkpoint(&mut user_lock, 0).unwrap();
+        assert_eq!(user_lock.reward_weight, 3_000);
+
+        pool.checkpoint(&mut user_lock, MAX_LOCK_DURATION).unwrap();
+        assert_eq!(user_lock.reward_weight, 1_000);
+        assert_eq!(pool.total_weight, 1_000);
+    }
+
+    #[test]
+    fn enroll_records_the_pool_and_weights_the_lock() {
+        let mut pool = RewardPool {
+            emission_rate: 10,
+            end_time: 1_000,
+            ..Default::default()
+        };
+        pool.update(100).unwrap();
+
+        let reward_pool = Pubkey::new_unique();
+        let mut user_lock = new_lock(1_000, 0, true);
+        pool.enroll(&mut user_lock, reward_pool, 100).unwrap();
+        assert_eq!(user_lock.reward_pool, reward_pool);
+        assert_eq!(user_lock.reward_weight, 3_000);
+        assert_eq!(pool.total_weight, 3_000);
+        // carried emissions go to the first enrolled lock from the next accrual
+        assert_eq!(user_lock.rewards_owed, 0);
+    }
+
+    #[test]
+    fn decaying_lock_earns_on_its_average_weight() {
+        let mut pool = RewardPool {
+            emission_rate: 6,
+            end_time: MAX_LOCK_DURATION,
+            ..Default::default()
+        };
+        let mut permanent = new_lock(1_000, 0, true);
+        let mut timed = new_lock(1_000, MAX_LOCK_DURATION, false);
+        pool.checkpoint(&mut permanent, 0).unwrap();
+        pool.checkpoint(&mut timed, 0).unwrap();
+        assert_eq!(pool.total_weight, 6_000);
+
+        // the timed lock decays from 3x to 1x, so it earns on an average 2x weight
+        pool.checkpoint(&mut permanent, MAX_LOCK_DURATION).unwrap();
+        pool.checkpoint(&mut timed, MAX_LOCK_DURATION).unwrap();
+        assert_eq!(permanent.rewards_owed, 3 * MAX_LOCK_DURATION);
+        assert_eq!(timed.rewards_owed, 2 * MAX_LOCK_DURATION);
+        // what its checkpointed weight would have earned on top stays sweepable
+        assert_eq!(pool.reward_liability, 5 * MAX_LOCK_DURATION);
+        assert_eq!(pool.undistributed(6 * MAX_LOCK_DURATION), MAX_LOCK_DURATION);
+    }
+
+    #[test]
+    fn expired_lock_earns_base_weight_after_unlock_time() {
+        assert_eq!(average_reward_multiplier_bps(100, 0, 100), 10_000);
+        assert_eq!(
+            average_reward_multiplier_bps(2 * MAX_LOCK_DURATION, 0, MAX_LOCK_DURATION),
+            30_000
+        );
+        // 3x decaying to 1x over the first half, 1x over the second half
+        assert_eq!(
+            average_reward_multiplier_bps(MAX_LOCK_DURATION, 0, 2 * MAX_LOCK_DURATION),
+            15_000
+        );
+    }
+
+    #[test]
+    fn unenroll_withdraws_the_lock_weight() {
+        let mut pool = RewardPool {
+            emission_rate: 10,
+            end_time: 1_000,
+            ..Default::default()
+        };
+        let mut user_lock = new_lock(1_000, 0, true);
+        pool.enroll(&mut user_lock, Pubkey::new_unique(), 0).unwrap();
+        pool.unenroll(&mut user_lock).unwrap();
+        assert_eq!(pool.total_weight, 0);
+        assert_eq!(user_lock.reward_weight, 0);
+        assert_eq!(user_lock.reward_pool, Pubkey::default());
+    }
+
+    #[test]
+    fn unlocked_lock_stops_earning() {
+        let mut pool = RewardPool {
+            emission_rate: 30,
+            end_time: 1_000,
+            ..Default::default()
+        };
+        let mut user_lock = new_lock(1_000, 0, true);
+        pool.checkpoint(&mut user_lock, 0).unwrap();
+
+        user_lock.is_unlocked = true;
+        pool.checkpoint(&mut user_lock, 100).unwrap();
+        assert_eq!(user_lock.reward_weight, 0);
+        assert_eq!(pool.total_weight, 0);
+        assert_eq!(user_lock.rewards_owed, 3_000);
+
+        pool.checkpoint(&mut user_lock, 500).unwrap();
+        assert_eq!(user_lock.rewards_owed, 3_000);
+    }
+}
@@ -0,0 +1,277 @@
+use crate::error::ErrorCode;
+use crate::states::{UserLock, MAX_LOCK_DURATION};
+use anchor_lang::prelude::*;
+
+/// Voting power of a lock at `timestamp`.
+/// Permanent locks carry their full `lock_amount`; timed locks carry
+/// `lock_amount * remaining / MAX_LOCK_DURATION`, decaying linearly to zero at unlock.
+pub fn voting_power(
+    lock_amount: u64,
+    unlock_time: u64,
+    is_locked_permanently: bool,
+    timestamp: u64,
+) -> u64 {
+    if is_locked_permanently {
+        return lock_amount;
+    }
+    let remaining = unlock_time.saturating_sub(timestamp).min(MAX_LOCK_DURATION);
+    (u128::from(lock_amount) * u128::from(remaining) / u128::from(MAX_LOCK_DURATION)) as u64
+}
+
+/// Aggregate voting power of a set of locks, updated on every lock mutation.
+///
+/// Timed locks are tracked as `sum(lock_amount * unlock_time)` and `sum(lock_amount)`, so the
+/// aggregate at any later timestamp is `(unlock_weighted_sum - decaying_amount * t) / MAX_LOCK_DURATION`.
+/// A timed lock that expires without being unlocked keeps reducing that term below its true
+/// value until it is unlocked; `voting_power_at` requires the correction for such locks.
+/// Rolling locks never decay, so their voting power is tracked as a constant sum.
+///
+/// Only the latest state is kept, so the aggregate answers for the time of its last change
+/// onward, not for past snapshots: governance reads it at the time it snapshots (e.g. now).
+/// Mutations that leave the aggregate unchanged keep its timestamp.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, Debug, PartialEq)]
+pub struct VotingPowerCheckpoint {
+    pub timestamp: u64,            // Timestamp of the last change to the aggregate
+    pub permanent_amount: u64,     // LP locked permanently
+    pub decaying_amount: u64,      // LP locked with an unlock time
+    pub unlock_weighted_sum: u128, // Sum of lock_amount * unlock_time over timed locks
+    pub rolling_power: u64,        // Voting power of rolling locks
+    pub decaying_count: u64,       // Number of timed locks in the aggregate
+}
+
+impl VotingPowerCheckpoint {
+    pub const LEN: usize = 8 + 8 + 8 + 16 + 8 + 8;
+
+    /// Add the contribution of `user_lock` to the aggregate.
+    pub fn add_lock(&mut self, user_lock: &UserLock, timestamp: u64) -> Result<()> {
+        if !user_lock.is_unlocked {
+            if user_lock.is_locked_permanently {
+                self.permanent_amount = self
+                    .permanent_amount
+                    .checked_add(user_lock.lock_amount)
+                    .ok_or(ErrorCode::Overflow)?;
+            } else if user_lock.is_rolling {
+                self.rolling_power = self
+                    .rolling_power
+                    .checked_add(rolling_voting_power(user_lock))
+                    .ok_or(ErrorCode::Overflow)?;
+            } else {
+                self.decaying_amount = self
+                    .decaying_amount
+                    .checked_add(user_lock.lock_amount)
+                    .ok_or(ErrorCode::Overflow)?;
+                self.unlock_weighted_sum = self
+                    .unlock_weighted_sum
+                    .checked_add(
+                        u128::from(user_lock.lock_amount) * u128::from(user_lock.unlock_time),
+                    )
+                    .ok_or(ErrorCode::Overflow)?;
+                self.decaying_count = self
+                    .decaying_count
+                    .checked_add(1)
+                    .ok_or(ErrorCode::Overflow)?;
+            }
+            self.touch(user_lock, timestamp);
+        }
+        Ok(())
+    }
+
+    /// Remove the contribution of `user_lock` from the aggregate.
+    /// Must be called with the lock's state as it was last added.
+    pub fn remove_lock(&mut self, user_lock: &UserLock, timestamp: u64) -> Result<()> {
+        if !user_lock.is_unlocked {
+            if user_lock.is_locked_permanently {
+                self.permanent_amount = self
+                    .permanent_amount
+                    .checked_sub(user_lock.lock_amount)
+                    .ok_or(ErrorCode::UnderflowError)?;
+            } else if user_lock.is_rolling {
+                self.rolling_power = self
+                    .rolling_power
+                    .checked_sub(rolling_voting_power(user_lock))
+                    .ok_or(ErrorCode::UnderflowError)?;
+            } else {
+                self.decaying_amount = self
+                    .decaying_amount
+                    .checked_sub(user_lock.lock_amount)
+                    .ok_or(ErrorCode::UnderflowError)?;
+                self.unlock_weighted_sum = self
+                    .unlock_weighted_sum
+                    .checked_sub(
+                        u128::from(user_lock.lock_amount) * u128::from(user_lock.unlock_time),
+                    )
+                    .ok_or(ErrorCode::UnderflowError)?;
+                self.decaying_count = self
+                    .decaying_count
+                    .checked_sub(1)
+                    .ok_or(ErrorCode::UnderflowError)?;
+            }
+            self.touch(user_lock, timestamp);
+        }
+        Ok(())
+    }
+
+    /// Record a change to the aggregate by `user_lock` at `timestamp`; empty locks change nothing.
+    fn touch(&mut self, user_lock: &UserLock, timestamp: u64) {
+        if user_lock.lock_amount > 0 {
+            self.timestamp = timestamp;
+        }
+    }
+
+    /// Keep the timestamp of `previous` if the aggregate is back to it, e.g. once a lock
+    /// was removed and re-added unchanged, so that queries valid before stay valid.
+    pub fn keep_timestamp_if_unchanged(&mut self, previous: &VotingPowerCheckpoint) {
+        let unchanged = VotingPowerCheckpoint {
+            timestamp: previous.timestamp,
+            ..*self
+        } == *previous;
+        if unchanged {
+            self.timestamp = previous.timestamp;
+        }
+    }
+
+    /// Aggregate voting power at `timestamp`, which must not precede the last change.
+    /// `expired_correction` is `sum(lock_amount * (timestamp - unlock_time))` over the
+    /// aggregated timed locks that have expired by `timestamp`; an incomplete correction
+    /// that leaves the decaying term negative is an error rather than zero power.
+    pub fn voting_power_at(&self, timestamp: u64, expired_correction: u128) -> Result<u64> {
+        require_gte!(
+            timestamp,
+            self.timestamp,
+            ErrorCode::VotingPowerTimestampTooEarly
+        );
+        let decaying_power = self
+            .unlock_weighted_sum
+            .checked_add(expired_correction)
+            .ok_or(ErrorCode::Overflow)?
+            .checked_sub(u128::from(self.decaying_amount) * u128::from(timestamp))
+            .ok_or(ErrorCode::UnderflowError)?
+            / u128::from(MAX_LOCK_DURATION);
+        u64::try_from(decaying_power)
+            .map_err(|_| error!(ErrorCode::Overflow))?
+            .checked_add(self.permanent_amount)
+            .and_then(|power| power.checked_add(self.rolling_power))
+            .ok_or(error!(ErrorCode::Overflow))
+    }
+}
+
+/// Constant voting power of a rolling lock, which always has `lock_duration` remaining.
+fn rolling_voting_power(user_lock: &UserLock) -> u64 {
+    voting_power(user_lock.lock_amount, user_lock.lock_duration, false, 0)
+}
+
+#[cfg(test)]
+pub mod voting_power_test {
+    use super::*;
+
+    fn new_lock(lock_amount: u64, unlock_time: u64, is_locked_permanently: bool) -> UserLock {
+        UserLock {
+            lock_amount,
+            unlock_time,
+            is_locked_permanently,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn voting_power_decays_toward_unlock() {
+        let unlock_time = 1_000 + MAX_LOCK_DURATION;
+        assert_eq!(voting_power(1_000, unlock_time, false, 1_000), 1_000);
+        assert_eq!(
+            voting_power(1_000, unlock_time, false, 1_000 + MAX_LOCK_DURATION / 4),
+            750
+        );
+        assert_eq!(voting_power(1_000, unlock_time, false, unlock_time), 0);
+        assert_eq!(voting_power(1_000, unlock_time, false, unlock_time + 1), 0);
+        assert_eq!(voting_power(1_000, 0, true, unlock_time), 1_000);
+    }
+
+    #[test]
+    fn checkpoint_matches_sum_of_locks() {
+        let half = MAX_LOCK_DURATION / 2;
+        let locks = [
+            new_lock(1_000, MAX_LOCK_DURATION, false),
+            new_lock(2_000, half, false),
+            new_lock(500, 0, true),
+        ];
+        let mut checkpoint = VotingPowerCheckpoint::default();
+        for lock in locks.iter() {
+            checkpoint.add_lock(lock, 0).unwrap();
+        }
+        for t in [0, half / 2, half] {
+            let expected: u64 = locks
+                .iter()
+                .map(|l| voting_power(l.lock_amount, l.unlock_time, l.is_locked_permanently, t))
+                .sum();
+            assert_eq!(checkpoint.voting_power_at(t, 0).unwrap(), expected);
+        }
+
+        checkpoint.remove_lock(&locks[1], half).unwrap();
+        assert_eq!(checkpoint.voting_power_at(half, 0).unwrap(), 500 + 500);
+        assert_eq!(
+            checkpoint.voting_power_at(half - 1, 0).unwrap_err(),
+            ErrorCode::VotingPowerTimestampTooEarly.into()
+        );
+    }
+
+    #[test]
+    fn unchanged_mutations_keep_the_timestamp() {
+        let user_lock = new_lock(1_000, MAX_LOCK_DURATION, false);
+        let mut checkpoint = VotingPowerCheckpoint::default();
+        checkpoint.add_lock(&user_lock, 100).unwrap();
+
+        let previous = checkpoint;
+        checkpoint.remove_lock(&user_lock, 200).unwrap();
+        checkpoint.add_lock(&user_lock, 200).unwrap();
+        checkpoint.keep_timestamp_if_unchanged(&previous);
+        assert_eq!(checkpoint.timestamp, 100);
+        assert!(checkpoint.voting_power_at(150, 0).is_ok());
+
+        checkpoint.add_lock(&new_lock(0, MAX_LOCK_DURATION, false), 300).unwrap();
+        assert_eq!(checkpoint.timestamp, 100);
+    }
+
+    #[test]
+    fn rolling_lock_power_does_not_decay() {
+        let half = MAX_LOCK_DURATION / 2;
+        let rolling = UserLock {
+            lock_amount: 1_000,
+            unlock_time: half,
+            lock_duration: half,
+            is_rolling: true,
+            ..Default::default()
+        };
+        let mut checkpoint = VotingPowerCheckpoint::default();
+        checkpoint.add_lock(&rolling, 0).unwrap();
+        checkpoint
+            .add_lock(&new_lock(1_000, half, false), 0)
+            .unwrap();
+        assert_eq!(checkpoint.voting_power_at(0, 0).unwrap(), 500 + 500);
+        assert_eq!(checkpoint.voting_power_at(half, 0).unwrap(), 500);
+
+        checkpoint.remove_lock(&rolling, half).unwrap();
+        assert_eq!(checkpoint.voting_power_at(half, 0).unwrap(), 0);
+    }
+
+    #[test]
+    fn expired_lock_correction_restores_exact_power() {
+        let half = MAX_LOCK_DURATION / 2;
+        let mut checkpoint = VotingPowerCheckpoint::default();
+        checkpoint
+            .add_lock(&new_lock(1_000, MAX_LOCK_DURATION, false), 0)
+            .unwrap();
+        checkpoint
+            .add_lock(&new_lock(1_000, half, false), 0)
+            .unwrap();
+
+        let t = MAX_LOCK_DURATION - MAX_LOCK_DURATION / 8;
+        assert_eq!(checkpoint.decaying_count, 2);
+        // leaving out the expired lock is an error, not a lower result
+        assert_eq!(
+            checkpoint.voting_power_at(t, 0).unwrap_err(),
+            ErrorCode::UnderflowError.into()
+        );
+        let correction = 1_000u128 * u128::from(t - half);
+        assert_eq!(checkpoint.voting_power_at(t, correction).unwrap(), 125);
+    }
+}
@@ -1,6 +1,8 @@
 ///! 128 and 256 bit numbers
 ///! U128 is more efficient that u128
 ///! https://github.com/solana-labs/solana/issues/19549
+use crate::error::ErrorCode;
+use anchor_lang::error;
 use uint::construct_uint;
 construct_uint! {
     pub struct U128(2);
@@ -45,3 +47,15 @@ impl CheckedCeilDiv for u128 {
         Some((quotient, rhs))
     }
 }
+
+/// Denominator for amounts expressed in basis points
+pub const BASIS_POINTS_DENOMINATOR: u64 = 10_000;
+
+/// Liquidity of a pair of token amounts, the square root of their product
+pub fn liquidity(token_0_amount: u64, token_1_amount: u64) -> anchor_lang::Result<u64> {
+    let liquidity = U128::from(token_0_amount)
+        .checked_mul(token_1_amount.into())
+        .ok_or(ErrorCode::Overflow)?
+        .integer_sqrt();
+    u64::try_from(liquidity).map_err(|_| error!(ErrorCode::Overflow))
+}
@@ -1,4 +1,7 @@
 pub mod math;
+pub mod raydium;
+pub mod time;
 pub mod token;
 pub use math::*;
+pub use time::*;
 pub use token::*;
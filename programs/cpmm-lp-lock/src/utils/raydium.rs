@@ -0,0 +1,251 @@
+use anchor_lang::prelude::borsh::BorshSerialize;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{instruction::Instruction, program::invoke_signed};
+
+#[derive(BorshSerialize)]
+struct Deposit {
+    lp_token_amount: u64,
+    maximum_token_0_amount: u64,
+    maximum_token_1_amount: u64,
+}
+
+#[derive(BorshSerialize)]
+struct Withdraw {
+    lp_token_amount: u64,
+    minimum_token_0_amount: u64,
+    minimum_token_1_amount: u64,
+}
+
+#[derive(BorshSerialize)]
+struct SwapBaseInput {
+    amount_in: u64,
+    minimum_amount_out: u64,
+}
+
+/// Accounts of the Raydium CPMM `deposit` instruction, in instruction order
+pub struct DepositAccounts<'info> {
+    pub owner: AccountInfo<'info>,
+    pub authority: AccountInfo<'info>,
+    pub pool_state: AccountInfo<'info>,
+    pub owner_lp_token: AccountInfo<'info>,
+    pub token_0_account: AccountInfo<'info>,
+    pub token_1_account: AccountInfo<'info>,
+    pub token_0_vault: AccountInfo<'info>,
+    pub token_1_vault: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+    pub token_program_2022: AccountInfo<'info>,
+    pub vault_0_mint: AccountInfo<'info>,
+    pub vault_1_mint: AccountInfo<'info>,
+    pub lp_mint: AccountInfo<'info>,
+}
+
+/// Accounts of the Raydium CPMM `withdraw` instruction, in instruction order
+pub struct WithdrawAccounts<'info> {
+    pub owner: AccountInfo<'info>,
+    pub authority: AccountInfo<'info>,
+    pub pool_state: AccountInfo<'info>,
+    pub owner_lp_token: AccountInfo<'info>,
+    pub token_0_account: AccountInfo<'info>,
+    pub token_1_account: AccountInfo<'info>,
+    pub token_0_vault: AccountInfo<'info>,
+    pub token_1_vault: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+    pub token_program_2022: AccountInfo<'info>,
+    pub vault_0_mint: AccountInfo<'info>,
+    pub vault_1_mint: AccountInfo<'info>,
+    pub lp_mint: AccountInfo<'info>,
+    pub memo_program: AccountInfo<'info>,
+}
+
+/// Accounts of the Raydium CPMM `swap_base_input` instruction, in instruction order
+pub struct SwapBaseInputAccounts<'info> {
+    pub payer: AccountInfo<'info>,
+    pub authority: AccountInfo<'info>,
+    pub amm_config: AccountInfo<'info>,
+    pub pool_state: AccountInfo<'info>,
+    pub input_token_account: AccountInfo<'info>,
+    pub output_token_account: AccountInfo<'info>,
+    pub input_vault: AccountInfo<'info>,
+    pub output_vault: AccountInfo<'info>,
+    pub input_token_program: AccountInfo<'info>,
+    pub output_token_program: AccountInfo<'info>,
+    pub input_token_mint: AccountInfo<'info>,
+    pub output_token_mint: AccountInfo<'info>,
+    pub observation_state: AccountInfo<'info>,
+}
+
+fn instruction_data<T: BorshSerialize>(name: &str, args: &T) -> Result<Vec<u8>> {
+    let mut data = anchor_lang::solana_program::hash::hash(format!("global:{}", name).as_bytes())
+        .to_bytes()[..8]
+        .to_vec();
+    data.extend(args.try_to_vec()?);
+    Ok(data)
+}
+
+/// Mint exactly `lp_token_amount` LP tokens to `owner_lp_token` for at most the given token amounts.
+pub fn deposit<'info>(
+    accounts: DepositAccounts<'info>,
+    lp_token_amount: u64,
+    maximum_token_0_amount: u64,
+    maximum_token_1_amount: u64,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let data = instruction_data(
+        "deposit",
+        &Deposit {
+            lp_token_amount,
+            maximum_token_0_amount,
+            maximum_token_1_amount,
+        },
+    )?;
+
+    let account_infos = vec![
+        accounts.owner,
+        accounts.authority,
+        accounts.pool_state,
+        accounts.owner_lp_token,
+        accounts.token_0_account,
+        accounts.token_1_account,
+        accounts.token_0_vault,
+        accounts.token_1_vault,
+        accounts.token_program,
+        accounts.token_program_2022,
+        accounts.vault_0_mint,
+        accounts.vault_1_mint,
+        accounts.lp_mint,
+    ];
+    let ix = Instruction {
+        program_id: crate::raydium_cpmm::id(),
+        accounts: vec![
+            AccountMeta::new_readonly(account_infos[0].key(), true),
+            AccountMeta::new_readonly(account_infos[1].key(), false),
+            AccountMeta::new(account_infos[2].key(), false),
+            AccountMeta::new(account_infos[3].key(), false),
+            AccountMeta::new(account_infos[4].key(), false),
+            AccountMeta::new(account_infos[5].key(), false),
+            AccountMeta::new(account_infos[6].key(), false),
+            AccountMeta::new(account_infos[7].key(), false),
+            AccountMeta::new_readonly(account_infos[8].key(), false),
+            AccountMeta::new_readonly(account_infos[9].key(), false),
+            AccountMeta::new_readonly(account_infos[10].key(), false),
+            AccountMeta::new_readonly(account_infos[11].key(), false),
+            AccountMeta::new(account_infos[12].key(), false),
+        ],
+        data,
+    };
+
+    invoke_signed(&ix, &account_infos, signer_seeds)?;
+    Ok(())
+}
+
+/// Burn `lp_token_amount` LP tokens from `owner_lp_token` for the underlying pool tokens.
+pub fn withdraw<'info>(
+    accounts: WithdrawAccounts<'info>,
+    lp_token_amount: u64,
+    minimum_token_0_amount: u64,
+    minimum_token_1_amount: u64,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let data = instruction_data(
+        "withdraw",
+        &Withdraw {
+            lp_token_amount,
+            minimum_token_0_amount,
+            minimum_token_1_amount,
+        },
+    )?;
+
+    let account_infos = vec![
+        accounts.owner,
+        accounts.authority,
+        accounts.pool_state,
+        accounts.owner_lp_token,
+        accounts.token_0_account,
+        accounts.token_1_account,
+        accounts.token_0_vault,
+        accounts.token_1_vault,
+        accounts.token_program,
+        accounts.token_program_2022,
+        accounts.vault_0_mint,
+        accounts.vault_1_mint,
+        accounts.lp_mint,
+        accounts.memo_program,
+    ];
+    let ix = Instruction {
+        program_id: crate::raydium_cpmm::id(),
+        accounts: vec![
+            AccountMeta::new_readonly(account_infos[0].key(), true),
+            AccountMeta::new_readonly(account_infos[1].key(), false),
+            AccountMeta::new(account_infos[2].key(), false),
+            AccountMeta::new(account_infos[3].key(), false),
+            AccountMeta::new(account_infos[4].key(), false),
+            AccountMeta::new(account_infos[5].key(), false),
+            AccountMeta::new(account_infos[6].key(), false),
+            AccountMeta::new(account_infos[7].key(), false),
+            AccountMeta::new_readonly(account_infos[8].key(), false),
+            AccountMeta::new_readonly(account_infos[9].key(), false),
+            AccountMeta::new_readonly(account_infos[10].key(), false),
+            AccountMeta::new_readonly(account_infos[11].key(), false),
+            AccountMeta::new(account_infos[12].key(), false),
+            AccountMeta::new_readonly(account_infos[13].key(), false),
+        ],
+        data,
+    };
+
+    invoke_signed(&ix, &account_infos, signer_seeds)?;
+    Ok(())
+}
+
+/// Swap exactly `amount_in` input tokens for at least `minimum_amount_out` output tokens.
+pub fn swap_base_input<'info>(
+    accounts: SwapBaseInputAccounts<'info>,
+    amount_in: u64,
+    minimum_amount_out: u64,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let data = instruction_data(
+        "swap_base_input",
+        &SwapBaseInput {
+            amount_in,
+            minimum_amount_out,
+        },
+    )?;
+
+    let account_infos = vec![
+        accounts.payer,
+        accounts.authority,
+        accounts.amm_config,
+        accounts.pool_state,
+        accounts.input_token_account,
+        accounts.output_token_account,
+        accounts.input_vault,
+        accounts.output_vault,
+        accounts.input_token_program,
+        accounts.output_token_program,
+        accounts.input_token_mint,
+        accounts.output_token_mint,
+        accounts.observation_state,
+    ];
+    let ix = Instruction {
+        program_id: crate::raydium_cpmm::id(),
+        accounts: vec![
+            AccountMeta::new_readonly(account_infos[0].key(), true),
+            AccountMeta::new_readonly(account_infos[1].key(), false),
+            AccountMeta::new_readonly(account_infos[2].key(), false),
+            AccountMeta::new(account_infos[3].key(), false),
+            AccountMeta::new(account_infos[4].key(), false),
+            AccountMeta::new(account_infos[5].key(), false),
+            AccountMeta::new(account_infos[6].key(), false),
+            AccountMeta::new(account_infos[7].key(), false),
+            AccountMeta::new_readonly(account_infos[8].key(), false),
+            AccountMeta::new_readonly(account_infos[9].key(), false),
+            AccountMeta::new_readonly(account_infos[10].key(), false),
+            AccountMeta::new_readonly(account_infos[11].key(), false),
+            AccountMeta::new(account_infos[12].key(), false),
+        ],
+        data,
+    };
+
+    invoke_signed(&ix, &account_infos, signer_seeds)?;
+    Ok(())
+}
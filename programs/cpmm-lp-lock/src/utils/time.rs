@@ -0,0 +1,14 @@
+use crate::error::ErrorCode;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::clock;
+
+/// Current block timestamp as an unsigned unix timestamp.
+pub fn block_timestamp() -> Result<u64> {
+    match clock::Clock::get() {
+        Ok(clock) => match clock.unix_timestamp.try_into() {
+            Ok(timestamp) => Ok(timestamp),
+            Err(_) => Err(error!(ErrorCode::InvalidTimestamp)),
+        },
+        Err(_) => Err(error!(ErrorCode::ClockUnavailable)),
+    }
+}
@@ -7,20 +7,19 @@ use anchor_spl::{
         spl_token_2022::{
             self,
             extension::{
-                transfer_fee::{TransferFeeConfig, MAX_FEE_BASIS_POINTS},
+                transfer_fee::{
+                    instruction::harvest_withheld_tokens_to_mint, TransferFeeAmount,
+                    TransferFeeConfig, MAX_FEE_BASIS_POINTS,
+                },
                 ExtensionType, StateWithExtensions,
             },
         },
     },
     token_interface::{
-        initialize_account3, spl_token_2022::extension::BaseStateWithExtensions,
-        InitializeAccount3, Mint,
+        close_account, initialize_account3, spl_token_2022::extension::BaseStateWithExtensions,
+        CloseAccount, InitializeAccount3, Mint,
     },
 };
-use std::collections::HashSet;
-
-const MINT_WHITELIST: [&'static str; 0] = [
-];
 
 pub fn transfer_from_user_to_pool_vault<'a>(
     authority: AccountInfo<'a>,
@@ -132,11 +131,18 @@ pub fn get_transfer_inverse_fee(mint_info: &AccountInfo, post_fee_amount: u64) -
         return err!(ErrorCode::InvalidInput);
     }
     let mint_data = mint_info.try_borrow_data()?;
-    let mint = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&mint_data)?;
+    calculate_transfer_inverse_fee(&mint_data, Clock::get()?.epoch, post_fee_amount)
+}
 
-    let fee = if let Ok(transfer_fee_config) = mint.get_extension::<TransferFeeConfig>() {
-        let epoch = Clock::get()?.epoch;
+/// Calculate the fee for output amount from Token-2022 mint data at `epoch`
+pub fn calculate_transfer_inverse_fee(
+    mint_data: &[u8],
+    epoch: u64,
+    post_fee_amount: u64,
+) -> Result<u64> {
+    let mint = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(mint_data)?;
 
+    let fee = if let Ok(transfer_fee_config) = mint.get_extension::<TransferFeeConfig>() {
         let transfer_fee = transfer_fee_config.get_epoch_fee(epoch);
         if u16::from(transfer_fee.transfer_fee_basis_points) == MAX_FEE_BASIS_POINTS {
             u64::from(transfer_fee.maximum_fee)
@@ -157,11 +163,16 @@ pub fn get_transfer_fee(mint_info: &AccountInfo, pre_fee_amount: u64) -> Result<
         return Ok(0);
     }
     let mint_data = mint_info.try_borrow_data()?;
-    let mint = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&mint_data)?;
+    calculate_transfer_fee(&mint_data, Clock::get()?.epoch, pre_fee_amount)
+}
+
+/// Calculate the fee for input amount from Token-2022 mint data at `epoch`
+pub fn calculate_transfer_fee(mint_data: &[u8], epoch: u64, pre_fee_amount: u64) -> Result<u64> {
+    let mint = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(mint_data)?;
 
     let fee = if let Ok(transfer_fee_config) = mint.get_extension::<TransferFeeConfig>() {
         transfer_fee_config
-            .calculate_epoch_fee(Clock::get()?.epoch, pre_fee_amount)
+            .calculate_epoch_fee(epoch, pre_fee_amount)
             .unwrap()
     } else {
         0
@@ -169,15 +180,20 @@ pub fn get_transfer_fee(mint_info: &AccountInfo, pre_fee_amount: u64) -> Result<
     Ok(fee)
 }
 
+/// Calculate the amount actually received when `pre_fee_amount` is transferred
+pub fn get_amount_after_transfer_fee(mint_info: &AccountInfo, pre_fee_amount: u64) -> Result<u64> {
+    Ok(pre_fee_amount
+        .checked_sub(get_transfer_fee(mint_info, pre_fee_amount)?)
+        .ok_or(ErrorCode::UnderflowError)?)
+}
+
+/// Whether a mint only uses Token-2022 extensions the locker supports.
+/// Mints failing this check can still be allowed through the pool registry.
 pub fn is_supported_mint(mint_account: &InterfaceAccount<Mint>) -> Result<bool> {
     let mint_info = mint_account.to_account_info();
     if *mint_info.owner == Token::id() {
         return Ok(true);
     }
-    let mint_whitelist: HashSet<&str> = MINT_WHITELIST.into_iter().collect();
-    if mint_whitelist.contains(mint_account.key().to_string().as_str()) {
-        return Ok(true);
-    }
     let mint_data = mint_info.try_borrow_data()?;
     let mint = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&mint_data)?;
     let extensions = mint.get_extension_types()?;
@@ -238,3 +254,114 @@ pub fn create_token_account<'a>(
         },
     ))
 }
+
+/// Close an empty token account owned by `authority` and send its rent to `destination`.
+/// Transfer fees withheld in a Token-2022 account are harvested to the mint first, since the
+/// account cannot be closed while it still holds any.
+pub fn close_token_account<'a>(
+    authority: &AccountInfo<'a>,
+    token_account: &AccountInfo<'a>,
+    destination: &AccountInfo<'a>,
+    mint: &AccountInfo<'a>,
+    token_program: &AccountInfo<'a>,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    if *token_program.key == token_2022::Token2022::id() {
+        let withheld_amount = {
+            let account_data = token_account.try_borrow_data()?;
+            let account_state =
+                StateWithExtensions::<spl_token_2022::state::Account>::unpack(&account_data)?;
+            account_state
+                .get_extension::<TransferFeeAmount>()
+                .map_or(0, |transfer_fee_amount| {
+                    u64::from(transfer_fee_amount.withheld_amount)
+                })
+        };
+        if withheld_amount > 0 {
+            anchor_lang::solana_program::program::invoke(
+                &harvest_withheld_tokens_to_mint(token_program.key, mint.key, &[token_account.key])?,
+                &[mint.clone(), token_account.clone(), token_program.clone()],
+            )?;
+        }
+    }
+    close_account(CpiContext::new_with_signer(
+        token_program.clone(),
+        CloseAccount {
+            account: token_account.clone(),
+            destination: destination.clone(),
+            authority: authority.clone(),
+        },
+        signer_seeds,
+    ))
+}
+
+#[cfg(test)]
+pub mod token_test {
+    use super::*;
+    use anchor_lang::solana_program::program_option::COption;
+    use spl_token_2022::{
+        extension::{
+            transfer_fee::TransferFee, BaseStateWithExtensionsMut, StateWithExtensionsMut,
+        },
+        state::Mint as MintState,
+    };
+
+    fn new_mint_data(transfer_fee: Option<(u16, u64)>) -> Vec<u8> {
+        let extensions: Vec<ExtensionType> = transfer_fee
+            .iter()
+            .map(|_| ExtensionType::TransferFeeConfig)
+            .collect();
+        let space = ExtensionType::try_calculate_account_len::<MintState>(&extensions).unwrap();
+        let mut mint_data = vec![0u8; space];
+        let mut mint = StateWithExtensionsMut::<MintState>::unpack_uninitialized(&mut mint_data).unwrap();
+        if let Some((transfer_fee_basis_points, maximum_fee)) = transfer_fee {
+            let transfer_fee = TransferFee {
+                epoch: 0.into(),
+                maximum_fee: maximum_fee.into(),
+                transfer_fee_basis_points: transfer_fee_basis_points.into(),
+            };
+            let transfer_fee_config = mint.init_extension::<TransferFeeConfig>(true).unwrap();
+            transfer_fee_config.older_transfer_fee = transfer_fee;
+            transfer_fee_config.newer_transfer_fee = transfer_fee;
+        }
+        mint.base = MintState {
+            mint_authority: COption::None,
+            supply: 0,
+            decimals: 6,
+            is_initialized: true,
+            freeze_authority: COption::None,
+        };
+        mint.pack_base();
+        mint.init_account_type().unwrap();
+        mint_data
+    }
+
+    #[test]
+    fn transfer_fee_is_withheld_from_transfer_fee_mints() {
+        // 1% fee capped at 5,000
+        let mint_data = new_mint_data(Some((100, 5_000)));
+        assert_eq!(calculate_transfer_fee(&mint_data, 1, 10_000).unwrap(), 100);
+        assert_eq!(calculate_transfer_fee(&mint_data, 1, 10_001).unwrap(), 101);
+        assert_eq!(calculate_transfer_fee(&mint_data, 1, 1_000_000_000).unwrap(), 5_000);
+
+        // Grossing up by the inverse fee delivers the net amount after the fee
+        for post_fee_amount in [1, 9_900, 123_456, 1_000_000_000] {
+            let fee = calculate_transfer_inverse_fee(&mint_data, 1, post_fee_amount).unwrap();
+            let pre_fee_amount = post_fee_amount + fee;
+            assert_eq!(
+                pre_fee_amount - calculate_transfer_fee(&mint_data, 1, pre_fee_amount).unwrap(),
+                post_fee_amount
+            );
+        }
+    }
+
+    #[test]
+    fn transfer_fee_is_zero_without_transfer_fee_config() {
+        let mint_data = new_mint_data(None);
+        assert_eq!(calculate_transfer_fee(&mint_data, 1, 10_000).unwrap(), 0);
+        assert_eq!(calculate_transfer_inverse_fee(&mint_data, 1, 10_000).unwrap(), 0);
+
+        let mint_data = new_mint_data(Some((0, 0)));
+        assert_eq!(calculate_transfer_fee(&mint_data, 1, 10_000).unwrap(), 0);
+    }
+}
@@ -0,0 +1,152 @@
+//! Sample program showing how a launchpad locks LP in `cpmm-lp-lock` through CPI,
+//! with a PDA of its own as the lock owner.
+use anchor_lang::prelude::*;
+use cpmm_lp_lock::cpi::accounts::LockLp;
+use cpmm_lp_lock::program::CpmmLpLock;
+use cpmm_lp_lock::states::CreatedLock;
+
+declare_id!("D5kbiJFTcwGUvLKb2MVuZDCFZYQjVPuyYj8vaAnVLoAf");
+
+/// Seed of the PDA that holds the LP and owns the locks
+pub const LOCKER_SEED: &str = "locker";
+
+#[program]
+pub mod lp_lock_caller {
+    use super::*;
+
+    /// Lock `amount` of the locker's LP for `lock_duration` seconds, signing as the locker
+    /// PDA, and pass on the lock created by the lock program.
+    pub fn lock_for_pda<'info>(
+        ctx: Context<'_, '_, '_, 'info, LockForPda<'info>>,
+        amount: u64,
+        lock_duration: u64,
+    ) -> Result<CreatedLock> {
+        let accounts = &ctx.accounts;
+        let cpi_accounts = LockLp {
+            owner: accounts.locker.to_account_info(),
+            payer: accounts.payer.to_account_info(),
+            authority: accounts.authority.to_account_info(),
+            lock_vault_authority: accounts.lock_vault_authority.to_account_info(),
+            pool_state: accounts.pool_state.to_account_info(),
+            owner_lp_token: accounts.owner_lp_token.to_account_info(),
+            token_program: accounts.token_program.to_account_info(),
+            lp_mint: accounts.lp_mint.to_account_info(),
+            lp_lock_counter: accounts.lp_lock_counter.to_account_info(),
+            user_lp_lock: accounts.user_lp_lock.to_account_info(),
+            pool_lock_stats: accounts.pool_lock_stats.to_account_info(),
+            global_lock_counter: accounts.global_lock_counter.to_account_info(),
+            lock_id_index: accounts.lock_id_index.to_account_info(),
+            active_lock_page: accounts.active_lock_page.to_account_info(),
+            lp_lock_vault: accounts.lp_lock_vault.to_account_info(),
+            token_0_vault: accounts.token_0_vault.to_account_info(),
+            token_1_vault: accounts.token_1_vault.to_account_info(),
+            vault_0_mint: accounts.vault_0_mint.to_account_info(),
+            vault_1_mint: accounts.vault_1_mint.to_account_info(),
+            registry_config: accounts.registry_config.to_account_info(),
+            pool_registry_entry: accounts.pool_registry_entry.to_account_info(),
+            vault_0_mint_registry_entry: accounts.vault_0_mint_registry_entry.to_account_info(),
+            vault_1_mint_registry_entry: accounts.vault_1_mint_registry_entry.to_account_info(),
+            reward_pool: accounts
+                .reward_pool
+                .as_ref()
+                .map(|reward_pool| reward_pool.to_account_info()),
+            system_program: accounts.system_program.to_account_info(),
+        };
+        let signer_seeds: &[&[&[u8]]] = &[&[LOCKER_SEED.as_bytes(), &[ctx.bumps.locker]]];
+
+        let created_lock = cpmm_lp_lock::cpi::lock_lp(
+            CpiContext::new_with_signer(
+                accounts.lock_program.to_account_info(),
+                cpi_accounts,
+                signer_seeds,
+            ),
+            amount,
+            lock_duration,
+        )?
+        .get();
+        require_keys_eq!(created_lock.user_lock, accounts.user_lp_lock.key());
+
+        msg!(
+            "locked {} LP as lock {} ({})",
+            amount,
+            created_lock.lock_id,
+            created_lock.user_lock
+        );
+        Ok(created_lock)
+    }
+}
+
+#[derive(Accounts)]
+pub struct LockForPda<'info> {
+    /// Pays rent for the accounts created by the lock
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: PDA of this program that owns the LP and the new lock
+    #[account(
+        seeds = [
+            LOCKER_SEED.as_bytes(),
+        ],
+        bump,
+    )]
+    pub locker: UncheckedAccount<'info>,
+
+    pub lock_program: Program<'info, CpmmLpLock>,
+
+    // The accounts below are forwarded to `lock_lp` as they are and checked by the lock program
+    /// CHECK: pool vault and lp mint authority
+    pub authority: UncheckedAccount<'info>,
+    /// CHECK: lock vault authority
+    pub lock_vault_authority: UncheckedAccount<'info>,
+    /// CHECK: Raydium pool state account
+    pub pool_state: UncheckedAccount<'info>,
+    /// CHECK: LP token account of the locker
+    #[account(mut)]
+    pub owner_lp_token: UncheckedAccount<'info>,
+    /// CHECK: token program
+    pub token_program: UncheckedAccount<'info>,
+    /// CHECK: LP token mint
+    pub lp_mint: UncheckedAccount<'info>,
+    /// CHECK: lock counter of the locker
+    #[account(mut)]
+    pub lp_lock_counter: UncheckedAccount<'info>,
+    /// CHECK: the new lock
+    #[account(mut)]
+    pub user_lp_lock: UncheckedAccount<'info>,
+    /// CHECK: lock stats of the LP mint
+    #[account(mut)]
+    pub pool_lock_stats: UncheckedAccount<'info>,
+    /// CHECK: program-wide lock counter
+    #[account(mut)]
+    pub global_lock_counter: UncheckedAccount<'info>,
+    /// CHECK: index of the new lock's program-wide ID
+    #[account(mut)]
+    pub lock_id_index: UncheckedAccount<'info>,
+    /// CHECK: active-lock index page of the new lock
+    #[account(mut)]
+    pub active_lock_page: UncheckedAccount<'info>,
+    /// CHECK: vault of the new lock
+    #[account(mut)]
+    pub lp_lock_vault: UncheckedAccount<'info>,
+    /// CHECK: pool vault of token_0
+    pub token_0_vault: UncheckedAccount<'info>,
+    /// CHECK: pool vault of token_1
+    pub token_1_vault: UncheckedAccount<'info>,
+    /// CHECK: mint of token_0
+    pub vault_0_mint: UncheckedAccount<'info>,
+    /// CHECK: mint of token_1
+    pub vault_1_mint: UncheckedAccount<'info>,
+    /// CHECK: pool registry settings
+    pub registry_config: UncheckedAccount<'info>,
+    /// CHECK: registry entry of the pool
+    pub pool_registry_entry: UncheckedAccount<'info>,
+    /// CHECK: registry entry of the token_0 mint
+    pub vault_0_mint_registry_entry: UncheckedAccount<'info>,
+    /// CHECK: registry entry of the token_1 mint
+    pub vault_1_mint_registry_entry: UncheckedAccount<'info>,
+    /// CHECK: reward pool of the LP mint, if one has been created
+    #[account(mut)]
+    pub reward_pool: Option<UncheckedAccount<'info>>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,326 @@
+//! Runs `lock_for_pda` and `lock_lp` together in a program-test bank, with a Raydium pool
+//! and its mints set up as accounts, so the lock is created for the locker PDA signing
+//! through CPI, paid for by a separate payer, and handed back through return data.
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::entrypoint::ProgramResult;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::program_option::COption;
+use anchor_lang::solana_program::program_pack::Pack;
+use anchor_lang::{system_program, Discriminator, InstructionData, ToAccountMetas};
+use anchor_spl::token::spl_token;
+use cpmm_lp_lock::states::*;
+use lp_lock_caller::LOCKER_SEED;
+use solana_program_test::{processor, BanksClientError, ProgramTest, ProgramTestContext};
+use solana_sdk::account::Account;
+use solana_sdk::signature::Signer;
+use solana_sdk::transaction::Transaction;
+
+const LP_SUPPLY: u64 = 1_000_000_000;
+const LOCKER_LP: u64 = 1_000_000;
+const VAULT_AMOUNT: u64 = 50_000_000;
+
+/// Native entrypoints take accounts of any lifetime, Anchor's ties them to the slice
+fn lock_program_entry<'info>(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo<'info>],
+    data: &[u8],
+) -> ProgramResult {
+    let accounts = Box::leak(Box::new(accounts.to_vec()));
+    cpmm_lp_lock::entry(program_id, accounts, data)
+}
+
+fn caller_program_entry<'info>(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo<'info>],
+    data: &[u8],
+) -> ProgramResult {
+    let accounts = Box::leak(Box::new(accounts.to_vec()));
+    lp_lock_caller::entry(program_id, accounts, data)
+}
+
+fn locker() -> Pubkey {
+    Pubkey::find_program_address(&[LOCKER_SEED.as_bytes()], &lp_lock_caller::ID).0
+}
+
+fn lock_program_address(seeds: &[&[u8]]) -> Pubkey {
+    Pubkey::find_program_address(seeds, &cpmm_lp_lock::ID).0
+}
+
+/// A Raydium pool with plain SPL token mints, and LP held by the locker
+struct Pool {
+    pool_state: Pubkey,
+    lp_mint: Pubkey,
+    mint_0: Pubkey,
+    mint_1: Pubkey,
+    token_0_vault: Pubkey,
+    token_1_vault: Pubkey,
+    locker_lp_token: Pubkey,
+}
+
+fn token_program_account<T: Pack>(state: T) -> Account {
+    let mut data = vec![0u8; T::LEN];
+    state.pack_into_slice(&mut data);
+    Account {
+        lamports: Rent::default().minimum_balance(T::LEN),
+        data,
+        owner: spl_token::ID,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+fn mint(mint_authority: Pubkey, supply: u64, decimals: u8) -> Account {
+    token_program_account(spl_token::state::Mint {
+        mint_authority: COption::Some(mint_authority),
+        supply,
+        decimals,
+        is_initialized: true,
+        freeze_authority: COption::None,
+    })
+}
+
+fn token_account(mint: Pubkey, owner: Pubkey, amount: u64) -> Account {
+    token_program_account(spl_token::state::Account {
+        mint,
+        owner,
+        amount,
+        state: spl_token::state::AccountState::Initialized,
+        ..Default::default()
+    })
+}
+
+fn add_pool(program_test: &mut ProgramTest) -> Pool {
+    let pool = Pool {
+        pool_state: Pubkey::new_unique(),
+        lp_mint: Pubkey::new_unique(),
+        mint_0: Pubkey::new_unique(),
+        mint_1: Pubkey::new_unique(),
+        token_0_vault: Pubkey::new_unique(),
+        token_1_vault: Pubkey::new_unique(),
+        locker_lp_token: Pubkey::new_unique(),
+    };
+    let authority = cpmm_lp_lock::vault_and_lp_mint_auth::ID;
+
+    let pool_state = PoolState {
+        pool_creator: Pubkey::new_unique(),
+        token_0_vault: pool.token_0_vault,
+        token_1_vault: pool.token_1_vault,
+        lp_mint: pool.lp_mint,
+        token_0_mint: pool.mint_0,
+        token_1_mint: pool.mint_1,
+        token_0_program: spl_token::ID,
+        token_1_program: spl_token::ID,
+        lp_mint_decimals: 9,
+        mint_0_decimals: 6,
+        mint_1_decimals: 6,
+        lp_supply: LP_SUPPLY,
+        ..Default::default()
+    };
+    let mut data = PoolState::DISCRIMINATOR.to_vec();
+    data.extend_from_slice(bytemuck::bytes_of(&pool_state));
+    program_test.add_account(
+        pool.pool_state,
+        Account {
+            lamports: Rent::default().minimum_balance(data.len()),
+            data,
+            owner: cpmm_lp_lock::raydium_cpmm::ID,
+            executable: false,
+            rent_epoch: 0,
+        },
+    );
+
+    program_test.add_account(pool.lp_mint, mint(authority, LP_SUPPLY, 9));
+    program_test.add_account(pool.mint_0, mint(Pubkey::new_unique(), VAULT_AMOUNT, 6));
+    program_test.add_account(pool.mint_1, mint(Pubkey::new_unique(), VAULT_AMOUNT, 6));
+    program_test.add_account(
+        pool.token_0_vault,
+        token_account(pool.mint_0, authority, VAULT_AMOUNT),
+    );
+    program_test.add_account(
+        pool.token_1_vault,
+        token_account(pool.mint_1, authority, VAULT_AMOUNT),
+    );
+    program_test.add_account(
+        pool.locker_lp_token,
+        token_account(pool.lp_mint, locker(), LOCKER_LP),
+    );
+    pool
+}
+
+async fn start() -> (ProgramTestContext, Pool) {
+    let mut program_test = ProgramTest::new(
+        "lp_lock_caller",
+        lp_lock_caller::ID,
+        processor!(caller_program_entry),
+    );
+    program_test.add_program(
+        "cpmm_lp_lock",
+        cpmm_lp_lock::ID,
+        processor!(lock_program_entry),
+    );
+    let pool = add_pool(&mut program_test);
+    (program_test.start_with_context().await, pool)
+}
+
+/// Accounts of `lock_for_pda` creating the locker's `lock_count`th lock, program-wide `lock_id`
+fn lock_for_pda_accounts(
+    payer: Pubkey,
+    pool: &Pool,
+    lock_count: u64,
+    lock_id: u64,
+) -> lp_lock_caller::accounts::LockForPda {
+    let locker = locker();
+    let lp_mint = pool.lp_mint;
+    let registry_entry =
+        |key: &Pubkey| lock_program_address(&[REGISTRY_ENTRY_SEED.as_bytes(), key.as_ref()]);
+    lp_lock_caller::accounts::LockForPda {
+        payer,
+        locker,
+        lock_program: cpmm_lp_lock::ID,
+        authority: cpmm_lp_lock::vault_and_lp_mint_auth::ID,
+        lock_vault_authority: lock_program_address(&[cpmm_lp_lock::AUTH_SEED.as_bytes()]),
+        pool_state: pool.pool_state,
+        owner_lp_token: pool.locker_lp_token,
+        token_program: spl_token::ID,
+        lp_mint,
+        lp_lock_counter: lock_program_address(&[
+            LP_LOCK_COUNTER_SEED.as_bytes(),
+            locker.as_ref(),
+            lp_mint.as_ref(),
+        ]),
+        user_lp_lock: lock_program_address(&[
+            USER_LOCK_SEED.as_bytes(),
+            locker.as_ref(),
+            lp_mint.as_ref(),
+            &lock_count.to_le_bytes(),
+        ]),
+        pool_lock_stats: lock_program_address(&[POOL_LOCK_STATS_SEED.as_bytes(), lp_mint.as_ref()]),
+        global_lock_counter: lock_program_address(&[GLOBAL_LOCK_COUNTER_SEED.as_bytes()]),
+        lock_id_index: lock_program_address(&[LOCK_ID_SEED.as_bytes(), &lock_id.to_le_bytes()]),
+        active_lock_page: lock_program_address(&[
+            ACTIVE_LOCK_PAGE_SEED.as_bytes(),
+            locker.as_ref(),
+            lp_mint.as_ref(),
+            &ActiveLockPage::page_of(lock_count).to_le_bytes(),
+        ]),
+        lp_lock_vault: lock_program_address(&[
+            cpmm_lp_lock::LP_LOCK_VAULT_SEED.as_bytes(),
+            locker.as_ref(),
+            lp_mint.as_ref(),
+            &lock_count.to_le_bytes(),
+        ]),
+        token_0_vault: pool.token_0_vault,
+        token_1_vault: pool.token_1_vault,
+        vault_0_mint: pool.mint_0,
+        vault_1_mint: pool.mint_1,
+        registry_config: lock_program_address(&[REGISTRY_CONFIG_SEED.as_bytes()]),
+        pool_registry_entry: registry_entry(&pool.pool_state),
+        vault_0_mint_registry_entry: registry_entry(&pool.mint_0),
+        vault_1_mint_registry_entry: registry_entry(&pool.mint_1),
+        reward_pool: None,
+        system_program: system_program::ID,
+    }
+}
+
+fn lock_for_pda_instruction(
+    accounts: &lp_lock_caller::accounts::LockForPda,
+    amount: u64,
+) -> Instruction {
+    Instruction {
+        program_id: lp_lock_caller::ID,
+        accounts: accounts.to_account_metas(None),
+        data: lp_lock_caller::instruction::LockForPda {
+            amount,
+            lock_duration: 86_400,
+        }
+        .data(),
+    }
+}
+
+async fn get_account(context: &mut ProgramTestContext, address: Pubkey) -> Option<Account> {
+    context.banks_client.get_account(address).await.unwrap()
+}
+
+async fn token_amount(context: &mut ProgramTestContext, address: Pubkey) -> u64 {
+    let account = get_account(context, address).await.unwrap();
+    spl_token::state::Account::unpack(&account.data)
+        .unwrap()
+        .amount
+}
+
+#[tokio::test]
+async fn lock_for_pda_locks_as_the_locker_and_returns_the_created_lock() {
+    let (mut context, pool) = start().await;
+    // A sponsor distinct from the locker pays for every account the lock creates
+    let sponsor = context.payer.insecure_clone();
+    let sponsor_lamports = get_account(&mut context, sponsor.pubkey())
+        .await
+        .unwrap()
+        .lamports;
+
+    let accounts = lock_for_pda_accounts(sponsor.pubkey(), &pool, 1, 1);
+    let transaction = Transaction::new_signed_with_payer(
+        &[lock_for_pda_instruction(&accounts, LOCKER_LP)],
+        Some(&sponsor.pubkey()),
+        &[&sponsor],
+        context.last_blockhash,
+    );
+    let result = context
+        .banks_client
+        .process_transaction_with_metadata(transaction)
+        .await
+        .unwrap();
+    result.result.unwrap();
+
+    // The created lock comes back through the caller's return data
+    let return_data = result.metadata.unwrap().return_data.unwrap();
+    assert_eq!(return_data.program_id, lp_lock_caller::ID);
+    let created_lock = CreatedLock::try_from_slice(&return_data.data).unwrap();
+    assert_eq!(
+        created_lock,
+        CreatedLock {
+            user_lock: accounts.user_lp_lock,
+            lock_count: 1,
+            lock_id: 1,
+        }
+    );
+
+    let user_lock_account = get_account(&mut context, accounts.user_lp_lock)
+        .await
+        .unwrap();
+    let user_lock = UserLock::try_deserialize(&mut &user_lock_account.data[..]).unwrap();
+    assert_eq!(user_lock.user, locker());
+    assert_eq!(user_lock.rent_payer, sponsor.pubkey());
+    assert_eq!(user_lock.lock_amount, LOCKER_LP);
+    assert_eq!(user_lock.lock_id, 1);
+    assert!(!user_lock.is_creator_lock);
+
+    assert_eq!(
+        token_amount(&mut context, accounts.lp_lock_vault).await,
+        LOCKER_LP
+    );
+    assert_eq!(token_amount(&mut context, pool.locker_lp_token).await, 0);
+    // The locker PDA holds no SOL and paid for nothing
+    assert!(get_account(&mut context, locker()).await.is_none());
+    let sponsor_account = get_account(&mut context, sponsor.pubkey()).await.unwrap();
+    assert!(sponsor_account.lamports < sponsor_lamports);
+}
+
+#[tokio::test]
+async fn lock_for_pda_rejects_a_lock_at_another_address() {
+    let (mut context, pool) = start().await;
+    // The locker has no lock yet, so its second lock cannot be created
+    let accounts = lock_for_pda_accounts(context.payer.pubkey(), &pool, 2, 1);
+    let transaction = Transaction::new_signed_with_payer(
+        &[lock_for_pda_instruction(&accounts, LOCKER_LP)],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+    let result = context.banks_client.process_transaction(transaction).await;
+    assert!(matches!(result, Err(BanksClientError::TransactionError(_))));
+    assert_eq!(
+        token_amount(&mut context, pool.locker_lp_token).await,
+        LOCKER_LP
+    );
+}